@@ -0,0 +1,102 @@
+use crate::ui::Ui;
+use anyhow::Result;
+use colored::Colorize;
+use dialoguer::Password;
+use rhinolabs_core::{GitHubCredentials, TokenSource, TokenStore};
+
+/// Show which GitHub token is used, if any
+pub fn status() -> Result<()> {
+    Ui::header("GitHub Authentication");
+
+    match GitHubCredentials::resolve() {
+        Some(token) => {
+            println!("  {} {}", "Token:".bold(), mask(&token.token));
+            println!("  {} {}", "Source:".bold(), token.source);
+            println!();
+
+            if matches!(
+                token.source,
+                TokenSource::GithubTokenEnv | TokenSource::GhTokenEnv
+            ) {
+                Ui::info("Environment variables take precedence over stored tokens.");
+            }
+        }
+        None => {
+            Ui::warning("No GitHub token configured");
+            println!();
+            Ui::info("Public repositories work without a token, subject to lower rate limits.");
+            Ui::info("Private repositories and deploys need one:");
+            println!("  - Set GITHUB_TOKEN or GH_TOKEN");
+            println!("  - Run 'gh auth login'");
+            println!("  - Run 'rhinolabs-ai auth login'");
+        }
+    }
+
+    Ok(())
+}
+
+/// Store a GitHub token in the OS keyring (or the secret file as fallback)
+pub fn login(token: Option<String>) -> Result<()> {
+    Ui::header("GitHub Login");
+
+    let token = match token {
+        Some(token) => token,
+        None => Password::new()
+            .with_prompt("GitHub token (needs 'repo' scope for private repositories)")
+            .interact()?,
+    };
+    // Checked before the keyring, so a bad token isn't mistaken for a
+    // keyring failure and retried with the secret file
+    if token.trim().is_empty() {
+        anyhow::bail!("Token cannot be empty");
+    }
+
+    match GitHubCredentials::store(&token, TokenStore::Keyring) {
+        Ok(()) => Ui::success("Token saved to the OS keyring"),
+        Err(e) => {
+            Ui::warning(&format!("Keyring unavailable ({}), using secret file", e));
+            GitHubCredentials::store(&token, TokenStore::SecretFile)?;
+            Ui::success("Token saved to the rhinolabs config directory");
+        }
+    }
+
+    if let Some(active) = GitHubCredentials::resolve() {
+        if !matches!(
+            active.source,
+            TokenSource::Keyring | TokenSource::SecretFile
+        ) {
+            Ui::info(&format!(
+                "Note: the token from {} takes precedence over the stored one.",
+                active.source
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove the stored GitHub token
+pub fn logout() -> Result<()> {
+    GitHubCredentials::clear()?;
+    Ui::success("Stored GitHub token removed");
+
+    if let Some(active) = GitHubCredentials::resolve() {
+        Ui::info(&format!(
+            "A token from {} is still available.",
+            active.source
+        ));
+    }
+
+    Ok(())
+}
+
+/// Show only the token prefix and last characters
+fn mask(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    let start: String = chars[..4].iter().collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", start, end)
+}
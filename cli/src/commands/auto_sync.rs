@@ -7,7 +7,7 @@
 
 use anyhow::Result;
 use colored::Colorize;
-use rhinolabs_core::{Deploy, ProfileType, Profiles};
+use rhinolabs_core::{Deploy, ProfileType, Profiles, RhinolabsError, CLI_UPGRADE_HINT};
 use std::fs;
 use std::io::{self, Write};
 use std::path::PathBuf;
@@ -132,6 +132,16 @@ pub async fn run_auto_sync() -> Result<bool> {
                     result.output_styles_installed
                 );
             }
+            if !result.conflicts.is_empty() {
+                println!(
+                    "  {} {} locally modified items kept (run 'rhinolabs-ai sync' to resolve)",
+                    "⚠".yellow(),
+                    result.conflicts.len()
+                );
+            }
+            for warning in &result.warnings {
+                println!("  {} {}", "⚠".yellow(), warning);
+            }
 
             // Mark as synced
             mark_synced(&result.version);
@@ -151,6 +161,19 @@ pub async fn run_auto_sync() -> Result<bool> {
             if error_msg.contains("No configuration release") {
                 println!("{} No configuration deployed yet", "○".dimmed());
                 mark_synced("none"); // Mark as checked to avoid repeated checks
+            } else if matches!(e, RhinolabsError::IncompatibleBundle(_)) {
+                println!("{} {}", "⚠".yellow(), error_msg);
+                println!("  {}", format!("{}.", CLI_UPGRADE_HINT).dimmed());
+                mark_synced("incompatible"); // Don't nag on every command
+            } else if matches!(
+                e,
+                RhinolabsError::GitHubAuth(_) | RhinolabsError::RateLimited(_)
+            ) {
+                println!("{} {}", "⚠".yellow(), error_msg);
+                println!(
+                    "  {}",
+                    "Run 'rhinolabs-ai auth status' to check your GitHub token.".dimmed()
+                );
             } else if error_msg.contains("GitHub repository not configured") {
                 // Silently skip - not configured
                 mark_synced("unconfigured");
@@ -0,0 +1,38 @@
+use crate::ui::Ui;
+use anyhow::Result;
+use colored::Colorize;
+use rhinolabs_core::{Channels, ReleaseChannel};
+
+/// Show the release channel this machine follows
+pub fn show() -> Result<()> {
+    Ui::header("Release Channel");
+
+    let current = Channels::current()?;
+
+    for channel in ReleaseChannel::ALL {
+        if channel == current {
+            println!("  {} {}", "●".green(), channel.to_string().bold());
+        } else {
+            println!("  {} {}", "○".dimmed(), channel.to_string().dimmed());
+        }
+    }
+    println!();
+
+    Ui::info("Change with 'rhinolabs-ai channel set <stable|beta|canary>'.");
+
+    Ok(())
+}
+
+/// Follow a release channel for config sync and plugin updates
+pub fn set(channel: String) -> Result<()> {
+    let channel: ReleaseChannel = channel.parse()?;
+
+    Channels::set(channel)?;
+
+    Ui::success(&format!("Now following the {} channel", channel));
+    if channel.is_prerelease() {
+        Ui::info("Sync and update will also pick up prereleases from this channel.");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use crate::ui::Ui;
+use anyhow::Result;
+use colored::Colorize;
+use rhinolabs_core::{ChangeKind, ConfigDiff, ConfigSnapshot};
+use std::path::Path;
+
+/// Compare configurations
+///
+/// - no arguments: latest deployed release vs local configuration
+/// - one bundle: bundle vs local configuration
+/// - two bundles: old bundle vs new bundle
+pub async fn diff(old: Option<String>, new: Option<String>, format: String) -> Result<()> {
+    let json = match format.as_str() {
+        "json" => true,
+        "text" => false,
+        other => anyhow::bail!("Unknown format '{}'. Use 'text' or 'json'", other),
+    };
+
+    let (old_label, diff) = match (old, new) {
+        (None, _) => {
+            let (version, diff) = ConfigDiff::local_vs_latest().await?;
+            (version, diff)
+        }
+        (Some(old), None) => {
+            let old_snapshot = ConfigSnapshot::from_bundle_file(Path::new(&old))?;
+            let diff = ConfigDiff::compare(&old_snapshot, &ConfigSnapshot::local()?);
+            (old, diff)
+        }
+        (Some(old), Some(new)) => {
+            let old_snapshot = ConfigSnapshot::from_bundle_file(Path::new(&old))?;
+            let new_snapshot = ConfigSnapshot::from_bundle_file(Path::new(&new))?;
+            (old, ConfigDiff::compare(&old_snapshot, &new_snapshot))
+        }
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+
+    Ui::header(&format!("Config Diff (from {})", old_label));
+
+    if diff.is_empty() {
+        Ui::info("No configuration changes.");
+        return Ok(());
+    }
+
+    if !diff.profiles_added.is_empty()
+        || !diff.profiles_removed.is_empty()
+        || !diff.profile_changes.is_empty()
+    {
+        Ui::section("Profiles");
+        for id in &diff.profiles_added {
+            println!("  {} {}", "+".green(), id);
+        }
+        for id in &diff.profiles_removed {
+            println!("  {} {}", "-".red(), id);
+        }
+        for change in &diff.profile_changes {
+            println!("  {} {}", "~".yellow(), change.profile_id);
+            for skill in &change.skills_assigned {
+                println!("      {} {}", "+".green(), skill);
+            }
+            for skill in &change.skills_unassigned {
+                println!("      {} {}", "-".red(), skill);
+            }
+        }
+        println!();
+    }
+
+    if !diff.skills_added.is_empty()
+        || !diff.skills_removed.is_empty()
+        || !diff.skill_changes.is_empty()
+    {
+        Ui::section("Skills");
+        for id in &diff.skills_added {
+            println!("  {} {}", "+".green(), id);
+        }
+        for id in &diff.skills_removed {
+            println!("  {} {}", "-".red(), id);
+        }
+        for change in &diff.skill_changes {
+            println!(
+                "  {} {} {}",
+                "~".yellow(),
+                change.skill_id,
+                format!("(+{} -{})", change.additions, change.deletions).dimmed()
+            );
+            for line in change.diff.lines() {
+                let line = if line.starts_with('+') && !line.starts_with("+++") {
+                    line.green()
+                } else if line.starts_with('-') && !line.starts_with("---") {
+                    line.red()
+                } else {
+                    line.dimmed()
+                };
+                println!("      {}", line);
+            }
+        }
+        println!();
+    }
+
+    if !diff.permission_changes.is_empty() {
+        Ui::section("Permissions");
+        for change in &diff.permission_changes {
+            println!(
+                "  {} {} {}",
+                change_marker(change.change),
+                change.list.dimmed(),
+                change.rule
+            );
+        }
+        println!();
+    }
+
+    if !diff.mcp_changes.is_empty() {
+        Ui::section("MCP Servers");
+        for change in &diff.mcp_changes {
+            println!("  {} {}", change_marker(change.change), change.name);
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Marker for a change kind
+fn change_marker(kind: ChangeKind) -> colored::ColoredString {
+    match kind {
+        ChangeKind::Added => "+".green(),
+        ChangeKind::Removed => "-".red(),
+        ChangeKind::Modified => "~".yellow(),
+    }
+}
@@ -1,16 +1,79 @@
 use crate::ui::Ui;
 use anyhow::Result;
 use colored::Colorize;
-use rhinolabs_core::Deploy;
+use rhinolabs_core::{
+    Channels, ConflictStrategy, Deploy, RhinolabsError, SyncConflict, SyncOptions, CLI_UPGRADE_HINT,
+};
+
+/// Build sync options from the CLI strategy flags
+fn parse_sync_options(
+    strategy: &str,
+    resolve: &[String],
+    channel: Option<&str>,
+) -> Result<SyncOptions> {
+    let mut options = SyncOptions {
+        default_strategy: strategy.parse()?,
+        channel: channel.map(|c| c.parse()).transpose()?,
+        ..Default::default()
+    };
+
+    for entry in resolve {
+        let (item, item_strategy) = entry.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("Invalid --resolve value '{}'. Use ITEM=STRATEGY", entry)
+        })?;
+        options
+            .item_strategies
+            .insert(item.to_string(), item_strategy.parse()?);
+    }
+
+    Ok(options)
+}
+
+/// Describe how a conflict was resolved
+fn resolution_display(strategy: ConflictStrategy) -> &'static str {
+    match strategy {
+        ConflictStrategy::KeepLocal => "kept local",
+        ConflictStrategy::TakeRemote => "took remote",
+        ConflictStrategy::WriteRemote => "remote written to .sync-remote/",
+    }
+}
+
+/// Print conflicts between local edits and the synced bundle
+pub fn print_conflicts(conflicts: &[SyncConflict]) {
+    if conflicts.is_empty() {
+        return;
+    }
+
+    Ui::section("Local Modifications");
+    for conflict in conflicts {
+        println!(
+            "  {} {} ({})",
+            "⚠".yellow(),
+            conflict.item,
+            resolution_display(conflict.resolution).dimmed()
+        );
+    }
+    println!();
+    Ui::info("Re-run with --resolve <item>=take-remote to accept a remote change.");
+}
 
 /// Sync configuration from GitHub (CLI-only, read-only operation)
 /// Deploy and export are GUI-only to prevent team devs from modifying config
-pub async fn sync() -> Result<()> {
+pub async fn sync(strategy: String, resolve: Vec<String>, channel: Option<String>) -> Result<()> {
     Ui::header("Syncing Configuration");
 
-    Ui::step("Fetching latest configuration from GitHub...");
+    let options = parse_sync_options(&strategy, &resolve, channel.as_deref())?;
+    let channel = match options.channel {
+        Some(channel) => channel,
+        None => Channels::current()?,
+    };
+
+    Ui::step(&format!(
+        "Fetching latest configuration from GitHub ({} channel)...",
+        channel
+    ));
 
-    match Deploy::sync().await {
+    match Deploy::sync_with_options(&options).await {
         Ok(result) => {
             println!();
             Ui::success("Configuration synced successfully!");
@@ -63,9 +126,27 @@ pub async fn sync() -> Result<()> {
             );
             println!();
 
+            print_conflicts(&result.conflicts);
+
+            for warning in &result.warnings {
+                Ui::warning(warning);
+            }
+
             Ui::info("Restart Claude Code to apply changes.");
             println!();
         }
+        Err(e @ RhinolabsError::IncompatibleBundle(_)) => {
+            Ui::error(&format!("Sync failed: {}", e));
+            println!();
+            Ui::info(&format!("{}, then sync again.", CLI_UPGRADE_HINT));
+            println!();
+        }
+        Err(e @ (RhinolabsError::GitHubAuth(_) | RhinolabsError::RateLimited(_))) => {
+            Ui::error(&format!("Sync failed: {}", e));
+            println!();
+            Ui::info("Run 'rhinolabs-ai auth status' to check your GitHub token.");
+            println!();
+        }
         Err(e) => {
             Ui::error(&format!("Sync failed: {}", e));
             println!();
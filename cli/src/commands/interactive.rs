@@ -24,7 +24,7 @@ pub async fn run() -> Result<()> {
 
         match selection {
             0 => super::install::run(None, false).await?,
-            1 => super::update::run(false).await?,
+            1 => super::update::run(false, None).await?,
             2 => {
                 Ui::info("Please use: rhinolabs sync-mcp --url <URL>");
                 Ui::info("Or: rhinolabs sync-mcp --file <PATH>");
@@ -1,4 +1,7 @@
+pub mod auth;
 pub mod auto_sync;
+pub mod channel;
+pub mod config;
 pub mod deploy;
 pub mod doctor;
 pub mod install;
@@ -7,6 +10,7 @@ pub mod profile;
 pub mod rag;
 pub mod skill;
 pub mod status;
+pub mod style;
 pub mod sync_mcp;
 pub mod uninstall;
 pub mod update;
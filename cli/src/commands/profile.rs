@@ -1,9 +1,14 @@
+use crate::editor::Editor;
 use crate::ui::Ui;
 use anyhow::Result;
 use colored::Colorize;
-use rhinolabs_core::{ProfileType, Profiles};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
+use rhinolabs_core::{
+    AutoInvokeRule, CreateProfileInput, OutputStyles, ProfileType, Profiles, SkillGraph, Skills,
+    TokenEstimator, UpdateProfileInput, DEFAULT_TOKEN_BUDGET,
+};
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::Path;
 
 /// Detect installed profile from .claude-plugin/plugin.json
@@ -50,7 +55,7 @@ pub fn list() -> Result<()> {
 
     if profiles.is_empty() {
         Ui::info("No profiles configured yet.");
-        Ui::info("Create profiles in the GUI to organize your skills.");
+        Ui::info("Create one with 'rhinolabs-ai profile create'.");
         return Ok(());
     }
 
@@ -106,19 +111,66 @@ pub fn show(profile_id: &str) -> Result<()> {
             println!("  Name:        {}", profile.name);
             println!("  Type:        {}", type_str);
             println!("  Description: {}", profile.description);
+            if let Some(style) = &profile.output_style {
+                println!("  Style:       {}", style);
+            }
             println!("  Created:     {}", profile.created_at);
             println!("  Updated:     {}", profile.updated_at);
             println!();
 
+            let report = TokenEstimator::profile(&profile.id)?;
+
             if profile.skills.is_empty() {
                 Ui::info("No skills assigned to this profile.");
             } else {
                 Ui::section("Assigned Skills");
                 for skill_id in &profile.skills {
-                    println!("  • {}", skill_id);
+                    match report.skills.iter().find(|s| &s.id == skill_id) {
+                        Some(estimate) => {
+                            println!("  • {}  {}", skill_id, Ui::tokens(estimate.tokens).dimmed())
+                        }
+                        None => println!("  • {}", skill_id),
+                    }
+                }
+                for estimate in report
+                    .skills
+                    .iter()
+                    .filter(|s| !profile.skills.contains(&s.id))
+                {
+                    println!(
+                        "  • {} {}  {}",
+                        estimate.id,
+                        "(dependency)".dimmed(),
+                        Ui::tokens(estimate.tokens).dimmed()
+                    );
                 }
             }
 
+            Ui::section("Context Size");
+            let skills_total: usize = report.skills.iter().map(|s| s.tokens).sum();
+            println!("  Skills:       {}", Ui::tokens(skills_total));
+            if let Some(style) = &report.output_style {
+                println!(
+                    "  Output style: {} ({})",
+                    Ui::tokens(style.tokens),
+                    style.id
+                );
+            }
+            for file in &report.instructions {
+                println!(
+                    "  {:<13} {}",
+                    format!("{}:", file.id),
+                    Ui::tokens(file.tokens)
+                );
+            }
+            let total = format!("{} (budget {})", Ui::tokens(report.total), report.budget);
+            if report.over_budget {
+                println!("  Total:        {}", total.red().bold());
+                Ui::warning("This profile exceeds its token budget.");
+            } else {
+                println!("  Total:        {}", total.green());
+            }
+
             println!();
         }
         None => {
@@ -177,7 +229,10 @@ pub fn install(profile_id: &str, target_path: Option<String>) -> Result<()> {
 
             if profile.skills.is_empty() {
                 Ui::warning("This profile has no skills assigned.");
-                Ui::info("Assign skills to this profile in the GUI first.");
+                Ui::info(&format!(
+                    "Assign skills with 'rhinolabs-ai profile assign {}' first.",
+                    profile.id
+                ));
                 return Ok(());
             }
 
@@ -195,6 +250,9 @@ pub fn install(profile_id: &str, target_path: Option<String>) -> Result<()> {
                 println!("  {} .claude-plugin/plugin.json", "✓".green());
                 println!("  {} .claude/skills/", "✓".green());
                 println!("  {} CLAUDE.md", "✓".green());
+                if let Some(style) = &result.output_style_installed {
+                    println!("  {} .claude/output-styles/ ({})", "✓".green(), style);
+                }
             }
 
             if !result.skills_installed.is_empty() {
@@ -363,3 +421,526 @@ pub fn uninstall(target_path: Option<String>) -> Result<()> {
 
     Ok(())
 }
+
+// ============================================
+// Profile Management
+// ============================================
+
+/// Flags of `profile create`
+pub struct CreateOptions {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub skills: Vec<String>,
+    pub instructions: Option<String>,
+    pub generate_copilot: bool,
+    pub generate_agents: bool,
+    pub budget: Option<usize>,
+    pub output_style: Option<String>,
+}
+
+/// Flags of `profile edit`
+pub struct EditOptions {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub copilot: Option<bool>,
+    pub agents: Option<bool>,
+    pub budget: Option<usize>,
+    pub output_style: Option<String>,
+}
+
+/// Whether missing values can be asked for
+fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Profile ids name files and directories, so keep them to lowercase words
+fn validate_profile_id(id: &str) -> Result<()> {
+    let valid = !id.is_empty()
+        && !id.starts_with('-')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        anyhow::bail!(
+            "Invalid profile id '{}': use lowercase letters, digits and hyphens",
+            id
+        );
+    }
+    Ok(())
+}
+
+/// Error on skill ids that aren't installed
+fn check_skills_exist(skill_ids: &[String]) -> Result<()> {
+    let installed: Vec<String> = Skills::list()?.into_iter().map(|s| s.id).collect();
+    let unknown: Vec<&str> = skill_ids
+        .iter()
+        .filter(|id| !installed.contains(id))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        anyhow::bail!("Unknown skills: {}", unknown.join(", "));
+    }
+    Ok(())
+}
+
+/// Let the user tick skills, with `selected` ticked to start with
+fn pick_skills(selected: &[String]) -> Result<Vec<String>> {
+    let skills = Skills::list()?;
+    if skills.is_empty() {
+        Ui::info("No skills installed yet.");
+        return Ok(vec![]);
+    }
+
+    let items: Vec<String> = skills
+        .iter()
+        .map(|s| format!("{} ({})", s.name, s.id))
+        .collect();
+    let checked: Vec<bool> = skills.iter().map(|s| selected.contains(&s.id)).collect();
+
+    let picked = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Skills (space to toggle, enter to confirm)")
+        .items(&items)
+        .defaults(&checked)
+        .interact()?;
+    Ok(picked.into_iter().map(|i| skills[i].id.clone()).collect())
+}
+
+/// Read a budget, where an empty answer keeps the default
+fn prompt_budget(current: Option<usize>) -> Result<Option<usize>> {
+    let answer: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "Token budget (empty for the default, {})",
+            DEFAULT_TOKEN_BUDGET
+        ))
+        .with_initial_text(current.map(|b| b.to_string()).unwrap_or_default())
+        .allow_empty(true)
+        .validate_with(|input: &String| -> std::result::Result<(), String> {
+            if input.trim().is_empty() || input.trim().parse::<usize>().is_ok() {
+                Ok(())
+            } else {
+                Err("Enter a number of tokens".into())
+            }
+        })
+        .interact_text()?;
+    Ok(answer.trim().parse().ok())
+}
+
+/// Let the user choose an output style, or none
+fn pick_output_style(current: Option<&str>) -> Result<Option<String>> {
+    let styles = OutputStyles::list()?;
+    if styles.is_empty() {
+        return Ok(None);
+    }
+
+    let mut items = vec!["None".to_string()];
+    items.extend(styles.iter().map(|s| format!("{} ({})", s.name, s.id)));
+    let selected = current
+        .and_then(|id| styles.iter().position(|s| s.id == id))
+        .map_or(0, |i| i + 1);
+
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Output style")
+        .items(&items)
+        .default(selected)
+        .interact()?;
+    Ok(choice.checked_sub(1).map(|i| styles[i].id.clone()))
+}
+
+/// Warn when a profile outgrows its token budget
+fn check_budget(profile_id: &str) -> Result<()> {
+    let report = TokenEstimator::profile(profile_id)?;
+    if report.over_budget {
+        Ui::warning(&format!(
+            "The profile uses {} of its {} token budget.",
+            Ui::tokens(report.total),
+            report.budget
+        ));
+    }
+    Ok(())
+}
+
+/// Create a project profile, asking for what the flags don't give
+pub fn create(options: CreateOptions) -> Result<()> {
+    Ui::header("Create Profile");
+
+    let wizard = options.id.is_none() || options.name.is_none();
+    if wizard && !is_interactive() {
+        anyhow::bail!("--id and --name are required when not running in a terminal");
+    }
+    let theme = ColorfulTheme::default();
+
+    let id = match options.id {
+        Some(id) => id,
+        None => Input::with_theme(&theme)
+            .with_prompt("Profile id")
+            .validate_with(|input: &String| validate_profile_id(input).map_err(|e| e.to_string()))
+            .interact_text()?,
+    };
+    validate_profile_id(&id)?;
+    if Profiles::get(&id)?.is_some() {
+        anyhow::bail!("Profile '{}' already exists", id);
+    }
+
+    let name = match options.name {
+        Some(name) => name,
+        None => Input::with_theme(&theme)
+            .with_prompt("Name")
+            .interact_text()?,
+    };
+
+    let description = match options.description {
+        Some(description) => description,
+        None if wizard => Input::with_theme(&theme)
+            .with_prompt("Description")
+            .allow_empty(true)
+            .interact_text()?,
+        None => String::new(),
+    };
+
+    let skills = if wizard && options.skills.is_empty() {
+        pick_skills(&[])?
+    } else {
+        options.skills
+    };
+    check_skills_exist(&skills)?;
+    // Required skills are part of the profile and shown in its instructions
+    let skills = SkillGraph::load()?.resolve(&skills)?;
+
+    let (generate_copilot, generate_agents, budget, output_style) = if wizard {
+        (
+            Confirm::with_theme(&theme)
+                .with_prompt("Generate .github/copilot-instructions.md?")
+                .default(options.generate_copilot)
+                .interact()?,
+            Confirm::with_theme(&theme)
+                .with_prompt("Generate AGENTS.md?")
+                .default(options.generate_agents)
+                .interact()?,
+            match options.budget {
+                Some(budget) => Some(budget),
+                None => prompt_budget(None)?,
+            },
+            match options.output_style {
+                Some(style) => Some(style),
+                None => pick_output_style(None)?,
+            },
+        )
+    } else {
+        (
+            options.generate_copilot,
+            options.generate_agents,
+            options.budget,
+            options.output_style,
+        )
+    };
+
+    let instructions = match options.instructions {
+        Some(file) => Some(
+            fs::read_to_string(&file)
+                .map_err(|e| anyhow::anyhow!("Could not read '{}': {}", file, e))?,
+        ),
+        None => None,
+    };
+
+    let profile = Profiles::create(CreateProfileInput {
+        id,
+        name,
+        description,
+        profile_type: ProfileType::Project,
+        skills,
+        instructions,
+        generate_copilot,
+        generate_agents,
+        token_budget: budget,
+        output_style,
+    })?;
+
+    println!();
+    Ui::success(&format!("Profile '{}' created", profile.id));
+    if !profile.skills.is_empty() {
+        Ui::info(&format!("Skills: {}", profile.skills.join(", ")));
+    }
+    check_budget(&profile.id)?;
+    Ui::info(&format!(
+        "Edit its instructions with 'rhinolabs-ai profile instructions {}'",
+        profile.id
+    ));
+    Ok(())
+}
+
+/// Change a profile's settings; without flags, ask for each one
+pub fn edit(profile_id: &str, options: EditOptions) -> Result<()> {
+    let profile = Profiles::get(profile_id)?
+        .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", profile_id))?;
+
+    let no_flags = options.name.is_none()
+        && options.description.is_none()
+        && options.copilot.is_none()
+        && options.agents.is_none()
+        && options.budget.is_none()
+        && options.output_style.is_none();
+
+    let input = if no_flags {
+        if !is_interactive() {
+            anyhow::bail!(
+                "Nothing to change. Pass --name, --description, --copilot, --agents, --budget or --output-style"
+            );
+        }
+        Ui::header(&format!("Edit Profile: {}", profile.name));
+        let theme = ColorfulTheme::default();
+        UpdateProfileInput {
+            name: Some(
+                Input::with_theme(&theme)
+                    .with_prompt("Name")
+                    .with_initial_text(&profile.name)
+                    .interact_text()?,
+            ),
+            description: Some(
+                Input::with_theme(&theme)
+                    .with_prompt("Description")
+                    .with_initial_text(&profile.description)
+                    .allow_empty(true)
+                    .interact_text()?,
+            ),
+            profile_type: None,
+            instructions: None,
+            generate_copilot: Some(
+                Confirm::with_theme(&theme)
+                    .with_prompt("Generate .github/copilot-instructions.md?")
+                    .default(profile.generate_copilot)
+                    .interact()?,
+            ),
+            generate_agents: Some(
+                Confirm::with_theme(&theme)
+                    .with_prompt("Generate AGENTS.md?")
+                    .default(profile.generate_agents)
+                    .interact()?,
+            ),
+            // 0 clears a budget that was emptied
+            token_budget: Some(prompt_budget(profile.token_budget)?.unwrap_or(0)),
+            // The Main-Profile always uses the active style
+            output_style: if profile.profile_type == ProfileType::Project {
+                Some(pick_output_style(profile.output_style.as_deref())?.unwrap_or_default())
+            } else {
+                None
+            },
+        }
+    } else {
+        UpdateProfileInput {
+            name: options.name,
+            description: options.description,
+            profile_type: None,
+            instructions: None,
+            generate_copilot: options.copilot,
+            generate_agents: options.agents,
+            token_budget: options.budget,
+            output_style: options.output_style,
+        }
+    };
+
+    let updated = Profiles::update(profile_id, input)?;
+    Ui::success(&format!("Profile '{}' updated", updated.id));
+    check_budget(&updated.id)?;
+    Ok(())
+}
+
+/// Edit (or print) a profile's instructions
+pub fn instructions(profile_id: &str, print: bool) -> Result<()> {
+    if Profiles::get(profile_id)?.is_none() {
+        anyhow::bail!("Profile '{}' not found", profile_id);
+    }
+
+    if print {
+        print!("{}", Profiles::get_instructions(profile_id)?);
+        return Ok(());
+    }
+
+    let path = Profiles::ensure_instructions_file(profile_id)?;
+    Editor::open(&path)?;
+
+    // Store what was saved in the profile config as well
+    let content = fs::read_to_string(&path)?;
+    Profiles::update_instructions(profile_id, &content)?;
+    Ui::success(&format!("Instructions of '{}' saved", profile_id));
+    check_budget(profile_id)?;
+    Ok(())
+}
+
+/// Delete a profile
+pub fn delete(profile_id: &str, yes: bool) -> Result<()> {
+    let profile = Profiles::get(profile_id)?
+        .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", profile_id))?;
+
+    let confirmed = yes
+        || Confirm::new()
+            .with_prompt(format!(
+                "Delete profile '{}'? Projects it was installed in keep their files",
+                profile.name
+            ))
+            .default(false)
+            .interact()?;
+    if !confirmed {
+        Ui::info("Cancelled");
+        return Ok(());
+    }
+
+    Profiles::delete(profile_id)?;
+    Ui::success(&format!("Profile '{}' deleted", profile_id));
+    Ok(())
+}
+
+/// Replace, extend or trim the skills of a profile
+pub fn assign(profile_id: &str, skills: Vec<String>, add: bool, remove: bool) -> Result<()> {
+    let profile = Profiles::get(profile_id)?
+        .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", profile_id))?;
+
+    let skill_ids = if skills.is_empty() {
+        if add || remove {
+            anyhow::bail!("Name the skills to add or remove");
+        }
+        if !is_interactive() {
+            anyhow::bail!("Name the skills to assign when not running in a terminal");
+        }
+        pick_skills(&profile.skills)?
+    } else if add {
+        let mut ids = profile.skills.clone();
+        ids.extend(skills.into_iter().filter(|id| !profile.skills.contains(id)));
+        ids
+    } else if remove {
+        let missing: Vec<&str> = skills
+            .iter()
+            .filter(|id| !profile.skills.contains(id))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            Ui::warning(&format!("Not assigned: {}", missing.join(", ")));
+        }
+        profile
+            .skills
+            .iter()
+            .filter(|id| !skills.contains(id))
+            .cloned()
+            .collect()
+    } else {
+        skills
+    };
+    check_skills_exist(&skill_ids)?;
+
+    let updated = Profiles::assign_skills(profile_id, skill_ids.clone())?;
+
+    Ui::success(&format!(
+        "Profile '{}' has {} skill(s)",
+        updated.id,
+        updated.skills.len()
+    ));
+    let dependencies: Vec<&str> = updated
+        .skills
+        .iter()
+        .filter(|id| !skill_ids.contains(id))
+        .map(String::as_str)
+        .collect();
+    if !dependencies.is_empty() {
+        Ui::info(&format!(
+            "Added as dependencies: {}",
+            dependencies.join(", ")
+        ));
+    }
+    check_budget(&updated.id)?;
+    Ok(())
+}
+
+/// List the auto-invoke rules of a profile
+pub fn rules_list(profile_id: &str) -> Result<()> {
+    let rules = Profiles::get_auto_invoke_rules(profile_id)?;
+    Ui::header(&format!("Auto-invoke Rules: {}", profile_id));
+
+    if rules.is_empty() {
+        Ui::info("No rules yet.");
+        Ui::info(&format!(
+            "Add one with 'rhinolabs-ai profile rules set {} <skill-id> --trigger \"...\"'",
+            profile_id
+        ));
+        return Ok(());
+    }
+
+    for rule in &rules {
+        println!();
+        println!("  {} {}", "•".cyan(), rule.skill_id.bold());
+        println!("    When: {}", rule.trigger);
+        if !rule.description.is_empty() {
+            println!("    {}", rule.description.dimmed());
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// Add or replace the rule of one skill
+pub fn rules_set(
+    profile_id: &str,
+    skill_id: &str,
+    trigger: String,
+    description: Option<String>,
+) -> Result<()> {
+    let profile = Profiles::get(profile_id)?
+        .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", profile_id))?;
+    if !profile.skills.iter().any(|id| id == skill_id) {
+        anyhow::bail!(
+            "Skill '{}' is not assigned to '{}'. Use 'rhinolabs-ai profile assign {} {} --add'",
+            skill_id,
+            profile_id,
+            profile_id,
+            skill_id
+        );
+    }
+
+    let mut rules = profile.auto_invoke_rules;
+    let existing = rules.iter().position(|r| r.skill_id == skill_id);
+    // Keep the previous description when only the trigger changes
+    let description = description
+        .or_else(|| existing.map(|i| rules[i].description.clone()))
+        .unwrap_or_default();
+    let rule = AutoInvokeRule {
+        skill_id: skill_id.to_string(),
+        trigger,
+        description,
+    };
+    match existing {
+        Some(i) => rules[i] = rule,
+        None => rules.push(rule),
+    }
+
+    Profiles::update_auto_invoke_rules(profile_id, rules)?;
+    Ui::success(&format!("Rule for '{}' saved", skill_id));
+    Ok(())
+}
+
+/// Remove the rule of one skill
+pub fn rules_remove(profile_id: &str, skill_id: &str) -> Result<()> {
+    let mut rules = Profiles::get_auto_invoke_rules(profile_id)?;
+    let before = rules.len();
+    rules.retain(|r| r.skill_id != skill_id);
+    if rules.len() == before {
+        anyhow::bail!("Profile '{}' has no rule for '{}'", profile_id, skill_id);
+    }
+
+    Profiles::update_auto_invoke_rules(profile_id, rules)?;
+    Ui::success(&format!("Rule for '{}' removed", skill_id));
+    Ok(())
+}
+
+/// Show or set the default user profile
+pub fn default(profile_id: Option<String>) -> Result<()> {
+    match profile_id {
+        Some(id) => {
+            Profiles::set_default_user_profile(&id)?;
+            Ui::success(&format!("'{}' is now the default user profile", id));
+        }
+        None => match Profiles::get_default_user_profile()? {
+            Some(profile) => println!("{} ({})", profile.name, profile.id),
+            None => Ui::info("No default user profile set."),
+        },
+    }
+    Ok(())
+}
@@ -1,53 +1,65 @@
+use crate::editor::Editor;
 use crate::ui::Ui;
 use anyhow::Result;
 use colored::Colorize;
-use rhinolabs_core::{CreateSkillInput, SkillCategory, Skills};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input};
+use rhinolabs_core::skill_templates::BUILTIN_VARIABLES;
+use rhinolabs_core::{
+    CreateFromTemplateInput, CreateSkillInput, LintSeverity, Marketplace, McpServer, MergeStatus,
+    PluginInstallOptions, RiskLevel, ScanPolicy, SearchOptions, SecurityReport, Skill,
+    SkillCatalog, SkillCategory, SkillFilter, SkillGraph, SkillLinter, SkillSchema, SkillSource,
+    SkillSourceType, SkillTemplates, SkillUpdate, Skills, SourceKind, TokenEstimator,
+    CUSTOM_CATEGORY,
+};
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 
-/// Parse category string to SkillCategory enum
-fn parse_category(category: &str) -> SkillCategory {
-    match category.to_lowercase().as_str() {
-        "corporate" => SkillCategory::Corporate,
-        "frontend" => SkillCategory::Frontend,
-        "testing" => SkillCategory::Testing,
-        "ai-sdk" | "aisdk" => SkillCategory::AiSdk,
-        "utilities" => SkillCategory::Utilities,
-        _ => SkillCategory::Custom,
-    }
-}
-
-/// Format category for display
-fn category_display(category: &SkillCategory) -> &'static str {
-    match category {
-        SkillCategory::Corporate => "Corporate",
-        SkillCategory::Frontend => "Frontend",
-        SkillCategory::Testing => "Testing",
-        SkillCategory::AiSdk => "AI SDK",
-        SkillCategory::Utilities => "Utilities",
-        SkillCategory::Custom => "Custom",
-    }
+/// Display name of a category id (the id itself if it isn't defined)
+fn category_display(categories: &[SkillCategory], id: &str) -> String {
+    categories
+        .iter()
+        .find(|c| c.id == id)
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| id.to_string())
 }
 
-/// List all skills
-pub fn list() -> Result<()> {
+/// List all skills, optionally filtered by category and tags
+pub fn list(category: Option<String>, tags: Vec<String>) -> Result<()> {
     Ui::header("Skills");
 
-    let skills = Skills::list()?;
+    let filtered = category.is_some() || !tags.is_empty();
+    let skills = Skills::list_filtered(&SkillFilter {
+        category: category.map(|c| c.to_lowercase()),
+        tags,
+    })?;
 
     if skills.is_empty() {
-        Ui::info("No skills installed yet.");
-        Ui::info("Create skills in the GUI or use 'rhinolabs skill create'.");
+        if filtered {
+            Ui::info("No skills match the filter.");
+        } else {
+            Ui::info("No skills installed yet.");
+            Ui::info("Create skills in the GUI or use 'rhinolabs skill create'.");
+        }
         return Ok(());
     }
 
+    let categories = Skills::list_categories()?;
+
     // Group by category
-    let mut current_category: Option<SkillCategory> = None;
+    let mut current_category: Option<String> = None;
 
     for skill in skills {
         // Print category header when it changes
         if current_category.as_ref() != Some(&skill.category) {
             current_category = Some(skill.category.clone());
             println!();
-            println!("  {}", category_display(&skill.category).bold().underline());
+            println!(
+                "  {}",
+                category_display(&categories, &skill.category)
+                    .bold()
+                    .underline()
+            );
         }
 
         let status = if skill.enabled {
@@ -71,10 +83,94 @@ pub fn list() -> Result<()> {
             "    {} {}{}{}",
             status, skill.name, custom_badge, source_badge
         );
-        println!("      ID: {}", skill.id.dimmed());
+        let tokens = TokenEstimator::skill(&skill.id)
+            .map(|estimate| format!("  {}", Ui::tokens(estimate.tokens)))
+            .unwrap_or_default();
+        println!("      ID: {}{}", skill.id.dimmed(), tokens.dimmed());
+        if !skill.tags.is_empty() {
+            println!("      Tags: {}", skill.tags.join(", ").dimmed());
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Search installed skills and the cached catalogs of the enabled sources
+pub async fn search(
+    query: &str,
+    local: bool,
+    remote: bool,
+    source: Option<String>,
+    limit: usize,
+    refresh: bool,
+    format: String,
+) -> Result<()> {
+    let json = match format.as_str() {
+        "json" => true,
+        "text" => false,
+        other => anyhow::bail!("Unknown format '{}'. Use 'text' or 'json'", other),
+    };
+
+    if !json {
+        Ui::header(&format!("Skill Search: {}", query));
+    }
+    if refresh {
+        if !json {
+            Ui::step("Refreshing source catalogs...");
+        }
+        for (source_id, e) in Skills::update_catalogs(true).await? {
+            let message = format!("Could not refresh '{}': {}", source_id, e);
+            if json {
+                eprintln!("{}", message);
+            } else {
+                Ui::warning(&message);
+            }
+        }
+    }
+
+    let options = SearchOptions {
+        local: !remote,
+        remote: !local,
+        source_id: source,
+        limit,
+    };
+    let results = Skills::search(query, &options)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    if results.is_empty() {
+        Ui::info("No skills match the search.");
+        if options.remote && SkillCatalog::load_all(&Skills::list_sources()?)?.is_empty() {
+            Ui::info("No source catalogs cached yet. Run with --refresh to fetch them.");
+        }
+        return Ok(());
+    }
+
+    for result in &results {
+        let origin = match (&result.source_name, result.installed) {
+            (Some(source), true) => format!(" [installed, {}]", source),
+            (None, true) => " [installed]".to_string(),
+            (Some(source), false) => format!(" [{}]", source),
+            (None, false) => String::new(),
+        };
+        println!();
+        println!("  {}{}", result.name.bold(), origin.dimmed());
+        println!("    ID: {}", result.skill_id.dimmed());
+        println!("    {}", result.description);
+        if !result.tags.is_empty() {
+            println!("    Tags: {}", result.tags.join(", ").dimmed());
+        }
+        if let Some(snippet) = &result.snippet {
+            println!("    {}", snippet.dimmed().italic());
+        }
     }
 
     println!();
+    Ui::info(&format!("{} result(s)", results.len()));
     Ok(())
 }
 
@@ -87,17 +183,20 @@ pub fn create(
 ) -> Result<()> {
     Ui::header("Create Skill");
 
-    let category_enum = parse_category(&category);
+    let category = category.to_lowercase();
     let desc = description.unwrap_or_else(|| format!("Custom skill: {}", name));
 
     Ui::step(&format!("Creating skill '{}'...", id));
-    Ui::step(&format!("Category: {}", category_display(&category_enum)));
+    Ui::step(&format!(
+        "Category: {}",
+        category_display(&Skills::list_categories()?, &category)
+    ));
 
     let input = CreateSkillInput {
         id: id.clone(),
         name: name.clone(),
         description: desc,
-        category: category_enum,
+        category,
         content: format!("# {}\n\nYour skill instructions here.", name),
     };
 
@@ -111,25 +210,381 @@ pub fn create(
     Ok(())
 }
 
+/// Scaffold a custom skill from a template
+pub fn new(
+    id: String,
+    template: String,
+    name: Option<String>,
+    category: String,
+    description: Option<String>,
+    vars: Vec<String>,
+) -> Result<()> {
+    if id.is_empty()
+        || id.starts_with('-')
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        anyhow::bail!(
+            "Invalid skill id '{}': use lowercase letters, digits and hyphens",
+            id
+        );
+    }
+    let found = SkillTemplates::get(&template)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Template '{}' not found. See 'rhinolabs-ai skill templates'",
+            template
+        )
+    })?;
+
+    let mut variables = BTreeMap::new();
+    for var in vars {
+        let (key, value) = var
+            .split_once('=')
+            .filter(|(key, _)| !key.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Expected --var KEY=VALUE, got '{}'", var))?;
+        variables.insert(key.trim().to_string(), value.to_string());
+    }
+
+    // Ask for required variables that weren't given
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    for variable in &found.variables {
+        if variables.contains_key(&variable.name) || variable.default.is_some() || !interactive {
+            continue;
+        }
+        let prompt = if variable.description.is_empty() {
+            variable.name.clone()
+        } else {
+            format!("{} ({})", variable.name, variable.description)
+        };
+        let value: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .interact_text()?;
+        variables.insert(variable.name.clone(), value);
+    }
+
+    let name = name.unwrap_or_else(|| id.clone());
+    let category = category.to_lowercase();
+
+    Ui::header("New Skill");
+    Ui::step(&format!(
+        "Creating '{}' from template '{}'...",
+        id, found.id
+    ));
+
+    let skill = Skills::create_from_template(CreateFromTemplateInput {
+        id,
+        // No colon: templates may write it unquoted into the frontmatter
+        description: description.unwrap_or_else(|| format!("Use for {}", name)),
+        name,
+        category,
+        template: found.id,
+        variables,
+    })?;
+
+    let skill_dir = Path::new(&skill.path)
+        .parent()
+        .unwrap_or(Path::new(&skill.path))
+        .to_path_buf();
+    println!();
+    for entry in walk_files(&skill_dir)? {
+        println!("  {} {}", "+".green(), entry);
+    }
+    println!();
+    Ui::success(&format!("Skill '{}' created", skill.id));
+    Ui::info(&format!("Path: {}", skill_dir.display()));
+    Ui::info(&format!(
+        "Fill in the placeholders with 'rhinolabs-ai skill edit {}', then check it with 'rhinolabs-ai skill lint {}'",
+        skill.id, skill.id
+    ));
+    Ok(())
+}
+
+/// Relative paths of the files below `dir`, sorted
+fn walk_files(dir: &Path) -> Result<Vec<String>> {
+    fn walk(dir: &Path, prefix: &str, files: &mut Vec<String>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let relative = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+            if entry.file_type()?.is_dir() {
+                walk(&entry.path(), &format!("{}/", relative), files)?;
+            } else {
+                files.push(relative);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    walk(dir, "", &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+/// List skill templates
+pub fn templates() -> Result<()> {
+    Ui::header("Skill Templates");
+
+    for template in SkillTemplates::list()? {
+        let badge = if template.builtin { "built-in" } else { "user" };
+        println!();
+        println!(
+            "  {} {}",
+            template.id.bold(),
+            format!("[{}]", badge).dimmed()
+        );
+        if !template.description.is_empty() {
+            println!("    {}", template.description);
+        }
+        for variable in &template.variables {
+            let default = match &variable.default {
+                Some(default) => format!("default: {}", default),
+                None => "required".to_string(),
+            };
+            println!(
+                "    {} {} {}",
+                format!("{{{{{}}}}}", variable.name).cyan(),
+                variable.description,
+                format!("({})", default).dimmed()
+            );
+        }
+        if let Some(path) = &template.path {
+            println!("    {}", path.dimmed());
+        }
+    }
+
+    println!();
+    Ui::info(&format!(
+        "Add your own as directories in {}",
+        SkillTemplates::templates_dir()?.display()
+    ));
+    Ui::info(&format!(
+        "Every template can use {}",
+        BUILTIN_VARIABLES
+            .iter()
+            .map(|v| format!("{{{{{}}}}}", v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    println!();
+    Ok(())
+}
+
+/// Open a skill in the user's editor, then check what was saved
+pub fn edit(skill_id: &str) -> Result<()> {
+    let skill =
+        Skills::get(skill_id)?.ok_or_else(|| anyhow::anyhow!("Skill '{}' not found", skill_id))?;
+
+    Editor::open(Path::new(&skill.path))?;
+
+    let skill = Skills::get(skill_id)
+        .map_err(|e| anyhow::anyhow!("SKILL.md of '{}' no longer parses: {}", skill_id, e))?
+        .ok_or_else(|| anyhow::anyhow!("Skill '{}' not found", skill_id))?;
+    Ui::success(&format!("Saved '{}'", skill.id));
+
+    let report = SkillLinter::load()?.lint(std::slice::from_ref(&skill.id))?;
+    if !report.is_clean() {
+        Ui::warning(&format!(
+            "{} error(s), {} warning(s). Run 'rhinolabs-ai skill lint {}' for details",
+            report.errors, report.warnings, skill.id
+        ));
+    }
+    if skill.is_modified {
+        Ui::info("Local edits are merged on upgrade; 'rhinolabs-ai skill reset' discards them.");
+    }
+
+    Ok(())
+}
+
+/// Enable or disable a skill
+pub fn toggle(skill_id: &str, enabled: bool) -> Result<()> {
+    Skills::toggle(skill_id, enabled)?;
+    if enabled {
+        Ui::success(&format!("Skill '{}' enabled", skill_id));
+    } else {
+        Ui::success(&format!("Skill '{}' disabled", skill_id));
+        let dependents = SkillGraph::load()?.dependents(skill_id);
+        if !dependents.is_empty() {
+            Ui::warning(&format!("Required by: {}", dependents.join(", ")));
+        }
+    }
+    Ok(())
+}
+
+/// Delete a custom or source-installed skill
+pub fn delete(skill_id: &str, force: bool, yes: bool) -> Result<()> {
+    if Skills::get(skill_id)?.is_none() {
+        anyhow::bail!("Skill '{}' not found", skill_id);
+    }
+
+    let profiles = Skills::get_assigned_profiles(skill_id)?;
+    if !profiles.is_empty() {
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        Ui::warning(&format!("Assigned to profiles: {}", names.join(", ")));
+    }
+
+    let confirmed = yes
+        || Confirm::new()
+            .with_prompt(format!(
+                "Delete skill '{}'? This cannot be undone",
+                skill_id
+            ))
+            .default(false)
+            .interact()?;
+    if !confirmed {
+        Ui::info("Cancelled");
+        return Ok(());
+    }
+
+    Skills::delete(skill_id, force)?;
+    Ui::success(&format!("Skill '{}' deleted", skill_id));
+    Ok(())
+}
+
+/// Rename a custom skill and every reference to it
+pub fn rename(skill_id: &str, new_id: &str) -> Result<()> {
+    let result = Skills::rename(skill_id, new_id)?;
+
+    Ui::success(&format!("Skill '{}' renamed to '{}'", skill_id, new_id));
+    if !result.profiles.is_empty() {
+        Ui::info(&format!("Updated profiles: {}", result.profiles.join(", ")));
+        Ui::info(&format!(
+            "Run 'rhinolabs-ai profile update' in projects using {} to apply it.",
+            result.profiles.join(", ")
+        ));
+    }
+    if !result.references.is_empty() {
+        Ui::info(&format!(
+            "Updated requires/conflicts in: {}",
+            result.references.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Copy a skill into a new custom skill
+pub fn clone(skill_id: &str, new_id: &str) -> Result<()> {
+    let skill = Skills::clone(skill_id, new_id)?;
+
+    Ui::success(&format!("Skill '{}' cloned to '{}'", skill_id, skill.id));
+    Ui::info(&format!("Path: {}", skill.path));
+    Ui::info(&format!(
+        "Profiles still use '{}'. Add the clone with 'rhinolabs-ai profile assign <profile> {} --add'",
+        skill_id, skill.id
+    ));
+    Ok(())
+}
+
 /// Set the category for an existing skill
 pub fn set_category(skill_id: String, category: String) -> Result<()> {
     Ui::header("Set Skill Category");
 
-    let category_enum = parse_category(&category);
+    let category = category.to_lowercase();
+    let name = category_display(&Skills::list_categories()?, &category);
 
     Ui::step(&format!(
         "Setting category for '{}' to '{}'...",
-        skill_id,
-        category_display(&category_enum)
+        skill_id, name
     ));
 
-    Skills::set_category(&skill_id, category_enum.clone())?;
+    Skills::set_category(&skill_id, &category)?;
+
+    println!();
+    Ui::success(&format!("Category for '{}' set to '{}'", skill_id, name));
+
+    Ok(())
+}
+
+/// Replace the tags of a skill
+pub fn set_tags(skill_id: String, tags: Vec<String>) -> Result<()> {
+    Skills::set_tags(&skill_id, &tags)?;
+
+    let skill =
+        Skills::get(&skill_id)?.ok_or_else(|| anyhow::anyhow!("Skill '{}' not found", skill_id))?;
+    if skill.tags.is_empty() {
+        Ui::success(&format!("Tags of '{}' cleared", skill_id));
+    } else {
+        Ui::success(&format!(
+            "Tags of '{}': {}",
+            skill_id,
+            skill.tags.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// List the skill categories in display order
+pub fn categories() -> Result<()> {
+    Ui::header("Skill Categories");
 
+    let skills = Skills::list()?;
+    for category in Skills::list_categories()? {
+        let count = skills.iter().filter(|s| s.category == category.id).count();
+        println!(
+            "  {:<14} {:<16} {}",
+            category.id,
+            category.name,
+            format!(
+                "order {}, {}, {} skill(s)",
+                category.order, category.color, count
+            )
+            .dimmed()
+        );
+    }
     println!();
+
+    Ok(())
+}
+
+/// Add a skill category, or update the one with the same id
+pub fn add_category(
+    id: String,
+    name: Option<String>,
+    order: Option<u32>,
+    color: Option<String>,
+) -> Result<()> {
+    let categories = Skills::list_categories()?;
+    let existing = categories.iter().find(|c| c.id == id);
+
+    let category = SkillCategory {
+        name: name
+            .or_else(|| existing.map(|c| c.name.clone()))
+            .unwrap_or_else(|| id.clone()),
+        // New categories go after the others, but before custom
+        order: order
+            .or_else(|| existing.map(|c| c.order))
+            .unwrap_or_else(|| {
+                categories
+                    .iter()
+                    .filter(|c| c.id != CUSTOM_CATEGORY)
+                    .map(|c| c.order + 10)
+                    .max()
+                    .unwrap_or(0)
+            }),
+        color: color
+            .or_else(|| existing.map(|c| c.color.clone()))
+            .unwrap_or_else(|| "#6b7280".to_string()),
+        id,
+    };
+
+    let verb = if existing.is_some() {
+        "updated"
+    } else {
+        "added"
+    };
+    Skills::save_category(category.clone())?;
+    Ui::success(&format!("Category '{}' {}", category.id, verb));
+
+    Ok(())
+}
+
+/// Remove a skill category
+pub fn remove_category(id: String) -> Result<()> {
+    Skills::remove_category(&id)?;
     Ui::success(&format!(
-        "Category for '{}' set to '{}'",
-        skill_id,
-        category_display(&category_enum)
+        "Category '{}' removed; its skills are now custom",
+        id
     ));
 
     Ok(())
@@ -152,7 +607,13 @@ pub fn show(skill_id: &str) -> Result<()> {
 
             println!("  ID:          {}", skill.id);
             println!("  Name:        {}", skill.name);
-            println!("  Category:    {}", category_display(&skill.category));
+            println!(
+                "  Category:    {}",
+                category_display(&Skills::list_categories()?, &skill.category)
+            );
+            if !skill.tags.is_empty() {
+                println!("  Tags:        {}", skill.tags.join(", "));
+            }
             println!("  Status:      {}", status_color);
             println!(
                 "  Custom:      {}",
@@ -164,6 +625,22 @@ pub fn show(skill_id: &str) -> Result<()> {
             if let Some(source) = &skill.source_name {
                 println!("  Source:      {}", source);
             }
+            if let Some(version) = &skill.version {
+                println!("  Version:     {}", version);
+            }
+            if let Some(commit) = &skill.source_commit {
+                println!("  Commit:      {}", short_commit(commit));
+            }
+            if !skill.requires.is_empty() {
+                println!("  Requires:    {}", skill.requires.join(", "));
+            }
+            if !skill.conflicts.is_empty() {
+                println!("  Conflicts:   {}", skill.conflicts.join(", "));
+            }
+            let dependents = SkillGraph::load()?.dependents(&skill.id);
+            if !dependents.is_empty() {
+                println!("  Required by: {}", dependents.join(", "));
+            }
 
             if skill.is_modified {
                 println!();
@@ -179,3 +656,815 @@ pub fn show(skill_id: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Restore a source-installed skill to its upstream files
+pub fn reset(skill_id: &str, yes: bool) -> Result<()> {
+    let skill =
+        Skills::get(skill_id)?.ok_or_else(|| anyhow::anyhow!("Skill '{}' not found", skill_id))?;
+
+    if !skill.is_modified {
+        Ui::info(&format!("'{}' has no local edits", skill_id));
+        return Ok(());
+    }
+
+    let confirmed = yes
+        || Confirm::new()
+            .with_prompt(format!("Discard the local edits of '{}'?", skill_id))
+            .default(false)
+            .interact()?;
+    if !confirmed {
+        Ui::info("Cancelled");
+        return Ok(());
+    }
+
+    Skills::reset(skill_id)?;
+    Ui::success(&format!("'{}' reset to its upstream version", skill_id));
+    Ok(())
+}
+
+/// List skills with newer upstream versions
+pub async fn outdated() -> Result<()> {
+    Ui::header("Outdated Skills");
+
+    Ui::step("Checking skill sources...");
+    let check = Skills::check_updates().await?;
+    let updates = &check.updates;
+    println!();
+
+    for warning in &check.warnings {
+        Ui::warning(warning);
+    }
+
+    if updates.is_empty() {
+        if check.warnings.is_empty() {
+            Ui::success("All skills are up to date");
+        } else {
+            Ui::info("The skills that could be checked are up to date");
+        }
+        return Ok(());
+    }
+
+    for update in updates {
+        let modified_badge = if update.is_modified {
+            " [modified]".yellow()
+        } else {
+            "".normal()
+        };
+
+        let (current, latest) = revision_labels(update);
+        println!(
+            "  {} {}  {} → {}{}",
+            "↑".yellow(),
+            update.skill_id.bold(),
+            current.dimmed(),
+            latest.green(),
+            modified_badge
+        );
+        println!("      Source: {}", update.source_id.dimmed());
+    }
+
+    println!();
+    Ui::info("Run 'rhinolabs-ai skill upgrade <id>' to update a skill.");
+    if updates.iter().any(|u| u.is_modified) {
+        Ui::info("Local modifications are merged; overlapping edits get conflict markers.");
+    }
+
+    Ok(())
+}
+
+/// Upgrade a skill to the latest upstream version
+pub async fn upgrade(skill_id: &str, force: bool) -> Result<()> {
+    Ui::header("Upgrade Skill");
+
+    Ui::step(&format!("Checking '{}' for updates...", skill_id));
+    if Skills::get(skill_id)?.is_none() {
+        anyhow::bail!("Skill '{}' not found", skill_id);
+    }
+    let Some(update) = Skills::check_update(skill_id).await? else {
+        Ui::success(&format!("'{}' is already up to date", skill_id));
+        return Ok(());
+    };
+
+    Ui::step(&format!("Downloading {}...", describe_update(&update)));
+    if update.is_modified {
+        if force {
+            Ui::warning("Discarding local modifications (--force)");
+        } else {
+            Ui::step("Merging local modifications...");
+        }
+    }
+    let result = Skills::upgrade(skill_id, force).await?;
+
+    println!();
+    for file in result
+        .files
+        .iter()
+        .filter(|f| f.status != MergeStatus::Unchanged)
+    {
+        let (marker, label) = match file.status {
+            MergeStatus::Updated => ("~".green(), "updated"),
+            MergeStatus::Added => ("+".green(), "added"),
+            MergeStatus::Removed => ("-".red(), "removed"),
+            MergeStatus::Merged => ("≈".cyan(), "merged"),
+            MergeStatus::Conflict => ("!".red().bold(), "conflict"),
+            MergeStatus::Unchanged => continue,
+        };
+        println!(
+            "  {} {} {}",
+            marker,
+            file.path,
+            format!("({})", label).dimmed()
+        );
+    }
+    println!();
+
+    if result.conflicts > 0 {
+        Ui::warning(&format!(
+            "Skill '{}' upgraded with {} conflicting file(s)",
+            result.skill_id, result.conflicts
+        ));
+        Ui::info("Resolve the <<<<<<< local / >>>>>>> upstream markers, then review the skill.");
+    } else {
+        Ui::success(&format!("Skill '{}' upgraded", result.skill_id));
+    }
+    Ui::info("Run 'rhinolabs-ai profile update' to refresh projects using this skill.");
+
+    Ok(())
+}
+
+/// Describe an update as "current → latest"
+fn describe_update(update: &SkillUpdate) -> String {
+    let (current, latest) = revision_labels(update);
+    format!("{} → {}", current, latest)
+}
+
+/// Labels for both revisions: the frontmatter version, or the short commit
+/// when the version is missing or unchanged
+fn revision_labels(update: &SkillUpdate) -> (String, String) {
+    let commit = |commit: &Option<String>| {
+        commit
+            .as_deref()
+            .map(|c| short_commit(c).to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+
+    match (&update.current_version, &update.latest_version) {
+        (Some(current), Some(latest)) if current != latest => (current.clone(), latest.clone()),
+        (None, Some(latest)) => (commit(&update.current_commit), latest.clone()),
+        _ => (
+            commit(&update.current_commit),
+            commit(&update.latest_commit),
+        ),
+    }
+}
+
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(7)]
+}
+
+/// List the skills a source offers
+pub async fn browse(source_id: &str, refresh: bool) -> Result<()> {
+    let source = find_source(source_id)?;
+    if !source.fetchable {
+        anyhow::bail!(
+            "'{}' can't be fetched automatically; browse it at {}",
+            source.name,
+            source.url
+        );
+    }
+
+    Ui::header(&format!("Skills in {}", source.name));
+    let skills = Skills::fetch_from_source(&source, refresh).await?;
+
+    if skills.is_empty() {
+        Ui::info("This source has no skills.");
+        return Ok(());
+    }
+
+    for skill in &skills {
+        let status = if skill.installed {
+            "●".green()
+        } else {
+            "○".dimmed()
+        };
+        println!();
+        println!("  {} {}", status, skill.name.bold());
+        println!("    ID: {}", skill.id.dimmed());
+        println!("    {}", skill.description);
+    }
+
+    println!();
+    Ui::info(&format!(
+        "Install with 'rhinolabs-ai skill install {}/<skill-id>'",
+        source.id
+    ));
+    Ok(())
+}
+
+/// Install a skill from a source, reviewing the security scan first
+pub async fn install(skill: &str, acknowledge: bool) -> Result<()> {
+    let (source_id, skill_id) = skill
+        .split_once('/')
+        .filter(|(source, id)| !source.is_empty() && !id.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Expected <source-id>/<skill-id>, got '{}'", skill))?;
+    let source = find_source(source_id)?;
+
+    if Skills::get(skill_id)?.is_some() {
+        anyhow::bail!(
+            "Skill '{}' is already installed. Use 'rhinolabs-ai skill upgrade {}'",
+            skill_id,
+            skill_id
+        );
+    }
+
+    Ui::header("Install Skill");
+    Ui::step(&format!("Scanning '{}' from {}...", skill_id, source.name));
+    let report = Skills::scan_remote(&source.url, skill_id, source_id).await?;
+    if !report.is_clean() {
+        print_findings(&report);
+        review_findings(&report, &source.source_type, acknowledge, true)?;
+    }
+
+    Ui::step(&format!("Installing '{}'...", skill_id));
+    let installed =
+        Skills::install_from_remote(&source.url, skill_id, &source.id, &source.name).await?;
+
+    print_installed(&installed);
+
+    Ok(())
+}
+
+/// Report an installed skill and any `requires` that aren't installed
+fn print_installed(skill: &Skill) {
+    println!();
+    Ui::success(&format!("Skill '{}' installed", skill.id));
+    Ui::info(&format!("Path: {}", skill.path));
+    let missing: Vec<&str> = skill
+        .requires
+        .iter()
+        .filter(|id| Skills::get(id).ok().flatten().is_none())
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        Ui::warning(&format!(
+            "Requires skills that aren't installed: {}",
+            missing.join(", ")
+        ));
+    }
+}
+
+/// Pack a skill into a .skill file
+pub fn pack(skill_id: &str, output: Option<String>) -> Result<()> {
+    let dest = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let path = Skills::pack(skill_id, &dest)?;
+
+    Ui::success(&format!("Skill '{}' packed", skill_id));
+    Ui::info(&format!("Package: {}", path.display()));
+    Ui::info("Install it elsewhere with 'rhinolabs-ai skill unpack <file>'");
+    Ok(())
+}
+
+/// Install a skill from a .skill file
+pub fn unpack(file: &str, acknowledge: bool) -> Result<()> {
+    let path = Path::new(file);
+
+    Ui::header("Install Skill Package");
+    Ui::step(&format!("Scanning {}...", path.display()));
+    // Packages are reviewed like skills from community sources
+    let report = Skills::scan_package(path)?;
+    if Skills::get(&report.skill_id)?.is_some() {
+        anyhow::bail!(
+            "Skill '{}' is already installed. Delete it first to install the package",
+            report.skill_id
+        );
+    }
+    if !report.is_clean() {
+        print_findings(&report);
+        review_findings(&report, &SkillSourceType::Community, acknowledge, true)?;
+    }
+
+    let installed = Skills::unpack(path)?;
+
+    print_installed(&installed);
+
+    Ok(())
+}
+
+/// List skill sources
+pub fn source_list() -> Result<()> {
+    Ui::header("Skill Sources");
+
+    for source in Skills::list_sources()? {
+        let status = if source.enabled {
+            "●".green()
+        } else {
+            "○".dimmed()
+        };
+        let mut badges = vec![source_type_name(&source.source_type).to_string()];
+        if source.schema == SkillSchema::Marketplace {
+            badges.push("marketplace".to_string());
+        }
+        if !source.fetchable {
+            badges.push("browse only".to_string());
+        }
+        if let Some(git_ref) = &source.git_ref {
+            badges.push(format!("ref {}", git_ref));
+        }
+
+        println!();
+        println!(
+            "  {} {} {}",
+            status,
+            source.name.bold(),
+            format!("[{}]", badges.join(", ")).dimmed()
+        );
+        println!("    ID:  {}", source.id.dimmed());
+        println!("    URL: {}", source.url);
+        if !source.description.is_empty() {
+            println!("    {}", source.description);
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Add a skill source
+pub fn source_add(
+    id: String,
+    url: String,
+    name: Option<String>,
+    source_type: Option<String>,
+    description: Option<String>,
+    git_ref: Option<String>,
+    marketplace: bool,
+) -> Result<()> {
+    let source_type = match source_type {
+        Some(name) => parse_source_type(&name)?,
+        None if SourceKind::detect(&url) == SourceKind::Local => SkillSourceType::Local,
+        None => SkillSourceType::Community,
+    };
+
+    let source = SkillSource {
+        name: name.unwrap_or_else(|| id.clone()),
+        id,
+        source_type,
+        url,
+        description: description.unwrap_or_default(),
+        enabled: true,
+        fetchable: true,
+        schema: if marketplace {
+            SkillSchema::Marketplace
+        } else {
+            SkillSchema::Standard
+        },
+        git_ref,
+        layout: None,
+        skill_count: None,
+    };
+    Skills::add_source(source.clone())?;
+
+    Ui::success(&format!("Source '{}' added", source.id));
+    Ui::info(&format!(
+        "Skills from {} sources: {}",
+        source_type_name(&source.source_type),
+        policy_display(Skills::scan_policy(&source.source_type)?)
+    ));
+    Ui::info(&format!(
+        "Browse it with 'rhinolabs-ai skill browse {}'",
+        source.id
+    ));
+    Ok(())
+}
+
+/// Remove a skill source
+pub fn source_remove(id: &str) -> Result<()> {
+    find_source(id)?;
+    Skills::remove_source(id)?;
+    Ui::success(&format!("Source '{}' removed", id));
+    Ok(())
+}
+
+/// Enable or disable a skill source
+pub fn source_toggle(id: &str, enabled: bool) -> Result<()> {
+    Skills::update_source(id, Some(enabled), None, None, None, None, None)?;
+    let state = if enabled { "enabled" } else { "disabled" };
+    Ui::success(&format!("Source '{}' {}", id, state));
+    Ok(())
+}
+
+/// List the plugins of a marketplace source with their components
+pub async fn plugins(source_id: &str) -> Result<()> {
+    Ui::header("Marketplace Plugins");
+
+    Ui::step(&format!("Reading marketplace '{}'...", source_id));
+    let catalog = Marketplace::list(source_id).await?;
+    println!();
+
+    println!(
+        "  {} {}",
+        catalog.name.bold(),
+        format!("@ {}", catalog.git_ref).dimmed()
+    );
+    if let Some(description) = &catalog.description {
+        println!("  {}", description.dimmed());
+    }
+
+    if catalog.plugins.is_empty() {
+        println!();
+        Ui::info("This marketplace lists no plugins.");
+        return Ok(());
+    }
+
+    for plugin in &catalog.plugins {
+        let version = plugin
+            .version
+            .as_ref()
+            .map(|v| format!(" v{}", v))
+            .unwrap_or_default();
+        println!();
+        println!("  {}{}", plugin.name.bold(), version.dimmed());
+        if !plugin.description.is_empty() {
+            println!("      {}", plugin.description);
+        }
+
+        let components = &plugin.components;
+        for (label, items) in [
+            ("Skills", &components.skills),
+            ("Commands", &components.commands),
+            ("Agents", &components.agents),
+            ("MCP servers", &components.mcp_servers),
+        ] {
+            if !items.is_empty() {
+                println!("      {}: {}", label, items.join(", ").dimmed());
+            }
+        }
+        if components.hooks {
+            println!("      Hooks: {}", "yes (not installed)".dimmed());
+        }
+    }
+
+    println!();
+    Ui::info(&format!(
+        "Run 'rhinolabs-ai skill install-plugin {} <plugin>' to install a plugin.",
+        source_id
+    ));
+
+    Ok(())
+}
+
+/// Install a whole plugin from a marketplace source, reviewing its commands,
+/// agents and MCP servers first
+pub async fn install_plugin(
+    source_id: &str,
+    plugin: &str,
+    mcp: bool,
+    acknowledge: bool,
+) -> Result<()> {
+    let source = find_source(source_id)?;
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+
+    Ui::header("Install Plugin");
+    Ui::step(&format!("Scanning '{}' from {}...", plugin, source.name));
+    let review = Marketplace::review_plugin(source_id, plugin).await?;
+    if !review.report.is_clean() {
+        print_findings(&review.report);
+        review_findings(
+            &review.report,
+            &source.source_type,
+            acknowledge,
+            interactive,
+        )?;
+    }
+
+    let mut mcp = mcp;
+    if !review.mcp_servers.is_empty() {
+        println!();
+        println!("  {}", "MCP servers (each runs the command shown):".bold());
+        for (name, server) in &review.mcp_servers {
+            println!("    {} {}", name.bold(), mcp_command_line(server).dimmed());
+        }
+        println!();
+        mcp = mcp
+            || (interactive
+                && Confirm::new()
+                    .with_prompt(format!(
+                        "Add these {} MCP server(s) to the MCP config?",
+                        review.mcp_servers.len()
+                    ))
+                    .default(false)
+                    .interact()?);
+    }
+
+    Ui::step(&format!("Installing '{}' from '{}'...", plugin, source_id));
+    let options = PluginInstallOptions { mcp_servers: mcp };
+    let result = Marketplace::install_plugin(source_id, plugin, &options).await?;
+    println!();
+
+    for (label, items) in [
+        ("skill", &result.skills),
+        ("command", &result.commands),
+        ("agent", &result.agents),
+    ] {
+        for item in items {
+            println!("  {} {} {}", "+".green(), label, item.bold());
+        }
+    }
+    for (name, server) in &result.mcp_servers {
+        println!(
+            "  {} MCP server {} {}",
+            "+".green(),
+            name.bold(),
+            mcp_command_line(server).dimmed()
+        );
+    }
+    for skipped in &result.skipped {
+        println!("  {} {}", "-".dimmed(), skipped.dimmed());
+    }
+
+    println!();
+    Ui::success(&format!("Plugin '{}' installed", result.plugin));
+    if !mcp && !review.mcp_servers.is_empty() {
+        Ui::info("MCP servers were left out. Run again with --mcp to add them.");
+    }
+
+    Ok(())
+}
+
+/// What an MCP server runs: its command line, or the URL it connects to
+fn mcp_command_line(server: &McpServer) -> String {
+    match (&server.command, &server.url) {
+        (Some(command), _) => std::iter::once(command.as_str())
+            .chain(server.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" "),
+        (None, Some(url)) => url.clone(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Check skills against the skill guidelines
+pub fn lint(skill_ids: Vec<String>, all: bool, format: String) -> Result<()> {
+    let json = match format.as_str() {
+        "json" => true,
+        "text" => false,
+        other => anyhow::bail!("Unknown format '{}'. Use 'text' or 'json'", other),
+    };
+    if !all && skill_ids.is_empty() {
+        anyhow::bail!("Specify the skills to check, or --all");
+    }
+
+    let linter = SkillLinter::load()?;
+    let report = if all {
+        linter.lint_all()?
+    } else {
+        linter.lint(&skill_ids)?
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        Ui::header("Skill Lint");
+
+        let mut current_skill: Option<&str> = None;
+        for finding in &report.findings {
+            if current_skill != Some(finding.skill_id.as_str()) {
+                current_skill = Some(&finding.skill_id);
+                println!();
+                println!("  {}", finding.skill_id.bold());
+            }
+
+            let severity = match finding.severity {
+                LintSeverity::Error => "error".red(),
+                LintSeverity::Warning => "warning".yellow(),
+            };
+            let location = match (&finding.file, finding.line) {
+                (Some(file), Some(line)) => format!("{}:{}", file, line),
+                (Some(file), None) => file.clone(),
+                _ => String::new(),
+            };
+            println!(
+                "    {} {} {} {}",
+                severity,
+                format!("[{}]", finding.rule).dimmed(),
+                location.dimmed(),
+                finding.message
+            );
+        }
+
+        println!();
+        if report.is_clean() {
+            Ui::success(&format!(
+                "{} skill(s) checked, no issues",
+                report.skills_checked
+            ));
+        } else {
+            println!(
+                "  {} skill(s) checked: {} errors, {} warnings",
+                report.skills_checked,
+                report.errors.to_string().red(),
+                report.warnings.to_string().yellow()
+            );
+        }
+    }
+
+    if report.errors > 0 {
+        anyhow::bail!("Skill lint found {} error(s)", report.errors);
+    }
+
+    Ok(())
+}
+
+/// Scan a skill from a source and acknowledge its findings
+pub async fn scan(
+    source_id: &str,
+    skill_id: &str,
+    acknowledge: bool,
+    format: String,
+) -> Result<()> {
+    let json = match format.as_str() {
+        "json" => true,
+        "text" => false,
+        other => anyhow::bail!("Unknown format '{}'. Use 'text' or 'json'", other),
+    };
+    let source = find_source(source_id)?;
+
+    if !json {
+        Ui::header("Skill Security Scan");
+        Ui::step(&format!(
+            "Downloading '{}' from {}...",
+            skill_id, source.name
+        ));
+    }
+    let report = Skills::scan_remote(&source.url, skill_id, source_id).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_findings(&report);
+    }
+
+    if report.is_clean() {
+        if !json {
+            Ui::success(&format!("No risks found in '{}'", skill_id));
+        }
+        return Ok(());
+    }
+
+    review_findings(&report, &source.source_type, acknowledge, !json)?;
+    if !json {
+        Ui::success(&format!(
+            "Findings acknowledged; '{}' can be installed as scanned",
+            skill_id
+        ));
+    }
+
+    Ok(())
+}
+
+/// Print the findings of a security scan
+fn print_findings(report: &SecurityReport) {
+    println!();
+    for finding in &report.findings {
+        let level = match finding.level {
+            RiskLevel::High => "high".red().bold(),
+            RiskLevel::Medium => "medium".yellow(),
+            RiskLevel::Low => "low".normal(),
+        };
+        let location = match finding.line {
+            Some(line) => format!("{}:{}", finding.file, line),
+            None => finding.file.clone(),
+        };
+        println!("  {} {} {}", level, location.dimmed(), finding.message);
+        if let Some(excerpt) = &finding.excerpt {
+            println!("      {}", excerpt.dimmed());
+        }
+    }
+    if !report.is_clean() {
+        println!();
+    }
+}
+
+/// Acknowledge the findings of a scan, asking first unless `acknowledge`.
+/// Fails for blocked skills and when the user declines.
+fn review_findings(
+    report: &SecurityReport,
+    source_type: &SkillSourceType,
+    acknowledge: bool,
+    interactive: bool,
+) -> Result<()> {
+    if report.blocked {
+        anyhow::bail!(
+            "'{}' has {} finding(s) and {} sources are set to block. \
+             Change it with 'rhinolabs-ai skill scan-policy'",
+            report.skill_id,
+            report.findings.len(),
+            source_type_name(source_type)
+        );
+    }
+
+    let acknowledged = acknowledge
+        || (interactive
+            && Confirm::new()
+                .with_prompt(format!(
+                    "Acknowledge these {} finding(s) and allow installing '{}'?",
+                    report.findings.len(),
+                    report.skill_id
+                ))
+                .default(false)
+                .interact()?);
+    if !acknowledged {
+        anyhow::bail!(
+            "'{}' was not acknowledged and can't be installed",
+            report.skill_id
+        );
+    }
+
+    Skills::acknowledge_risks(&report.skill_id, &report.fingerprint)?;
+    Ok(())
+}
+
+pub fn scan_policy(source_type: Option<String>, policy: Option<String>) -> Result<()> {
+    if let (Some(source_type), Some(policy)) = (&source_type, &policy) {
+        let source_type = parse_source_type(source_type)?;
+        let policy = match policy.to_lowercase().as_str() {
+            "block" => ScanPolicy::Block,
+            "warn" => ScanPolicy::Warn,
+            other => anyhow::bail!("Unknown policy '{}'. Use 'block' or 'warn'", other),
+        };
+
+        Skills::set_scan_policy(source_type.clone(), policy)?;
+        Ui::success(&format!(
+            "Skills from {} sources: {}",
+            source_type_name(&source_type),
+            policy_display(policy)
+        ));
+        return Ok(());
+    }
+
+    Ui::header("Skill Scan Policy");
+    for source_type in SOURCE_TYPES.iter().filter(|t| {
+        source_type
+            .as_ref()
+            .is_none_or(|s| source_type_name(t) == s.to_lowercase())
+    }) {
+        println!(
+            "  {:<12} {}",
+            source_type_name(source_type),
+            policy_display(Skills::scan_policy(source_type)?)
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+const SOURCE_TYPES: [SkillSourceType; 4] = [
+    SkillSourceType::Official,
+    SkillSourceType::Marketplace,
+    SkillSourceType::Community,
+    SkillSourceType::Local,
+];
+
+fn parse_source_type(name: &str) -> Result<SkillSourceType> {
+    SOURCE_TYPES
+        .iter()
+        .find(|t| source_type_name(t) == name.to_lowercase())
+        .cloned()
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown source type '{}'. Use official, marketplace, community or local",
+                name
+            )
+        })
+}
+
+fn find_source(source_id: &str) -> Result<SkillSource> {
+    Skills::list_sources()?
+        .into_iter()
+        .find(|s| s.id == source_id)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Source '{}' not found. See 'rhinolabs-ai skill source list'",
+                source_id
+            )
+        })
+}
+
+fn source_type_name(source_type: &SkillSourceType) -> &'static str {
+    match source_type {
+        SkillSourceType::Official => "official",
+        SkillSourceType::Marketplace => "marketplace",
+        SkillSourceType::Community => "community",
+        SkillSourceType::Local => "local",
+    }
+}
+
+fn policy_display(policy: ScanPolicy) -> colored::ColoredString {
+    match policy {
+        ScanPolicy::Block => "block (skills with findings are refused)".red(),
+        ScanPolicy::Warn => "warn (findings must be acknowledged)".yellow(),
+    }
+}
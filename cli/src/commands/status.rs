@@ -1,7 +1,7 @@
 use crate::ui::Ui;
 use anyhow::Result;
 use colored::*;
-use rhinolabs_core::{Paths, Version};
+use rhinolabs_core::{Channels, Paths, Version};
 
 pub fn run() -> Result<()> {
     Ui::header("📊 Rhinolabs AI Plugin Status");
@@ -26,6 +26,10 @@ pub fn run() -> Result<()> {
             println!("  Location:     {}", plugin_dir.display());
         }
 
+        if let Ok(channel) = Channels::current() {
+            println!("  Channel:      {}", channel.to_string().cyan());
+        }
+
         println!("  Status:       {}", "✓ Installed".green());
     } else {
         println!("  Status:       {}", "✗ Not installed".red());
@@ -0,0 +1,253 @@
+use crate::editor::Editor;
+use crate::ui::Ui;
+use anyhow::Result;
+use colored::Colorize;
+use dialoguer::Confirm;
+use rhinolabs_core::{OutputStyles, ProfileType, Profiles, UpdateProfileInput};
+use std::fs;
+
+/// Re-select the active style after its name changed (settings refer to it by name)
+fn keep_active(style_id: &str, was_active: bool) -> Result<()> {
+    if was_active {
+        OutputStyles::set_active(style_id)?;
+    }
+    Ok(())
+}
+
+fn is_active(style_id: &str) -> Result<bool> {
+    Ok(OutputStyles::get_active()?.is_some_and(|s| s.id == style_id))
+}
+
+/// Ids of the profiles that install a style
+fn profiles_using(style_id: &str) -> Result<Vec<String>> {
+    Ok(Profiles::list()?
+        .into_iter()
+        .filter(|p| p.output_style.as_deref() == Some(style_id))
+        .map(|p| p.id)
+        .collect())
+}
+
+/// List output styles
+pub fn list() -> Result<()> {
+    Ui::header("Output Styles");
+
+    let styles = OutputStyles::list()?;
+    if styles.is_empty() {
+        Ui::info("No output styles yet.");
+        Ui::info("Create one with 'rhinolabs-ai style create --name <name>'.");
+        return Ok(());
+    }
+
+    let active = OutputStyles::get_active()?.map(|s| s.id);
+
+    for style in &styles {
+        let status = if active.as_ref() == Some(&style.id) {
+            format!("{} {}", "●".green(), "(active)".green())
+        } else {
+            "○".dimmed().to_string()
+        };
+
+        println!();
+        println!("  {} {}", style.name.bold(), status);
+        println!("    ID: {}", style.id.dimmed());
+        if !style.description.is_empty() {
+            println!("    {}", style.description);
+        }
+        let profiles = profiles_using(&style.id)?;
+        if !profiles.is_empty() {
+            println!("    Profiles: {}", profiles.join(", ").cyan());
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Show an output style
+pub fn show(style_id: &str) -> Result<()> {
+    let style = OutputStyles::get(style_id)?
+        .ok_or_else(|| anyhow::anyhow!("Output style '{}' not found", style_id))?;
+
+    Ui::header(&format!("Output Style: {}", style.name));
+    println!("  ID:          {}", style.id);
+    println!("  Description: {}", style.description);
+    println!(
+        "  Coding:      {}",
+        if style.keep_coding_instructions {
+            "Claude Code's instructions kept"
+        } else {
+            "Claude Code's instructions replaced"
+        }
+    );
+    if is_active(&style.id)? {
+        println!("  Active:      {}", "yes".green());
+    }
+    let profiles = profiles_using(&style.id)?;
+    if !profiles.is_empty() {
+        println!("  Profiles:    {}", profiles.join(", "));
+    }
+    println!(
+        "  Path:        {}",
+        OutputStyles::get_style_path(&style.id)?.display()
+    );
+
+    Ui::section("Content");
+    println!("{}", style.content);
+    println!();
+    Ok(())
+}
+
+/// Create an output style
+pub fn create(
+    name: &str,
+    description: Option<String>,
+    keep_coding_instructions: bool,
+    file: Option<String>,
+) -> Result<()> {
+    // Same id the core derives; it names the style's file
+    let id = name.to_lowercase().replace(' ', "-");
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        anyhow::bail!(
+            "Invalid style name '{}': use letters, digits, spaces and hyphens",
+            name
+        );
+    }
+
+    let content = match file {
+        Some(file) => fs::read_to_string(&file)
+            .map_err(|e| anyhow::anyhow!("Could not read '{}': {}", file, e))?,
+        None => format!(
+            "# {}\n\nDescribe how Claude should communicate in this style.",
+            name
+        ),
+    };
+    let description = description.unwrap_or_else(|| format!("Custom style: {}", name));
+
+    let style = OutputStyles::create(name, &description, keep_coding_instructions, &content)?;
+
+    Ui::success(&format!("Output style '{}' created", style.id));
+    Ui::info(&format!(
+        "Path: {}",
+        OutputStyles::get_style_path(&style.id)?.display()
+    ));
+    Ui::info(&format!(
+        "Edit it with 'rhinolabs-ai style edit {}'",
+        style.id
+    ));
+    Ok(())
+}
+
+/// Change an output style's metadata, or edit its file when no flag is given
+pub fn edit(
+    style_id: &str,
+    name: Option<String>,
+    description: Option<String>,
+    keep_coding_instructions: Option<bool>,
+) -> Result<()> {
+    if OutputStyles::get(style_id)?.is_none() {
+        anyhow::bail!("Output style '{}' not found", style_id);
+    }
+    let was_active = is_active(style_id)?;
+
+    if name.is_none() && description.is_none() && keep_coding_instructions.is_none() {
+        Editor::open(&OutputStyles::get_style_path(style_id)?)?;
+        OutputStyles::get(style_id)
+            .map_err(|e| anyhow::anyhow!("The style no longer parses: {}", e))?;
+    } else {
+        OutputStyles::update(
+            style_id,
+            name.as_deref(),
+            description.as_deref(),
+            keep_coding_instructions,
+            None,
+        )?;
+    }
+    keep_active(style_id, was_active)?;
+
+    Ui::success(&format!("Output style '{}' saved", style_id));
+    let profiles = profiles_using(style_id)?;
+    if !profiles.is_empty() {
+        Ui::info(&format!(
+            "Run 'rhinolabs-ai profile update' in projects using {} to apply it.",
+            profiles.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Delete an output style
+pub fn delete(style_id: &str, yes: bool) -> Result<()> {
+    if OutputStyles::get(style_id)?.is_none() {
+        anyhow::bail!("Output style '{}' not found", style_id);
+    }
+
+    if is_active(style_id)? {
+        Ui::warning("This is the active output style.");
+    }
+    let profiles = profiles_using(style_id)?;
+    if !profiles.is_empty() {
+        Ui::warning(&format!(
+            "Used by profiles (they will have no style): {}",
+            profiles.join(", ")
+        ));
+    }
+
+    let confirmed = yes
+        || Confirm::new()
+            .with_prompt(format!("Delete output style '{}'?", style_id))
+            .default(false)
+            .interact()?;
+    if !confirmed {
+        Ui::info("Cancelled");
+        return Ok(());
+    }
+
+    OutputStyles::delete(style_id)?;
+    Ui::success(&format!("Output style '{}' deleted", style_id));
+    Ok(())
+}
+
+/// Activate a style globally, or select it for a project profile
+pub fn activate(style_id: &str, profile_id: Option<String>) -> Result<()> {
+    match profile_id {
+        Some(profile_id) => {
+            let profile = Profiles::get(&profile_id)?
+                .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found", profile_id))?;
+            if profile.profile_type == ProfileType::User {
+                anyhow::bail!(
+                    "The Main-Profile uses the active style. Run 'rhinolabs-ai style activate {}'",
+                    style_id
+                );
+            }
+
+            Profiles::update(
+                &profile_id,
+                UpdateProfileInput {
+                    name: None,
+                    description: None,
+                    profile_type: None,
+                    instructions: None,
+                    generate_copilot: None,
+                    generate_agents: None,
+                    token_budget: None,
+                    output_style: Some(style_id.to_string()),
+                },
+            )?;
+            Ui::success(&format!(
+                "Profile '{}' now installs output style '{}'",
+                profile_id, style_id
+            ));
+            Ui::info("Run 'rhinolabs-ai profile update' in its projects to apply it.");
+        }
+        None => {
+            OutputStyles::set_active(style_id)?;
+            Ui::success(&format!("'{}' is now the active output style", style_id));
+            Ui::info("Run 'rhinolabs-ai profile install main' to apply it to ~/.claude/.");
+        }
+    }
+    Ok(())
+}
@@ -1,13 +1,18 @@
 use crate::ui::Ui;
 use anyhow::Result;
 use indicatif::{ProgressBar, ProgressStyle};
-use rhinolabs_core::{Updater, Version};
+use rhinolabs_core::{Channels, ReleaseChannel, Updater, Version};
 
-pub async fn run(dry_run: bool) -> Result<()> {
+pub async fn run(dry_run: bool, channel: Option<String>) -> Result<()> {
     Ui::header("🔄 Updating Rhinolabs Claude Plugin");
 
+    let channel: ReleaseChannel = match channel {
+        Some(channel) => channel.parse()?,
+        None => Channels::current()?,
+    };
+
     // Check for updates
-    Ui::step("Checking for updates...");
+    Ui::step(&format!("Checking for updates ({} channel)...", channel));
 
     let pb = ProgressBar::new_spinner();
     pb.set_style(
@@ -17,7 +22,7 @@ pub async fn run(dry_run: bool) -> Result<()> {
     );
     pb.enable_steady_tick(std::time::Duration::from_millis(100));
 
-    match Version::check_update().await? {
+    match Version::check_update_for(channel).await? {
         Some(version) => {
             pb.finish_and_clear();
             Ui::success(format!("Update available: v{}", version).as_str());
@@ -28,7 +33,7 @@ pub async fn run(dry_run: bool) -> Result<()> {
             }
 
             Ui::step("Downloading latest version...");
-            let updater = Updater::new().dry_run(dry_run);
+            let updater = Updater::new().dry_run(dry_run).channel(channel);
             updater.update().await?;
 
             println!();
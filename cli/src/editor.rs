@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Opens files in the user's editor ($VISUAL, then $EDITOR)
+pub struct Editor;
+
+impl Editor {
+    /// Edit `path` and wait for the editor to exit
+    pub fn open(path: &Path) -> Result<()> {
+        let editor = Self::command();
+        // Allow editors with arguments, e.g. EDITOR="code --wait"
+        let mut parts = editor.split_whitespace();
+        let program = parts.next().unwrap_or(Self::fallback());
+
+        let status = Command::new(program)
+            .args(parts)
+            .arg(path)
+            .status()
+            .with_context(|| {
+                format!(
+                    "Could not start editor '{}'. Set $EDITOR to your editor",
+                    program
+                )
+            })?;
+
+        if !status.success() {
+            anyhow::bail!("Editor '{}' exited with {}", program, status);
+        }
+        Ok(())
+    }
+
+    fn command() -> String {
+        ["VISUAL", "EDITOR"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| Self::fallback().to_string())
+    }
+
+    fn fallback() -> &'static str {
+        if cfg!(windows) {
+            "notepad"
+        } else {
+            "vi"
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod commands;
+pub mod editor;
 pub mod ui;
 
 use clap::{Parser, Subcommand};
@@ -31,6 +32,10 @@ enum Commands {
         /// Dry run - show what would be done without making changes
         #[arg(long)]
         dry_run: bool,
+
+        /// Release channel to update from (defaults to this machine's channel)
+        #[arg(long)]
+        channel: Option<String>,
     },
 
     /// Uninstall the plugin
@@ -76,8 +81,44 @@ enum Commands {
         action: SkillAction,
     },
 
+    /// Manage output styles
+    Style {
+        #[command(subcommand)]
+        action: StyleAction,
+    },
+
     /// Sync configuration from GitHub (pull latest deployed config)
-    Sync,
+    Sync {
+        /// How to resolve locally modified items: keep-local, take-remote, write-remote
+        #[arg(long, default_value = "keep-local")]
+        strategy: String,
+
+        /// Per-item override, e.g. --resolve skills/react-patterns=take-remote
+        #[arg(long = "resolve", value_name = "ITEM=STRATEGY")]
+        resolve: Vec<String>,
+
+        /// Release channel to sync from (defaults to this machine's channel)
+        #[arg(long)]
+        channel: Option<String>,
+    },
+
+    /// Show or change the release channel (stable, beta, canary)
+    Channel {
+        #[command(subcommand)]
+        action: Option<ChannelAction>,
+    },
+
+    /// Manage the GitHub token used for private repositories and deploys
+    Auth {
+        #[command(subcommand)]
+        action: Option<AuthAction>,
+    },
+
+    /// Inspect configuration changes
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
 
     /// Manage RAG (Retrieval-Augmented Generation) for project memory
     Rag {
@@ -123,12 +164,204 @@ enum ProfileAction {
         #[arg(short = 'P', long)]
         path: Option<String>,
     },
+
+    /// Create a project profile (asks for anything not given as a flag)
+    Create {
+        /// Unique profile identifier (e.g., "react-stack")
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Display name for the profile
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Optional description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Skill to assign (repeatable)
+        #[arg(short, long = "skill", value_name = "SKILL_ID")]
+        skills: Vec<String>,
+
+        /// File with the profile's instructions (defaults to a template)
+        #[arg(long, value_name = "FILE")]
+        instructions: Option<String>,
+
+        /// Don't generate .github/copilot-instructions.md
+        #[arg(long)]
+        no_copilot: bool,
+
+        /// Generate AGENTS.md as master file
+        #[arg(long)]
+        agents: bool,
+
+        /// Token budget for the profile's context
+        #[arg(long)]
+        budget: Option<usize>,
+
+        /// Output style installed with the profile
+        #[arg(long, value_name = "STYLE_ID")]
+        output_style: Option<String>,
+    },
+
+    /// Change a profile's settings (asks when no flag is given)
+    Edit {
+        /// Profile ID to edit
+        profile_id: String,
+
+        /// New display name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// New description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Generate .github/copilot-instructions.md
+        #[arg(long, value_name = "BOOL")]
+        copilot: Option<bool>,
+
+        /// Generate AGENTS.md as master file
+        #[arg(long, value_name = "BOOL")]
+        agents: Option<bool>,
+
+        /// Token budget (0 goes back to the default)
+        #[arg(long)]
+        budget: Option<usize>,
+
+        /// Output style installed with the profile ("" removes it)
+        #[arg(long, value_name = "STYLE_ID")]
+        output_style: Option<String>,
+    },
+
+    /// Open a profile's instructions in $VISUAL / $EDITOR
+    Instructions {
+        /// Profile ID
+        profile_id: String,
+
+        /// Print the instructions instead of editing them
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Delete a profile
+    Delete {
+        /// Profile ID to delete
+        profile_id: String,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Set the skills of a profile (asks when no skill is given)
+    Assign {
+        /// Profile ID
+        profile_id: String,
+
+        /// Skills to assign (replace the current ones unless --add or --remove)
+        skills: Vec<String>,
+
+        /// Add the skills to the current ones
+        #[arg(long, conflicts_with = "remove")]
+        add: bool,
+
+        /// Remove the skills from the current ones
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Manage when each skill of a profile is loaded
+    Rules {
+        #[command(subcommand)]
+        action: ProfileRulesAction,
+    },
+
+    /// Show or set the default user profile
+    Default {
+        /// Profile ID to make the default
+        profile_id: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileRulesAction {
+    /// List the auto-invoke rules of a profile
+    List {
+        /// Profile ID
+        profile_id: String,
+    },
+
+    /// Add or replace the rule of a skill
+    Set {
+        /// Profile ID
+        profile_id: String,
+
+        /// Skill the rule applies to
+        skill_id: String,
+
+        /// When to load the skill (e.g., "Editing .tsx/.jsx files")
+        #[arg(long)]
+        trigger: String,
+
+        /// What the skill provides
+        #[arg(long)]
+        description: Option<String>,
+    },
+
+    /// Remove the rule of a skill
+    Remove {
+        /// Profile ID
+        profile_id: String,
+
+        /// Skill the rule applies to
+        skill_id: String,
+    },
 }
 
 #[derive(Subcommand)]
 enum SkillAction {
     /// List all skills
-    List,
+    List {
+        /// Only skills with this tag (repeat to require several)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Only skills in this category
+        #[arg(long)]
+        category: Option<String>,
+    },
+
+    /// Full-text search of installed skills and cached source catalogs
+    Search {
+        /// Words to look for in names, tags, descriptions and content
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only installed skills
+        #[arg(long, conflicts_with = "remote")]
+        local: bool,
+
+        /// Only skills from source catalogs
+        #[arg(long)]
+        remote: bool,
+
+        /// Only skills from this source
+        #[arg(long)]
+        source: Option<String>,
+
+        /// Maximum number of results (0 = all)
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Refetch the catalogs of all enabled sources before searching
+        #[arg(long, conflicts_with = "local")]
+        refresh: bool,
+
+        /// Output format: text, json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
 
     /// Show details of a specific skill
     Show {
@@ -146,7 +379,7 @@ enum SkillAction {
         #[arg(long)]
         name: String,
 
-        /// Skill category: corporate, frontend, testing, ai-sdk, utilities, custom
+        /// Skill category id (see 'skill categories')
         #[arg(long, default_value = "custom")]
         category: String,
 
@@ -155,14 +388,430 @@ enum SkillAction {
         description: Option<String>,
     },
 
+    /// Scaffold a new custom skill from a template
+    New {
+        /// Unique skill identifier (e.g., "my-skill")
+        skill_id: String,
+
+        /// Template id (see 'skill templates')
+        #[arg(short, long, default_value = "basic")]
+        template: String,
+
+        /// Name in the frontmatter (default: the id)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Skill category id (see 'skill categories')
+        #[arg(long, default_value = "custom")]
+        category: String,
+
+        /// Optional description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Value of a template variable (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+    },
+
+    /// List the templates 'skill new' can use
+    Templates,
+
+    /// Open a skill's SKILL.md in $VISUAL / $EDITOR
+    Edit {
+        /// Skill ID to edit
+        skill_id: String,
+    },
+
+    /// Enable a disabled skill
+    Enable {
+        /// Skill ID to enable
+        skill_id: String,
+    },
+
+    /// Disable a skill without deleting it
+    Disable {
+        /// Skill ID to disable
+        skill_id: String,
+    },
+
+    /// Delete a custom or source-installed skill
+    Delete {
+        /// Skill ID to delete
+        skill_id: String,
+
+        /// Delete even if other skills require it
+        #[arg(long)]
+        force: bool,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Rename a custom skill, updating profiles and the skills config
+    Rename {
+        /// Current skill ID
+        skill_id: String,
+
+        /// New skill ID
+        new_id: String,
+    },
+
+    /// Copy a skill (e.g. a built-in) into a new custom skill
+    Clone {
+        /// Skill ID to copy
+        skill_id: String,
+
+        /// ID of the new custom skill
+        new_id: String,
+    },
+
     /// Set the category for an existing skill
     SetCategory {
         /// Skill ID to update
         skill_id: String,
 
-        /// New category: corporate, frontend, testing, ai-sdk, utilities, custom
+        /// New category id (see 'skill categories')
         category: String,
     },
+
+    /// Replace the tags of a skill (no tags clears them)
+    SetTags {
+        /// Skill ID to update
+        skill_id: String,
+
+        /// Tags, e.g. "docker kubernetes"
+        tags: Vec<String>,
+    },
+
+    /// List the skill categories
+    Categories,
+
+    /// Add a skill category, or update an existing one
+    AddCategory {
+        /// Category id (lowercase letters, digits and dashes)
+        id: String,
+
+        /// Display name (default: the id)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Position in listings, lowest first (default: after the others)
+        #[arg(long)]
+        order: Option<u32>,
+
+        /// Badge color, e.g. "#0ea5e9"
+        #[arg(long)]
+        color: Option<String>,
+    },
+
+    /// Remove a skill category (its skills become custom)
+    RemoveCategory {
+        /// Category id
+        id: String,
+    },
+
+    /// List installed skills with newer versions at their source
+    Outdated,
+
+    /// Update an installed skill to the latest version from its source
+    Upgrade {
+        /// Skill ID to upgrade
+        skill_id: String,
+
+        /// Discard local modifications instead of merging them
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Discard local edits of a source-installed skill
+    Reset {
+        /// Skill ID to reset
+        skill_id: String,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// List the skills available in a source
+    Browse {
+        /// Source ID (see 'skill source list')
+        source_id: String,
+
+        /// Fetch the source again instead of using the cached catalog
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Install a skill from a source, e.g. 'anthropic-official/pdf'
+    Install {
+        /// <source-id>/<skill-id>
+        skill: String,
+
+        /// Acknowledge security scan findings without prompting
+        #[arg(long)]
+        acknowledge: bool,
+    },
+
+    /// Pack a skill into a .skill file to share it
+    Pack {
+        /// Skill ID to pack
+        skill_id: String,
+
+        /// Package file, or directory to write it to (default: current directory)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Install a skill from a .skill file
+    Unpack {
+        /// Path of the .skill file
+        file: String,
+
+        /// Acknowledge security scan findings without prompting
+        #[arg(long)]
+        acknowledge: bool,
+    },
+
+    /// Manage the sources skills are installed from
+    Source {
+        #[command(subcommand)]
+        action: SkillSourceAction,
+    },
+
+    /// List the plugins of a plugin marketplace source
+    Plugins {
+        /// Source ID (a source with the marketplace schema)
+        source_id: String,
+    },
+
+    /// Install a plugin's skills, commands, agents and MCP servers
+    InstallPlugin {
+        /// Source ID (a source with the marketplace schema)
+        source_id: String,
+
+        /// Plugin name from the marketplace
+        plugin: String,
+
+        /// Also add the plugin's MCP servers to the MCP config
+        #[arg(long)]
+        mcp: bool,
+
+        /// Acknowledge security scan findings without prompting
+        #[arg(long)]
+        acknowledge: bool,
+    },
+
+    /// Check skills against the skill guidelines (exits non-zero on errors)
+    Lint {
+        /// Skill IDs to check
+        skill_ids: Vec<String>,
+
+        /// Check every installed skill
+        #[arg(long, conflicts_with = "skill_ids")]
+        all: bool,
+
+        /// Output format: text, json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Scan a skill from a source for security risks before installing it
+    Scan {
+        /// Source ID
+        source_id: String,
+
+        /// Skill ID within the source
+        skill_id: String,
+
+        /// Acknowledge the findings without prompting
+        #[arg(long)]
+        acknowledge: bool,
+
+        /// Output format: text, json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Show or set what the security scan does per source type
+    ScanPolicy {
+        /// Source type: official, marketplace, community, local
+        source_type: Option<String>,
+
+        /// Policy: block (refuse skills with findings) or warn (require acknowledgement)
+        #[arg(requires = "source_type")]
+        policy: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SkillSourceAction {
+    /// List skill sources
+    List,
+
+    /// Add a skill source (GitHub repository, git URL, directory or archive)
+    Add {
+        /// Unique source identifier (e.g., "team-skills")
+        id: String,
+
+        /// Repository URL, directory path or archive URL
+        url: String,
+
+        /// Display name (default: the id)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Source type: official, marketplace, community, local
+        /// (default: local for directories, community otherwise)
+        #[arg(long = "type")]
+        source_type: Option<String>,
+
+        /// Short description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Branch, tag or commit to install from (default: the default branch)
+        #[arg(long = "ref")]
+        git_ref: Option<String>,
+
+        /// Read the source as a Claude Code plugin marketplace
+        #[arg(long)]
+        marketplace: bool,
+    },
+
+    /// Remove a skill source (built-in sources can only be disabled)
+    Remove {
+        /// Source ID to remove
+        id: String,
+    },
+
+    /// Enable a skill source
+    Enable {
+        /// Source ID to enable
+        id: String,
+    },
+
+    /// Disable a skill source
+    Disable {
+        /// Source ID to disable
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum StyleAction {
+    /// List output styles
+    List,
+
+    /// Show an output style
+    Show {
+        /// Style ID to show
+        style_id: String,
+    },
+
+    /// Create an output style
+    Create {
+        /// Display name (the id is derived from it)
+        #[arg(long)]
+        name: String,
+
+        /// Optional description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Keep Claude Code's coding instructions alongside the style
+        #[arg(long)]
+        keep_coding_instructions: bool,
+
+        /// File with the style's instructions (defaults to a template)
+        #[arg(long, value_name = "FILE")]
+        file: Option<String>,
+    },
+
+    /// Change an output style (opens $VISUAL / $EDITOR when no flag is given)
+    Edit {
+        /// Style ID to edit
+        style_id: String,
+
+        /// New display name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// New description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Keep Claude Code's coding instructions alongside the style
+        #[arg(long, value_name = "BOOL")]
+        keep_coding_instructions: Option<bool>,
+    },
+
+    /// Delete an output style
+    Delete {
+        /// Style ID to delete
+        style_id: String,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Make a style the active one, or the style of a project profile
+    Activate {
+        /// Style ID to activate
+        style_id: String,
+
+        /// Project profile that installs the style (instead of the global style)
+        #[arg(long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ChannelAction {
+    /// Show the release channel this machine follows
+    Show,
+
+    /// Follow a release channel: stable, beta, canary
+    Set {
+        /// Channel name
+        channel: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthAction {
+    /// Show which GitHub token is used and where it comes from
+    Status,
+
+    /// Store a GitHub token in the OS keyring
+    Login {
+        /// Token to store (prompted if omitted)
+        #[arg(long)]
+        token: Option<String>,
+    },
+
+    /// Remove the stored GitHub token
+    Logout,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Show what changed between two configurations
+    /// (defaults to the latest deployed release vs local config)
+    Diff {
+        /// Old config bundle (.zip); defaults to the latest deployed release
+        old: Option<String>,
+
+        /// New config bundle (.zip); defaults to the local configuration
+        new: Option<String>,
+
+        /// Output format: text, json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -228,8 +877,8 @@ pub async fn run() -> anyhow::Result<()> {
         Some(Commands::Install { local, dry_run }) => {
             install::run(local, dry_run).await?;
         }
-        Some(Commands::Update { dry_run }) => {
-            update::run(dry_run).await?;
+        Some(Commands::Update { dry_run, channel }) => {
+            update::run(dry_run, channel).await?;
         }
         Some(Commands::Uninstall { dry_run }) => {
             uninstall::run(dry_run)?;
@@ -262,10 +911,110 @@ pub async fn run() -> anyhow::Result<()> {
             ProfileAction::Uninstall { path } => {
                 profile::uninstall(path)?;
             }
+            ProfileAction::Create {
+                id,
+                name,
+                description,
+                skills,
+                instructions,
+                no_copilot,
+                agents,
+                budget,
+                output_style,
+            } => {
+                profile::create(profile::CreateOptions {
+                    id,
+                    name,
+                    description,
+                    skills,
+                    instructions,
+                    generate_copilot: !no_copilot,
+                    generate_agents: agents,
+                    budget,
+                    output_style,
+                })?;
+            }
+            ProfileAction::Edit {
+                profile_id,
+                name,
+                description,
+                copilot,
+                agents,
+                budget,
+                output_style,
+            } => {
+                profile::edit(
+                    &profile_id,
+                    profile::EditOptions {
+                        name,
+                        description,
+                        copilot,
+                        agents,
+                        budget,
+                        output_style,
+                    },
+                )?;
+            }
+            ProfileAction::Instructions { profile_id, print } => {
+                profile::instructions(&profile_id, print)?;
+            }
+            ProfileAction::Delete { profile_id, yes } => {
+                profile::delete(&profile_id, yes)?;
+            }
+            ProfileAction::Assign {
+                profile_id,
+                skills,
+                add,
+                remove,
+            } => {
+                profile::assign(&profile_id, skills, add, remove)?;
+            }
+            ProfileAction::Rules { action } => match action {
+                ProfileRulesAction::List { profile_id } => {
+                    profile::rules_list(&profile_id)?;
+                }
+                ProfileRulesAction::Set {
+                    profile_id,
+                    skill_id,
+                    trigger,
+                    description,
+                } => {
+                    profile::rules_set(&profile_id, &skill_id, trigger, description)?;
+                }
+                ProfileRulesAction::Remove {
+                    profile_id,
+                    skill_id,
+                } => {
+                    profile::rules_remove(&profile_id, &skill_id)?;
+                }
+            },
+            ProfileAction::Default { profile_id } => {
+                profile::default(profile_id)?;
+            }
         },
         Some(Commands::Skill { action }) => match action {
-            SkillAction::List => {
-                skill::list()?;
+            SkillAction::List { tags, category } => {
+                skill::list(category, tags)?;
+            }
+            SkillAction::Search {
+                query,
+                local,
+                remote,
+                source,
+                limit,
+                refresh,
+                format,
+            } => {
+                skill::search(
+                    &query.join(" "),
+                    local,
+                    remote,
+                    source,
+                    limit,
+                    refresh,
+                    format,
+                )
+                .await?;
             }
             SkillAction::Show { skill_id } => {
                 skill::show(&skill_id)?;
@@ -278,14 +1027,210 @@ pub async fn run() -> anyhow::Result<()> {
             } => {
                 skill::create(id, name, category, description)?;
             }
+            SkillAction::New {
+                skill_id,
+                template,
+                name,
+                category,
+                description,
+                vars,
+            } => {
+                skill::new(skill_id, template, name, category, description, vars)?;
+            }
+            SkillAction::Templates => {
+                skill::templates()?;
+            }
+            SkillAction::Edit { skill_id } => {
+                skill::edit(&skill_id)?;
+            }
+            SkillAction::Enable { skill_id } => {
+                skill::toggle(&skill_id, true)?;
+            }
+            SkillAction::Disable { skill_id } => {
+                skill::toggle(&skill_id, false)?;
+            }
+            SkillAction::Delete {
+                skill_id,
+                force,
+                yes,
+            } => {
+                skill::delete(&skill_id, force, yes)?;
+            }
             SkillAction::SetCategory { skill_id, category } => {
                 skill::set_category(skill_id, category)?;
             }
+            SkillAction::SetTags { skill_id, tags } => {
+                skill::set_tags(skill_id, tags)?;
+            }
+            SkillAction::Categories => {
+                skill::categories()?;
+            }
+            SkillAction::AddCategory {
+                id,
+                name,
+                order,
+                color,
+            } => {
+                skill::add_category(id, name, order, color)?;
+            }
+            SkillAction::RemoveCategory { id } => {
+                skill::remove_category(id)?;
+            }
+            SkillAction::Outdated => {
+                skill::outdated().await?;
+            }
+            SkillAction::Upgrade { skill_id, force } => {
+                skill::upgrade(&skill_id, force).await?;
+            }
+            SkillAction::Reset { skill_id, yes } => {
+                skill::reset(&skill_id, yes)?;
+            }
+            SkillAction::Browse { source_id, refresh } => {
+                skill::browse(&source_id, refresh).await?;
+            }
+            SkillAction::Install { skill, acknowledge } => {
+                skill::install(&skill, acknowledge).await?;
+            }
+            SkillAction::Rename { skill_id, new_id } => {
+                skill::rename(&skill_id, &new_id)?;
+            }
+            SkillAction::Clone { skill_id, new_id } => {
+                skill::clone(&skill_id, &new_id)?;
+            }
+            SkillAction::Pack { skill_id, output } => {
+                skill::pack(&skill_id, output)?;
+            }
+            SkillAction::Unpack { file, acknowledge } => {
+                skill::unpack(&file, acknowledge)?;
+            }
+            SkillAction::Source { action } => match action {
+                SkillSourceAction::List => {
+                    skill::source_list()?;
+                }
+                SkillSourceAction::Add {
+                    id,
+                    url,
+                    name,
+                    source_type,
+                    description,
+                    git_ref,
+                    marketplace,
+                } => {
+                    skill::source_add(
+                        id,
+                        url,
+                        name,
+                        source_type,
+                        description,
+                        git_ref,
+                        marketplace,
+                    )?;
+                }
+                SkillSourceAction::Remove { id } => {
+                    skill::source_remove(&id)?;
+                }
+                SkillSourceAction::Enable { id } => {
+                    skill::source_toggle(&id, true)?;
+                }
+                SkillSourceAction::Disable { id } => {
+                    skill::source_toggle(&id, false)?;
+                }
+            },
+            SkillAction::Plugins { source_id } => {
+                skill::plugins(&source_id).await?;
+            }
+            SkillAction::InstallPlugin {
+                source_id,
+                plugin,
+                mcp,
+                acknowledge,
+            } => {
+                skill::install_plugin(&source_id, &plugin, mcp, acknowledge).await?;
+            }
+            SkillAction::Lint {
+                skill_ids,
+                all,
+                format,
+            } => {
+                skill::lint(skill_ids, all, format)?;
+            }
+            SkillAction::Scan {
+                source_id,
+                skill_id,
+                acknowledge,
+                format,
+            } => {
+                skill::scan(&source_id, &skill_id, acknowledge, format).await?;
+            }
+            SkillAction::ScanPolicy {
+                source_type,
+                policy,
+            } => {
+                skill::scan_policy(source_type, policy)?;
+            }
         },
-        Some(Commands::Sync) => {
+        Some(Commands::Style { action }) => match action {
+            StyleAction::List => {
+                style::list()?;
+            }
+            StyleAction::Show { style_id } => {
+                style::show(&style_id)?;
+            }
+            StyleAction::Create {
+                name,
+                description,
+                keep_coding_instructions,
+                file,
+            } => {
+                style::create(&name, description, keep_coding_instructions, file)?;
+            }
+            StyleAction::Edit {
+                style_id,
+                name,
+                description,
+                keep_coding_instructions,
+            } => {
+                style::edit(&style_id, name, description, keep_coding_instructions)?;
+            }
+            StyleAction::Delete { style_id, yes } => {
+                style::delete(&style_id, yes)?;
+            }
+            StyleAction::Activate { style_id, profile } => {
+                style::activate(&style_id, profile)?;
+            }
+        },
+        Some(Commands::Sync {
+            strategy,
+            resolve,
+            channel,
+        }) => {
             // Manual sync - always runs regardless of session marker
-            deploy::sync().await?;
+            deploy::sync(strategy, resolve, channel).await?;
         }
+        Some(Commands::Channel { action }) => match action {
+            None | Some(ChannelAction::Show) => {
+                channel::show()?;
+            }
+            Some(ChannelAction::Set { channel }) => {
+                channel::set(channel)?;
+            }
+        },
+        Some(Commands::Auth { action }) => match action {
+            None | Some(AuthAction::Status) => {
+                auth::status()?;
+            }
+            Some(AuthAction::Login { token }) => {
+                auth::login(token)?;
+            }
+            Some(AuthAction::Logout) => {
+                auth::logout()?;
+            }
+        },
+        Some(Commands::Config { action }) => match action {
+            ConfigAction::Diff { old, new, format } => {
+                config::diff(old, new, format).await?;
+            }
+        },
         Some(Commands::Rag { action }) => match action {
             RagAction::Init { project, api_key } => {
                 rag::init(project, api_key)?;
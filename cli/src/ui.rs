@@ -35,4 +35,13 @@ impl Ui {
         println!();
         println!("{}", title.bold().underline());
     }
+
+    /// Format an estimated token count, e.g. `~1.2k tokens`
+    pub fn tokens(count: usize) -> String {
+        if count >= 1000 {
+            format!("~{:.1}k tokens", count as f64 / 1000.0)
+        } else {
+            format!("~{} tokens", count)
+        }
+    }
 }
@@ -0,0 +1,386 @@
+//! Release channels module for staged rollouts
+//!
+//! This module handles:
+//! - The per-machine release channel (stable, beta, canary)
+//! - Mapping channels to release tags (`config-v1.2.0-beta`, `v1.2.0-canary`)
+//! - Picking the highest release version a channel accepts
+//! - Promoting a release to a more stable channel
+
+use crate::{GitHub, GitHubCredentials, Paths, Result, RhinolabsError};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Release channel, ordered from most to least stable
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseChannel {
+    #[default]
+    Stable,
+    Beta,
+    Canary,
+}
+
+impl ReleaseChannel {
+    pub const ALL: [ReleaseChannel; 3] = [Self::Stable, Self::Beta, Self::Canary];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Beta => "beta",
+            Self::Canary => "canary",
+        }
+    }
+
+    /// Suffix appended to release tags of this channel
+    pub fn tag_suffix(&self) -> &'static str {
+        match self {
+            Self::Stable => "",
+            Self::Beta => "-beta",
+            Self::Canary => "-canary",
+        }
+    }
+
+    /// Releases outside the stable channel are published as GitHub prereleases
+    pub fn is_prerelease(&self) -> bool {
+        *self != Self::Stable
+    }
+
+    /// Build a release tag, e.g. `tag("config-v", "1.2.0")` -> `config-v1.2.0-beta`
+    pub fn tag(&self, prefix: &str, version: &str) -> String {
+        format!("{}{}{}", prefix, version, self.tag_suffix())
+    }
+
+    /// Channel a release tag was published to
+    pub fn from_tag(tag: &str) -> Self {
+        if tag.ends_with(Self::Canary.tag_suffix()) {
+            Self::Canary
+        } else if tag.ends_with(Self::Beta.tag_suffix()) {
+            Self::Beta
+        } else {
+            Self::Stable
+        }
+    }
+
+    /// Whether a machine on this channel takes releases from `release`.
+    /// Less stable channels also receive everything more stable channels get.
+    pub fn accepts(&self, release: ReleaseChannel) -> bool {
+        release <= *self
+    }
+}
+
+impl std::fmt::Display for ReleaseChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ReleaseChannel {
+    type Err = RhinolabsError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "stable" => Ok(Self::Stable),
+            "beta" => Ok(Self::Beta),
+            "canary" => Ok(Self::Canary),
+            _ => Err(RhinolabsError::ConfigError(format!(
+                "Invalid release channel: {}. Use stable, beta, or canary",
+                s
+            ))),
+        }
+    }
+}
+
+/// Per-machine channel settings (channel.json in the rhinolabs config dir)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChannelSettings {
+    #[serde(default)]
+    channel: ReleaseChannel,
+}
+
+pub struct Channels;
+
+impl Channels {
+    fn settings_path() -> Result<PathBuf> {
+        Ok(Paths::rhinolabs_config_dir()?.join("channel.json"))
+    }
+
+    /// Get the release channel this machine follows
+    pub fn current() -> Result<ReleaseChannel> {
+        let path = Self::settings_path()?;
+
+        if !path.exists() {
+            return Ok(ReleaseChannel::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let settings: ChannelSettings = serde_json::from_str(&content)?;
+        Ok(settings.channel)
+    }
+
+    /// Set the release channel this machine follows
+    pub fn set(channel: ReleaseChannel) -> Result<()> {
+        let path = Self::settings_path()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(&ChannelSettings { channel })?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// Pick the highest version a channel accepts from a GitHub releases list.
+    /// Creation order doesn't count: promoting a release creates a new one
+    /// after newer beta and canary releases. On equal versions the more
+    /// stable release wins; tags without a version come last. Drafts are ignored.
+    pub fn select_release(
+        releases: &[serde_json::Value],
+        channel: ReleaseChannel,
+        is_candidate: impl Fn(&str) -> bool,
+    ) -> Option<&serde_json::Value> {
+        let mut best: Option<(&serde_json::Value, Option<semver::Version>, ReleaseChannel)> = None;
+        for release in releases {
+            if release["draft"].as_bool().unwrap_or(false) {
+                continue;
+            }
+            let Some(tag) = release["tag_name"].as_str() else {
+                continue;
+            };
+            let release_channel = ReleaseChannel::from_tag(tag);
+            if !is_candidate(tag) || !channel.accepts(release_channel) {
+                continue;
+            }
+            let version = Self::tag_version(tag);
+            let better = match &best {
+                None => true,
+                Some((_, best_version, best_channel)) => {
+                    (&version, std::cmp::Reverse(release_channel))
+                        > (best_version, std::cmp::Reverse(*best_channel))
+                }
+            };
+            if better {
+                best = Some((release, version, release_channel));
+            }
+        }
+        best.map(|(release, _, _)| release)
+    }
+
+    /// Version in a release tag, e.g. `config-v1.2.0-beta` -> 1.2.0
+    fn tag_version(tag: &str) -> Option<semver::Version> {
+        let tag = tag
+            .strip_suffix(ReleaseChannel::from_tag(tag).tag_suffix())
+            .unwrap_or(tag);
+        let start = tag.find(|c: char| c.is_ascii_digit())?;
+        semver::Version::parse(&tag[start..]).ok()
+    }
+
+    /// Promote a release to a more stable channel
+    ///
+    /// Creates a new release tagged for `to` with the same notes and assets,
+    /// leaving the original release in place. Returns the new release URL.
+    pub async fn promote_release(
+        owner: &str,
+        repo: &str,
+        tag_prefix: &str,
+        version: &str,
+        from: ReleaseChannel,
+        to: ReleaseChannel,
+    ) -> Result<String> {
+        if to >= from {
+            return Err(RhinolabsError::ConfigError(format!(
+                "Cannot promote from {} to {}: target must be a more stable channel",
+                from, to
+            )));
+        }
+
+        GitHubCredentials::require()?;
+
+        let client = reqwest::Client::new();
+        let source_tag = from.tag(tag_prefix, version);
+        let target_tag = to.tag(tag_prefix, version);
+
+        // 1. Find the source release
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/releases/tags/{}",
+            owner, repo, source_tag
+        );
+        let response = GitHub::api(&client, &url).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(RhinolabsError::ConfigError(format!(
+                "Release {} not found",
+                source_tag
+            )));
+        }
+        let response = GitHub::check(response, "Failed to fetch release").await?;
+
+        let source: serde_json::Value = response.json().await?;
+
+        // 2. Create the promoted release
+        let body = format!(
+            "{}\n\n_Promoted from {}._",
+            source["body"].as_str().unwrap_or("").trim_end(),
+            source_tag
+        );
+        let name = source["name"]
+            .as_str()
+            .unwrap_or(&source_tag)
+            .replace(&source_tag, &target_tag);
+
+        let url = format!("https://api.github.com/repos/{}/{}/releases", owner, repo);
+        let response = GitHub::post(&client, &url)?
+            .json(&serde_json::json!({
+                "tag_name": target_tag,
+                "target_commitish": source["target_commitish"],
+                "name": name,
+                "body": body,
+                "prerelease": to.is_prerelease(),
+                "draft": false,
+            }))
+            .send()
+            .await?;
+
+        let response = GitHub::check(response, "Failed to create release").await?;
+
+        let release: serde_json::Value = response.json().await?;
+        let release_id = release["id"]
+            .as_u64()
+            .ok_or_else(|| RhinolabsError::Other("Failed to get release ID".into()))?;
+        let release_url = release["html_url"].as_str().unwrap_or("").to_string();
+
+        // 3. Copy assets
+        for asset in source["assets"].as_array().into_iter().flatten() {
+            let Some(name) = asset["name"].as_str() else {
+                continue;
+            };
+
+            let content = GitHub::download_asset(&client, asset).await?;
+
+            let upload_url = format!(
+                "https://uploads.github.com/repos/{}/{}/releases/{}/assets?name={}",
+                owner, repo, release_id, name
+            );
+            let upload = GitHub::post(&client, &upload_url)?
+                .header(
+                    "Content-Type",
+                    asset["content_type"]
+                        .as_str()
+                        .unwrap_or("application/octet-stream"),
+                )
+                .body(content)
+                .send()
+                .await?;
+
+            GitHub::check(upload, &format!("Failed to copy asset {}", name)).await?;
+        }
+
+        Ok(release_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_channel_tags() {
+        assert_eq!(
+            ReleaseChannel::Stable.tag("config-v", "1.2.0"),
+            "config-v1.2.0"
+        );
+        assert_eq!(
+            ReleaseChannel::Beta.tag("config-v", "1.2.0"),
+            "config-v1.2.0-beta"
+        );
+        assert_eq!(ReleaseChannel::Canary.tag("v", "1.2.0"), "v1.2.0-canary");
+
+        for channel in ReleaseChannel::ALL {
+            assert_eq!(
+                ReleaseChannel::from_tag(&channel.tag("config-v", "2.0.0")),
+                channel
+            );
+        }
+    }
+
+    #[test]
+    fn test_channel_parse_and_prerelease() {
+        assert_eq!(
+            "beta".parse::<ReleaseChannel>().unwrap(),
+            ReleaseChannel::Beta
+        );
+        assert_eq!(
+            "CANARY".parse::<ReleaseChannel>().unwrap(),
+            ReleaseChannel::Canary
+        );
+        assert!("nightly".parse::<ReleaseChannel>().is_err());
+
+        assert!(!ReleaseChannel::Stable.is_prerelease());
+        assert!(ReleaseChannel::Beta.is_prerelease());
+    }
+
+    #[test]
+    fn test_channel_accepts_more_stable_releases() {
+        assert!(ReleaseChannel::Stable.accepts(ReleaseChannel::Stable));
+        assert!(!ReleaseChannel::Stable.accepts(ReleaseChannel::Beta));
+        assert!(ReleaseChannel::Beta.accepts(ReleaseChannel::Stable));
+        assert!(!ReleaseChannel::Beta.accepts(ReleaseChannel::Canary));
+        assert!(ReleaseChannel::Canary.accepts(ReleaseChannel::Beta));
+    }
+
+    #[test]
+    fn test_select_release_by_channel() {
+        let releases = vec![
+            json!({ "tag_name": "config-v1.3.0-canary" }),
+            json!({ "tag_name": "v2.0.0" }),
+            json!({ "tag_name": "config-v1.2.0-beta", "draft": true }),
+            json!({ "tag_name": "config-v1.2.0-beta" }),
+            json!({ "tag_name": "config-v1.1.0" }),
+        ];
+        let is_config = |tag: &str| tag.starts_with("config-v");
+
+        let pick = |channel| {
+            Channels::select_release(&releases, channel, is_config)
+                .and_then(|r| r["tag_name"].as_str())
+        };
+
+        assert_eq!(pick(ReleaseChannel::Stable), Some("config-v1.1.0"));
+        assert_eq!(pick(ReleaseChannel::Beta), Some("config-v1.2.0-beta"));
+        assert_eq!(pick(ReleaseChannel::Canary), Some("config-v1.3.0-canary"));
+    }
+
+    #[test]
+    fn test_select_release_after_promotion() {
+        // 1.2.0 was promoted to stable after 1.3.0-beta was published
+        let releases = vec![
+            json!({ "tag_name": "v1.2.0" }),
+            json!({ "tag_name": "v1.3.0-beta" }),
+            json!({ "tag_name": "v1.2.0-beta" }),
+            json!({ "tag_name": "v1.10.0-canary" }),
+            json!({ "tag_name": "v1.1.0" }),
+        ];
+        let pick = |channel| {
+            Channels::select_release(&releases, channel, |_: &str| true)
+                .and_then(|r| r["tag_name"].as_str())
+        };
+
+        assert_eq!(pick(ReleaseChannel::Stable), Some("v1.2.0"));
+        assert_eq!(pick(ReleaseChannel::Beta), Some("v1.3.0-beta"));
+        assert_eq!(pick(ReleaseChannel::Canary), Some("v1.10.0-canary"));
+    }
+
+    #[test]
+    fn test_channel_settings_roundtrip() {
+        let _lock = crate::test_utils::ENV_MUTEX.lock().unwrap();
+        let env = crate::test_utils::TestEnv::new();
+
+        assert_eq!(Channels::current().unwrap(), ReleaseChannel::Stable);
+        Channels::set(ReleaseChannel::Canary).unwrap();
+        assert_eq!(Channels::current().unwrap(), ReleaseChannel::Canary);
+        assert!(env.config_dir().join("channel.json").exists());
+    }
+}
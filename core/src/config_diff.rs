@@ -0,0 +1,565 @@
+//! Config diff module for comparing deployed configurations
+//!
+//! This module handles:
+//! - Loading a configuration snapshot from a bundle or the local state
+//! - Computing a structured diff between two snapshots
+//! - Rendering the diff as a Markdown changelog
+
+use crate::{Deploy, PermissionConfig, Profile, Result};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Kind of change for a single entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Skills assigned/unassigned for a profile present on both sides
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSkillChanges {
+    pub profile_id: String,
+    pub skills_assigned: Vec<String>,
+    pub skills_unassigned: Vec<String>,
+}
+
+/// Text diff of a SKILL.md present on both sides
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillTextDiff {
+    pub skill_id: String,
+    pub additions: usize,
+    pub deletions: usize,
+    /// Unified diff of the SKILL.md content
+    pub diff: String,
+}
+
+/// Permission rule added or removed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionChange {
+    /// Permission list: "allow", "ask" or "deny"
+    pub list: String,
+    pub rule: String,
+    pub change: ChangeKind,
+}
+
+/// MCP server added, removed or reconfigured
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServerChange {
+    pub name: String,
+    pub change: ChangeKind,
+}
+
+/// Structured diff between two configuration snapshots
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDiff {
+    pub profiles_added: Vec<String>,
+    pub profiles_removed: Vec<String>,
+    pub profile_changes: Vec<ProfileSkillChanges>,
+    pub skills_added: Vec<String>,
+    pub skills_removed: Vec<String>,
+    pub skill_changes: Vec<SkillTextDiff>,
+    pub permission_changes: Vec<PermissionChange>,
+    pub mcp_changes: Vec<McpServerChange>,
+}
+
+/// The parts of a configuration bundle that the diff looks at
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSnapshot {
+    profiles: Vec<Profile>,
+    /// skill_id -> SKILL.md content
+    skills: BTreeMap<String, String>,
+    permissions: PermissionConfig,
+    /// server name -> raw server config
+    mcp_servers: BTreeMap<String, serde_json::Value>,
+}
+
+impl ConfigSnapshot {
+    /// Load a snapshot from a config bundle zip buffer
+    pub fn from_bundle(zip_content: &[u8]) -> Result<Self> {
+        let bundle = Deploy::load_bundle(zip_content)?;
+        let mut snapshot = ConfigSnapshot::default();
+
+        for (name, content) in bundle.files {
+            let Ok(content) = String::from_utf8(content) else {
+                // Binary supporting files are not part of the diff
+                continue;
+            };
+
+            if name == "profiles.json" {
+                let json: serde_json::Value = serde_json::from_str(&content)?;
+                snapshot.profiles = serde_json::from_value(json["profiles"].clone())?;
+            } else if name == "settings.json" {
+                let json: serde_json::Value = serde_json::from_str(&content)?;
+                if !json["permissions"].is_null() {
+                    snapshot.permissions = serde_json::from_value(json["permissions"].clone())?;
+                }
+            } else if name == ".mcp.json" {
+                let json: serde_json::Value = serde_json::from_str(&content)?;
+                if let Some(servers) = json["mcpServers"].as_object() {
+                    snapshot.mcp_servers = servers
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect();
+                }
+            } else if let Some(rest) = name.strip_prefix("skills/") {
+                if let Some(skill_id) = rest.strip_suffix("/SKILL.md") {
+                    if !skill_id.contains('/') {
+                        snapshot.skills.insert(skill_id.to_string(), content);
+                    }
+                }
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Load a snapshot from a config bundle file
+    pub fn from_bundle_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read(path)?;
+        Self::from_bundle(&content)
+    }
+
+    /// Snapshot the local configuration (exported the same way as a deploy)
+    pub fn local() -> Result<Self> {
+        let temp_dir = tempfile::tempdir()?;
+        let (zip_path, _) = Deploy::export_config(temp_dir.path())?;
+        Self::from_bundle_file(&zip_path)
+    }
+}
+
+impl ConfigDiff {
+    /// Compare two snapshots (old -> new)
+    pub fn compare(old: &ConfigSnapshot, new: &ConfigSnapshot) -> Self {
+        let mut diff = ConfigDiff::default();
+
+        // Profiles
+        let old_profiles: BTreeMap<&str, &Profile> =
+            old.profiles.iter().map(|p| (p.id.as_str(), p)).collect();
+        let new_profiles: BTreeMap<&str, &Profile> =
+            new.profiles.iter().map(|p| (p.id.as_str(), p)).collect();
+
+        for (id, new_profile) in &new_profiles {
+            match old_profiles.get(id) {
+                None => diff.profiles_added.push(id.to_string()),
+                Some(old_profile) => {
+                    let (assigned, unassigned) =
+                        Self::set_changes(&old_profile.skills, &new_profile.skills);
+                    if !assigned.is_empty() || !unassigned.is_empty() {
+                        diff.profile_changes.push(ProfileSkillChanges {
+                            profile_id: id.to_string(),
+                            skills_assigned: assigned,
+                            skills_unassigned: unassigned,
+                        });
+                    }
+                }
+            }
+        }
+        diff.profiles_removed = old_profiles
+            .keys()
+            .filter(|id| !new_profiles.contains_key(*id))
+            .map(|id| id.to_string())
+            .collect();
+
+        // Skills
+        for (id, new_content) in &new.skills {
+            match old.skills.get(id) {
+                None => diff.skills_added.push(id.clone()),
+                Some(old_content) if old_content != new_content => {
+                    diff.skill_changes
+                        .push(Self::skill_text_diff(id, old_content, new_content));
+                }
+                Some(_) => {}
+            }
+        }
+        diff.skills_removed = old
+            .skills
+            .keys()
+            .filter(|id| !new.skills.contains_key(*id))
+            .cloned()
+            .collect();
+
+        // Permissions
+        for (list, old_rules, new_rules) in [
+            ("allow", &old.permissions.allow, &new.permissions.allow),
+            ("ask", &old.permissions.ask, &new.permissions.ask),
+            ("deny", &old.permissions.deny, &new.permissions.deny),
+        ] {
+            let (added, removed) = Self::set_changes(old_rules, new_rules);
+            for rule in added {
+                diff.permission_changes.push(PermissionChange {
+                    list: list.to_string(),
+                    rule,
+                    change: ChangeKind::Added,
+                });
+            }
+            for rule in removed {
+                diff.permission_changes.push(PermissionChange {
+                    list: list.to_string(),
+                    rule,
+                    change: ChangeKind::Removed,
+                });
+            }
+        }
+
+        // MCP servers
+        let names: BTreeSet<&String> = old
+            .mcp_servers
+            .keys()
+            .chain(new.mcp_servers.keys())
+            .collect();
+        for name in names {
+            let change = match (old.mcp_servers.get(name), new.mcp_servers.get(name)) {
+                (None, Some(_)) => ChangeKind::Added,
+                (Some(_), None) => ChangeKind::Removed,
+                (Some(a), Some(b)) if a != b => ChangeKind::Modified,
+                _ => continue,
+            };
+            diff.mcp_changes.push(McpServerChange {
+                name: name.clone(),
+                change,
+            });
+        }
+
+        diff
+    }
+
+    /// Compare the local configuration against the latest deployed release
+    /// Returns the release version and the diff (release -> local)
+    pub async fn local_vs_latest() -> Result<(String, Self)> {
+        let (version, bundle) = Deploy::fetch_latest_bundle().await?;
+        let released = ConfigSnapshot::from_bundle(&bundle)?;
+        let local = ConfigSnapshot::local()?;
+        Ok((version, Self::compare(&released, &local)))
+    }
+
+    /// Check if the two snapshots are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.profiles_added.is_empty()
+            && self.profiles_removed.is_empty()
+            && self.profile_changes.is_empty()
+            && self.skills_added.is_empty()
+            && self.skills_removed.is_empty()
+            && self.skill_changes.is_empty()
+            && self.permission_changes.is_empty()
+            && self.mcp_changes.is_empty()
+    }
+
+    /// Render the diff as a Markdown changelog (used for release notes)
+    pub fn to_markdown(&self) -> String {
+        if self.is_empty() {
+            return "No configuration changes.\n".to_string();
+        }
+
+        let mut out = String::new();
+
+        if !self.profiles_added.is_empty()
+            || !self.profiles_removed.is_empty()
+            || !self.profile_changes.is_empty()
+        {
+            out.push_str("### Profiles\n\n");
+            for id in &self.profiles_added {
+                out.push_str(&format!("- Added profile `{}`\n", id));
+            }
+            for id in &self.profiles_removed {
+                out.push_str(&format!("- Removed profile `{}`\n", id));
+            }
+            for change in &self.profile_changes {
+                for skill in &change.skills_assigned {
+                    out.push_str(&format!(
+                        "- `{}`: assigned skill `{}`\n",
+                        change.profile_id, skill
+                    ));
+                }
+                for skill in &change.skills_unassigned {
+                    out.push_str(&format!(
+                        "- `{}`: unassigned skill `{}`\n",
+                        change.profile_id, skill
+                    ));
+                }
+            }
+            out.push('\n');
+        }
+
+        if !self.skills_added.is_empty()
+            || !self.skills_removed.is_empty()
+            || !self.skill_changes.is_empty()
+        {
+            out.push_str("### Skills\n\n");
+            for id in &self.skills_added {
+                out.push_str(&format!("- Added skill `{}`\n", id));
+            }
+            for id in &self.skills_removed {
+                out.push_str(&format!("- Removed skill `{}`\n", id));
+            }
+            for change in &self.skill_changes {
+                out.push_str(&format!(
+                    "- Updated skill `{}` (+{} -{})\n",
+                    change.skill_id, change.additions, change.deletions
+                ));
+            }
+            out.push('\n');
+        }
+
+        if !self.permission_changes.is_empty() {
+            out.push_str("### Permissions\n\n");
+            for change in &self.permission_changes {
+                let verb = match change.change {
+                    ChangeKind::Added => "Added",
+                    ChangeKind::Removed => "Removed",
+                    ChangeKind::Modified => "Changed",
+                };
+                out.push_str(&format!(
+                    "- {} `{}` rule `{}`\n",
+                    verb, change.list, change.rule
+                ));
+            }
+            out.push('\n');
+        }
+
+        if !self.mcp_changes.is_empty() {
+            out.push_str("### MCP Servers\n\n");
+            for change in &self.mcp_changes {
+                let verb = match change.change {
+                    ChangeKind::Added => "Added",
+                    ChangeKind::Removed => "Removed",
+                    ChangeKind::Modified => "Reconfigured",
+                };
+                out.push_str(&format!("- {} `{}`\n", verb, change.name));
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Entries added and removed between two lists (order-insensitive)
+    fn set_changes(old: &[String], new: &[String]) -> (Vec<String>, Vec<String>) {
+        let added = new.iter().filter(|s| !old.contains(s)).cloned().collect();
+        let removed = old.iter().filter(|s| !new.contains(s)).cloned().collect();
+        (added, removed)
+    }
+
+    /// Line diff of a SKILL.md
+    fn skill_text_diff(skill_id: &str, old: &str, new: &str) -> SkillTextDiff {
+        let text_diff = TextDiff::from_lines(old, new);
+
+        let mut additions = 0;
+        let mut deletions = 0;
+        for change in text_diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => additions += 1,
+                ChangeTag::Delete => deletions += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+
+        let path = format!("skills/{}/SKILL.md", skill_id);
+        let diff = text_diff
+            .unified_diff()
+            .context_radius(3)
+            .header(&path, &path)
+            .to_string();
+
+        SkillTextDiff {
+            skill_id: skill_id.to_string(),
+            additions,
+            deletions,
+            diff,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProfileType;
+
+    fn profile(id: &str, skills: &[&str]) -> Profile {
+        Profile {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            profile_type: ProfileType::Project,
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            auto_invoke_rules: Vec::new(),
+            instructions: None,
+            generate_copilot: false,
+            generate_agents: false,
+            token_budget: None,
+            output_style: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn build_bundle(files: &[(&str, String)]) -> Vec<u8> {
+        use std::io::{Cursor, Write};
+        use zip::write::FileOptions;
+
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            zip.start_file(*name, FileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_identical_snapshots_have_empty_diff() {
+        let snapshot = ConfigSnapshot {
+            profiles: vec![profile("frontend", &["react-patterns"])],
+            ..Default::default()
+        };
+
+        let diff = ConfigDiff::compare(&snapshot, &snapshot);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_markdown(), "No configuration changes.\n");
+    }
+
+    #[test]
+    fn test_profile_and_assignment_changes() {
+        let old = ConfigSnapshot {
+            profiles: vec![
+                profile("main", &["rhinolabs-standards"]),
+                profile("legacy", &[]),
+            ],
+            ..Default::default()
+        };
+        let new = ConfigSnapshot {
+            profiles: vec![
+                profile("main", &["rhinolabs-standards", "playwright"]),
+                profile("frontend", &["react-patterns"]),
+            ],
+            ..Default::default()
+        };
+
+        let diff = ConfigDiff::compare(&old, &new);
+
+        assert_eq!(diff.profiles_added, vec!["frontend"]);
+        assert_eq!(diff.profiles_removed, vec!["legacy"]);
+        assert_eq!(diff.profile_changes.len(), 1);
+        assert_eq!(diff.profile_changes[0].profile_id, "main");
+        assert_eq!(diff.profile_changes[0].skills_assigned, vec!["playwright"]);
+        assert!(diff.profile_changes[0].skills_unassigned.is_empty());
+    }
+
+    #[test]
+    fn test_skill_text_diff() {
+        let mut old = ConfigSnapshot::default();
+        old.skills.insert("a".into(), "line 1\nline 2\n".into());
+        old.skills.insert("gone".into(), "x\n".into());
+        let mut new = ConfigSnapshot::default();
+        new.skills
+            .insert("a".into(), "line 1\nline 2 changed\nline 3\n".into());
+        new.skills.insert("b".into(), "new\n".into());
+
+        let diff = ConfigDiff::compare(&old, &new);
+
+        assert_eq!(diff.skills_added, vec!["b"]);
+        assert_eq!(diff.skills_removed, vec!["gone"]);
+        assert_eq!(diff.skill_changes.len(), 1);
+        let change = &diff.skill_changes[0];
+        assert_eq!(change.additions, 2);
+        assert_eq!(change.deletions, 1);
+        assert!(change.diff.contains("+line 2 changed"));
+        assert!(change.diff.contains("-line 2"));
+    }
+
+    #[test]
+    fn test_permission_and_mcp_changes_from_bundles() {
+        let old = build_bundle(&[
+            (
+                "settings.json",
+                r#"{"permissions":{"allow":["Read"],"ask":[],"deny":["Read(.env)"]}}"#.into(),
+            ),
+            (
+                ".mcp.json",
+                r#"{"mcpServers":{"git":{"command":"npx"},"old":{"command":"x"}}}"#.into(),
+            ),
+        ]);
+        let new = build_bundle(&[
+            (
+                "settings.json",
+                r#"{"permissions":{"allow":["Read","Edit"],"ask":[],"deny":[]}}"#.into(),
+            ),
+            (
+                ".mcp.json",
+                r#"{"mcpServers":{"git":{"command":"bunx"},"docs":{"url":"https://x"}}}"#.into(),
+            ),
+        ]);
+
+        let diff = ConfigDiff::compare(
+            &ConfigSnapshot::from_bundle(&old).unwrap(),
+            &ConfigSnapshot::from_bundle(&new).unwrap(),
+        );
+
+        assert!(diff.permission_changes.contains(&PermissionChange {
+            list: "allow".into(),
+            rule: "Edit".into(),
+            change: ChangeKind::Added,
+        }));
+        assert!(diff.permission_changes.contains(&PermissionChange {
+            list: "deny".into(),
+            rule: "Read(.env)".into(),
+            change: ChangeKind::Removed,
+        }));
+
+        let mcp: Vec<(&str, ChangeKind)> = diff
+            .mcp_changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.change))
+            .collect();
+        assert_eq!(
+            mcp,
+            vec![
+                ("docs", ChangeKind::Added),
+                ("git", ChangeKind::Modified),
+                ("old", ChangeKind::Removed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_reads_skills_and_profiles_from_bundle() {
+        let profiles = serde_json::json!({ "profiles": [profile("main", &["a"])] });
+        let bundle = build_bundle(&[
+            ("profiles.json", profiles.to_string()),
+            ("skills/a/SKILL.md", "---\nname: A\n---\n".into()),
+            ("skills/a/references/more.md", "ignored".into()),
+        ]);
+
+        let snapshot = ConfigSnapshot::from_bundle(&bundle).unwrap();
+        assert_eq!(snapshot.profiles.len(), 1);
+        assert_eq!(snapshot.skills.len(), 1);
+        assert!(snapshot.skills.contains_key("a"));
+    }
+
+    #[test]
+    fn test_markdown_changelog_sections() {
+        let diff = ConfigDiff {
+            skills_added: vec!["playwright".into()],
+            mcp_changes: vec![McpServerChange {
+                name: "git".into(),
+                change: ChangeKind::Removed,
+            }],
+            ..Default::default()
+        };
+
+        let md = diff.to_markdown();
+        assert!(md.contains("### Skills"));
+        assert!(md.contains("- Added skill `playwright`"));
+        assert!(md.contains("### MCP Servers"));
+        assert!(md.contains("- Removed `git`"));
+        assert!(!md.contains("### Profiles"));
+    }
+}
@@ -4,20 +4,39 @@
 //! - Exporting current configuration (profiles, skills, settings, etc.)
 //! - Publishing configuration to GitHub releases
 //! - Syncing configuration from GitHub releases
-
-use crate::{InstructionsManager, Paths, Profiles, Result, RhinolabsError, Settings};
+//! - Protecting local modifications during sync
+
+use crate::config_diff::{ConfigDiff, ConfigSnapshot};
+use crate::migrations::{
+    BundleFiles, BUNDLE_FORMAT_VERSION, BUNDLE_MIN_CLI_VERSION, LEGACY_BUNDLE_FORMAT_VERSION,
+};
+use crate::{
+    Channels, GitHub, GitHubCredentials, InstructionsManager, Migrations, Paths, Profiles,
+    ReleaseChannel, Result, RhinolabsError, Settings, Skills,
+};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use zip::write::FileOptions;
 use zip::ZipWriter;
 
+/// Tag prefix of config releases
+const CONFIG_TAG_PREFIX: &str = "config-v";
+
 /// Configuration bundle manifest
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfigManifest {
     pub version: String,
+    /// Bundle layout version (bundles without it predate versioning)
+    #[serde(default = "legacy_format_version")]
+    pub format_version: u32,
+    /// Oldest CLI able to apply this bundle
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_cli_version: Option<String>,
     pub created_at: String,
     pub profiles_count: usize,
     pub skills_count: usize,
@@ -26,11 +45,17 @@ pub struct ConfigManifest {
     pub output_styles_count: usize,
 }
 
+fn legacy_format_version() -> u32 {
+    LEGACY_BUNDLE_FORMAT_VERSION
+}
+
 /// Deploy result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeployResult {
     pub version: String,
+    #[serde(default)]
+    pub channel: ReleaseChannel,
     pub release_url: String,
     pub asset_url: String,
     pub manifest: ConfigManifest,
@@ -46,6 +71,101 @@ pub struct SyncResult {
     pub instructions_installed: bool,
     pub settings_installed: bool,
     pub output_styles_installed: usize,
+    /// Items modified locally that were also changed in the bundle
+    #[serde(default)]
+    pub conflicts: Vec<SyncConflict>,
+    /// Compatibility warnings about the bundle (e.g. it expects a newer CLI)
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// How to resolve a conflict between a local edit and a remote change
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictStrategy {
+    /// Keep the local file untouched
+    #[default]
+    KeepLocal,
+    /// Overwrite the local file with the remote version
+    TakeRemote,
+    /// Keep the local file and write the remote version to `.sync-remote/<file>`
+    /// in the plugin dir, outside the skill and config trees
+    WriteRemote,
+}
+
+impl std::str::FromStr for ConflictStrategy {
+    type Err = RhinolabsError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "keep-local" | "local" => Ok(Self::KeepLocal),
+            "take-remote" | "remote" => Ok(Self::TakeRemote),
+            "write-remote" | "side-by-side" => Ok(Self::WriteRemote),
+            _ => Err(RhinolabsError::ConfigError(format!(
+                "Invalid conflict strategy: {}. Use keep-local, take-remote, or write-remote",
+                s
+            ))),
+        }
+    }
+}
+
+/// Options controlling how sync treats locally modified items
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncOptions {
+    /// Strategy used for conflicts without a per-item override
+    #[serde(default)]
+    pub default_strategy: ConflictStrategy,
+    /// Per-item overrides, keyed by item id (e.g. "skills/react-patterns", "CLAUDE.md")
+    #[serde(default)]
+    pub item_strategies: HashMap<String, ConflictStrategy>,
+    /// Channel to sync from instead of this machine's channel
+    #[serde(default)]
+    pub channel: Option<ReleaseChannel>,
+}
+
+impl SyncOptions {
+    /// Resolve the strategy for a given item
+    pub fn strategy_for(&self, item: &str) -> ConflictStrategy {
+        self.item_strategies
+            .get(item)
+            .copied()
+            .unwrap_or(self.default_strategy)
+    }
+}
+
+/// A locally modified item that the incoming bundle also changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConflict {
+    /// Item id (e.g. "skills/react-patterns", "CLAUDE.md", "settings.json")
+    pub item: String,
+    /// Bundle paths of the conflicting files within the item
+    pub files: Vec<String>,
+    /// Strategy that was applied
+    pub resolution: ConflictStrategy,
+}
+
+/// Hashes of the files written by the last sync, keyed by bundle path.
+/// Used as the common base to tell local edits apart from remote changes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncState {
+    #[serde(default)]
+    files: HashMap<String, String>,
+}
+
+/// Bundle contents after compatibility checks and migrations
+pub(crate) struct LoadedBundle {
+    pub files: BundleFiles,
+    pub warnings: Vec<String>,
+}
+
+/// File extracted from a bundle, with its install location
+struct BundleEntry {
+    name: String,
+    target: PathBuf,
+    content: Vec<u8>,
 }
 
 pub struct Deploy;
@@ -70,7 +190,7 @@ impl Deploy {
         // 1. Export profiles.json
         let profiles_path = config_dir.join("profiles.json");
         if profiles_path.exists() {
-            let content = fs::read_to_string(&profiles_path)?;
+            let content = Self::read_upgraded(&profiles_path, Migrations::upgrade_profiles)?;
             zip.start_file("profiles.json", options)?;
             zip.write_all(content.as_bytes())?;
         }
@@ -113,7 +233,8 @@ impl Deploy {
         // 7. Export .skills-config.json if exists
         let skills_config_path = plugin_dir.join(".skills-config.json");
         if skills_config_path.exists() {
-            let content = fs::read_to_string(&skills_config_path)?;
+            let content =
+                Self::read_upgraded(&skills_config_path, Migrations::upgrade_skills_config)?;
             zip.start_file(".skills-config.json", options)?;
             zip.write_all(content.as_bytes())?;
         }
@@ -125,6 +246,8 @@ impl Deploy {
         // Create manifest
         let manifest = ConfigManifest {
             version: Self::get_current_version()?,
+            format_version: BUNDLE_FORMAT_VERSION,
+            min_cli_version: Some(BUNDLE_MIN_CLI_VERSION.to_string()),
             created_at: chrono::Utc::now().to_rfc3339(),
             profiles_count,
             skills_count,
@@ -143,6 +266,21 @@ impl Deploy {
         Ok((zip_path, manifest))
     }
 
+    /// Read a JSON config file, upgraded to its current schema
+    /// Files already in the current schema are returned verbatim
+    fn read_upgraded(
+        path: &Path,
+        upgrade: fn(&mut serde_json::Value) -> Result<bool>,
+    ) -> Result<String> {
+        let content = fs::read_to_string(path)?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)?;
+        if upgrade(&mut value)? {
+            Ok(serde_json::to_string_pretty(&value)?)
+        } else {
+            Ok(content)
+        }
+    }
+
     /// Add a directory recursively to the zip file
     fn add_directory_to_zip<W: Write + std::io::Seek>(
         zip: &mut ZipWriter<W>,
@@ -204,9 +342,19 @@ impl Deploy {
         Ok("1.0.0".to_string())
     }
 
-    /// Deploy configuration to GitHub
+    /// Deploy configuration to GitHub on the stable channel
     /// Creates a release and uploads the config bundle as an asset
     pub async fn deploy(version: &str, changelog: &str) -> Result<DeployResult> {
+        Self::deploy_to_channel(version, changelog, ReleaseChannel::Stable).await
+    }
+
+    /// Deploy configuration to a release channel
+    /// Non-stable channels are published as prereleases tagged `config-v<version>-<channel>`
+    pub async fn deploy_to_channel(
+        version: &str,
+        changelog: &str,
+        channel: ReleaseChannel,
+    ) -> Result<DeployResult> {
         // Get GitHub config
         let project_config = crate::Project::get_config()?;
 
@@ -216,9 +364,8 @@ impl Deploy {
             ));
         }
 
-        let token = std::env::var("GITHUB_TOKEN").map_err(|_| {
-            RhinolabsError::ConfigError("GITHUB_TOKEN environment variable not set".into())
-        })?;
+        // Fail early, before exporting, when no token is configured
+        GitHubCredentials::require()?;
 
         // 1. Export config to temp directory
         let temp_dir = std::env::temp_dir().join("rhinolabs-deploy");
@@ -226,8 +373,30 @@ impl Deploy {
 
         let (zip_path, manifest) = Self::export_config(&temp_dir)?;
 
-        // 2. Create GitHub release
-        let tag = format!("config-v{}", version);
+        // 2. Generate changelog against the previous release
+        let mut changelog = changelog.to_string();
+        if project_config.auto_changelog {
+            let generated = match Self::find_latest_bundle(channel).await? {
+                Some((previous, bundle)) => {
+                    let old = ConfigSnapshot::from_bundle(&bundle)?;
+                    let new = ConfigSnapshot::from_bundle_file(&zip_path)?;
+                    format!(
+                        "## Changes since {}\n\n{}",
+                        previous,
+                        ConfigDiff::compare(&old, &new).to_markdown()
+                    )
+                }
+                None => "## Changes\n\nInitial configuration release.\n".to_string(),
+            };
+            changelog = if changelog.trim().is_empty() {
+                generated
+            } else {
+                format!("{}\n\n{}", changelog.trim_end(), generated)
+            };
+        }
+
+        // 3. Create GitHub release
+        let tag = channel.tag(CONFIG_TAG_PREFIX, version);
         let release_body = format!(
             "{}\n\n## Configuration Summary\n- Profiles: {}\n- Skills: {}\n- Instructions: {}\n- Output Styles: {}",
             changelog,
@@ -249,28 +418,23 @@ impl Deploy {
             "tag_name": tag,
             "name": format!("Configuration {}", tag),
             "body": release_body,
-            "prerelease": false,
+            "prerelease": channel.is_prerelease(),
             "draft": false,
         });
 
-        let response = client
-            .post(&release_url)
-            .header("User-Agent", "rhinolabs-cli")
-            .header("Authorization", format!("Bearer {}", token))
-            .header("Accept", "application/vnd.github+json")
+        let response = GitHub::post(&client, &release_url)?
             .json(&release_body_json)
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            // Clean up temp files
-            let _ = fs::remove_dir_all(&temp_dir);
-            return Err(RhinolabsError::Other(format!(
-                "Failed to create release: {}",
-                error_text
-            )));
-        }
+        let response = match GitHub::check(response, "Failed to create release").await {
+            Ok(response) => response,
+            Err(e) => {
+                // Clean up temp files
+                let _ = fs::remove_dir_all(&temp_dir);
+                return Err(e);
+            }
+        };
 
         let release: serde_json::Value = response.json().await?;
         let release_id = release["id"]
@@ -278,7 +442,7 @@ impl Deploy {
             .ok_or_else(|| RhinolabsError::Other("Failed to get release ID".into()))?;
         let release_html_url = release["html_url"].as_str().unwrap_or("").to_string();
 
-        // 3. Upload config zip as release asset
+        // 4. Upload config zip as release asset
         let upload_url = format!(
             "https://uploads.github.com/repos/{}/{}/releases/{}/assets?name=rhinolabs-config.zip",
             project_config.github.owner, project_config.github.repo, release_id
@@ -286,24 +450,21 @@ impl Deploy {
 
         let zip_content = fs::read(&zip_path)?;
 
-        let upload_response = client
-            .post(&upload_url)
-            .header("User-Agent", "rhinolabs-cli")
-            .header("Authorization", format!("Bearer {}", token))
+        let upload_response = GitHub::post(&client, &upload_url)?
             .header("Content-Type", "application/zip")
             .body(zip_content)
             .send()
             .await?;
 
-        if !upload_response.status().is_success() {
-            let error_text = upload_response.text().await.unwrap_or_default();
-            // Clean up temp files
-            let _ = fs::remove_dir_all(&temp_dir);
-            return Err(RhinolabsError::Other(format!(
-                "Failed to upload config bundle: {}",
-                error_text
-            )));
-        }
+        let upload_response =
+            match GitHub::check(upload_response, "Failed to upload config bundle").await {
+                Ok(response) => response,
+                Err(e) => {
+                    // Clean up temp files
+                    let _ = fs::remove_dir_all(&temp_dir);
+                    return Err(e);
+                }
+            };
 
         let asset: serde_json::Value = upload_response.json().await?;
         let asset_url = asset["browser_download_url"]
@@ -316,6 +477,7 @@ impl Deploy {
 
         Ok(DeployResult {
             version: version.to_string(),
+            channel,
             release_url: release_html_url,
             asset_url,
             manifest,
@@ -323,8 +485,45 @@ impl Deploy {
     }
 
     /// Sync configuration from GitHub
-    /// Downloads the latest config release and installs it
+    /// Downloads the latest config release and installs it, keeping local edits
     pub async fn sync() -> Result<SyncResult> {
+        Self::sync_with_options(&SyncOptions::default()).await
+    }
+
+    /// Sync configuration from GitHub using the given conflict strategies
+    pub async fn sync_with_options(options: &SyncOptions) -> Result<SyncResult> {
+        let channel = match options.channel {
+            Some(channel) => channel,
+            None => Channels::current()?,
+        };
+        let (version, zip_content) = Self::fetch_latest_bundle_for(channel).await?;
+
+        // Extract and install the config
+        let mut result = Self::import_config(&zip_content, options)?;
+        result.version = version;
+
+        Ok(result)
+    }
+
+    /// Download the bundle of the latest config release for this machine's channel
+    /// Returns the release version and the zip content
+    pub async fn fetch_latest_bundle() -> Result<(String, Vec<u8>)> {
+        Self::fetch_latest_bundle_for(Channels::current()?).await
+    }
+
+    /// Download the bundle of the latest config release a channel accepts
+    pub async fn fetch_latest_bundle_for(channel: ReleaseChannel) -> Result<(String, Vec<u8>)> {
+        Self::find_latest_bundle(channel).await?.ok_or_else(|| {
+            RhinolabsError::ConfigError(format!(
+                "No configuration release found on the {} channel. Deploy a configuration first.",
+                channel
+            ))
+        })
+    }
+
+    /// Like `fetch_latest_bundle_for`, but `None` when the channel has no
+    /// config release yet
+    async fn find_latest_bundle(channel: ReleaseChannel) -> Result<Option<(String, Vec<u8>)>> {
         // Get GitHub config
         let project_config = crate::Project::get_config()?;
 
@@ -342,34 +541,18 @@ impl Deploy {
             project_config.github.owner, project_config.github.repo
         );
 
-        let response = client
-            .get(&releases_url)
-            .header("User-Agent", "rhinolabs-cli")
-            .header("Accept", "application/vnd.github+json")
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(RhinolabsError::Other(
-                "Failed to fetch releases from GitHub".into(),
-            ));
-        }
+        let response = GitHub::api(&client, &releases_url).send().await?;
+        let response = GitHub::check(response, "Failed to fetch releases from GitHub").await?;
 
         let releases: Vec<serde_json::Value> = response.json().await?;
 
-        // Find the latest config release (tag starts with "config-v")
-        let config_release = releases.iter().find(|r| {
-            r["tag_name"]
-                .as_str()
-                .map(|t| t.starts_with("config-v"))
-                .unwrap_or(false)
-        });
+        // Find the latest config release (tag starts with "config-v") for the channel
+        let config_release =
+            Channels::select_release(&releases, channel, |t| t.starts_with(CONFIG_TAG_PREFIX));
 
-        let release = config_release.ok_or_else(|| {
-            RhinolabsError::ConfigError(
-                "No configuration release found. Deploy a configuration first.".into(),
-            )
-        })?;
+        let Some(release) = config_release else {
+            return Ok(None);
+        };
 
         // Find the config zip asset
         let assets = release["assets"]
@@ -387,61 +570,110 @@ impl Deploy {
             RhinolabsError::ConfigError("No config bundle found in release".into())
         })?;
 
-        let download_url = asset["browser_download_url"]
-            .as_str()
-            .ok_or_else(|| RhinolabsError::Other("Invalid asset URL".into()))?;
-
         let version = release["tag_name"]
             .as_str()
             .unwrap_or("unknown")
-            .trim_start_matches("config-v")
+            .trim_start_matches(CONFIG_TAG_PREFIX)
             .to_string();
 
-        // 2. Download the config zip
-        let zip_response = client
-            .get(download_url)
-            .header("User-Agent", "rhinolabs-cli")
-            .send()
-            .await?;
+        // 2. Download the config zip (API asset endpoint, works for private repos)
+        let zip_content = GitHub::download_asset(&client, asset).await?;
 
-        if !zip_response.status().is_success() {
-            return Err(RhinolabsError::Other(
-                "Failed to download config bundle".into(),
+        Ok(Some((version, zip_content)))
+    }
+
+    /// Promote a config release to a more stable channel (e.g. beta -> stable)
+    /// Returns the URL of the promoted release
+    pub async fn promote(
+        version: &str,
+        from: ReleaseChannel,
+        to: ReleaseChannel,
+    ) -> Result<String> {
+        let project_config = crate::Project::get_config()?;
+
+        if project_config.github.owner.is_empty() || project_config.github.repo.is_empty() {
+            return Err(RhinolabsError::ConfigError(
+                "GitHub repository not configured. Configure it in Project Settings.".into(),
             ));
         }
 
-        let zip_content = zip_response.bytes().await?;
+        Channels::promote_release(
+            &project_config.github.owner,
+            &project_config.github.repo,
+            CONFIG_TAG_PREFIX,
+            version,
+            from,
+            to,
+        )
+        .await
+    }
+
+    /// Get the sync state file path
+    fn sync_state_path() -> Result<PathBuf> {
+        Ok(Paths::plugin_dir()?.join(".sync-state.json"))
+    }
 
-        // 3. Extract and install the config
-        let result = Self::import_config(&zip_content)?;
+    /// Load the hashes recorded by the last sync
+    fn load_sync_state() -> Result<SyncState> {
+        let path = Self::sync_state_path()?;
 
-        Ok(SyncResult {
-            version,
-            profiles_installed: result.0,
-            skills_installed: result.1,
-            instructions_installed: result.2,
-            settings_installed: result.3,
-            output_styles_installed: result.4,
-        })
+        if !path.exists() {
+            return Ok(SyncState::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
     }
 
-    /// Import configuration from a zip buffer
-    fn import_config(zip_content: &[u8]) -> Result<(usize, usize, bool, bool, usize)> {
+    /// Save the hashes of the files written by this sync
+    fn save_sync_state(state: &SyncState) -> Result<()> {
+        let path = Self::sync_state_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(state)?)?;
+        Ok(())
+    }
+
+    /// SHA-256 of a file's content, hex encoded
+    fn content_hash(content: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(content))
+    }
+
+    /// Map a bundle path to the item it belongs to.
+    /// Skills are grouped by directory so all of a skill's files resolve together.
+    fn item_id(name: &str) -> String {
+        if let Some(rest) = name.strip_prefix("skills/") {
+            if let Some((skill_id, _)) = rest.split_once('/') {
+                return format!("skills/{}", skill_id);
+            }
+        }
+        name.to_string()
+    }
+
+    /// Resolve where a bundle path is installed, or None if it isn't part of the config
+    fn target_for(name: &str, plugin_dir: &Path, config_dir: &Path) -> Option<PathBuf> {
+        match name {
+            "profiles.json" => Some(config_dir.join("profiles.json")),
+            "CLAUDE.md" | "settings.json" | ".mcp.json" | ".skills-config.json" => {
+                Some(plugin_dir.join(name))
+            }
+            _ if name.starts_with("skills/") || name.starts_with("output-styles/") => {
+                Some(plugin_dir.join(name))
+            }
+            _ => None,
+        }
+    }
+
+    /// Read a bundle, check it is compatible with this CLI and upgrade older formats
+    pub(crate) fn load_bundle(zip_content: &[u8]) -> Result<LoadedBundle> {
         use std::io::Cursor;
         use zip::ZipArchive;
 
         let reader = Cursor::new(zip_content);
         let mut archive = ZipArchive::new(reader)?;
 
-        let plugin_dir = Paths::plugin_dir()?;
-        let config_dir = Paths::rhinolabs_config_dir()?;
-
-        let mut profiles_installed = 0;
-        let mut skills_installed = 0;
-        let mut instructions_installed = false;
-        let mut settings_installed = false;
-        let mut output_styles_installed = 0;
-
+        let mut files = BundleFiles::new();
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)?;
             let name = file.name().to_string();
@@ -453,79 +685,225 @@ impl Deploy {
 
             let mut content = Vec::new();
             file.read_to_end(&mut content)?;
+            files.insert(name, content);
+        }
+
+        // Bundles without a manifest or formatVersion predate versioning
+        let manifest: serde_json::Value = match files.get("manifest.json") {
+            Some(content) => serde_json::from_slice(content)?,
+            None => serde_json::Value::Null,
+        };
+        let format_version = manifest["formatVersion"]
+            .as_u64()
+            .map(|v| v as u32)
+            .unwrap_or(LEGACY_BUNDLE_FORMAT_VERSION);
+        let min_cli_version = manifest["minCliVersion"].as_str();
+
+        let warnings = Migrations::check_bundle(format_version, min_cli_version)?;
+        Migrations::upgrade_bundle(&mut files, format_version)?;
+
+        Ok(LoadedBundle { files, warnings })
+    }
+
+    /// Read every installable file from a zip buffer
+    fn read_bundle(zip_content: &[u8]) -> Result<(Vec<BundleEntry>, Vec<String>)> {
+        let bundle = Self::load_bundle(zip_content)?;
+
+        let plugin_dir = Paths::plugin_dir()?;
+        let config_dir = Paths::rhinolabs_config_dir()?;
 
+        let entries = bundle
+            .files
+            .into_iter()
+            .filter_map(|(name, content)| {
+                let target = Self::target_for(&name, &plugin_dir, &config_dir)?;
+                Some(BundleEntry {
+                    name,
+                    target,
+                    content,
+                })
+            })
+            .collect();
+
+        Ok((entries, bundle.warnings))
+    }
+
+    /// Import configuration from a zip buffer
+    ///
+    /// A file counts as locally modified when its hash differs from the one
+    /// recorded by the last sync. Local edits are only reported as conflicts
+    /// when the bundle changed the same file; otherwise the side that changed wins.
+    /// Files never synced before have no base: skills fall back to their
+    /// install hash, and any other file that differs from the bundle conflicts.
+    fn import_config(zip_content: &[u8], options: &SyncOptions) -> Result<SyncResult> {
+        let (entries, warnings) = Self::read_bundle(zip_content)?;
+        let mut state = Self::load_sync_state()?;
+
+        // 1. Find files changed on both sides since the last sync
+        let mut conflicted: HashMap<String, Vec<String>> = HashMap::new();
+        let mut unchanged_remote: Vec<&str> = Vec::new();
+        let mut modified_skills: HashMap<String, bool> = HashMap::new();
+
+        for entry in &entries {
+            let Ok(local) = fs::read(&entry.target) else {
+                continue;
+            };
+            if local == entry.content {
+                continue;
+            }
+
+            let (local_modified, remote_changed) = match state.files.get(&entry.name) {
+                Some(base) => (
+                    *base != Self::content_hash(&local),
+                    *base != Self::content_hash(&entry.content),
+                ),
+                None => match Self::item_id(&entry.name).strip_prefix("skills/") {
+                    Some(skill_id) => {
+                        if !modified_skills.contains_key(skill_id) {
+                            let modified = Skills::get(skill_id)?
+                                .map(|skill| skill.is_modified)
+                                .unwrap_or(true);
+                            modified_skills.insert(skill_id.to_string(), modified);
+                        }
+                        (modified_skills[skill_id], true)
+                    }
+                    None => (true, true),
+                },
+            };
+
+            if local_modified && remote_changed {
+                conflicted
+                    .entry(Self::item_id(&entry.name))
+                    .or_default()
+                    .push(entry.name.clone());
+            } else if local_modified {
+                unchanged_remote.push(&entry.name);
+            }
+        }
+
+        // 2. Install files, applying the strategy to conflicting ones
+        let mut result = SyncResult {
+            version: String::new(),
+            profiles_installed: 0,
+            skills_installed: 0,
+            instructions_installed: false,
+            settings_installed: false,
+            output_styles_installed: 0,
+            conflicts: Vec::new(),
+            warnings,
+        };
+
+        for entry in &entries {
+            let item = Self::item_id(&entry.name);
+            let is_conflict = conflicted
+                .get(&item)
+                .map(|files| files.contains(&entry.name))
+                .unwrap_or(false);
+
+            let write_target = if unchanged_remote.contains(&entry.name.as_str()) {
+                // Only the local side changed: nothing new to apply
+                None
+            } else if is_conflict {
+                match options.strategy_for(&item) {
+                    ConflictStrategy::KeepLocal => None,
+                    ConflictStrategy::TakeRemote => Some(entry.target.clone()),
+                    ConflictStrategy::WriteRemote => {
+                        let remote_path = Self::remote_copy_dir()?.join(&entry.name);
+                        Self::write_file(&remote_path, &entry.content)?;
+                        None
+                    }
+                }
+            } else {
+                Some(entry.target.clone())
+            };
+
+            // Conflicts left unapplied keep their old base, so a later sync
+            // still sees both sides changed and can take the remote
+            let Some(target) = write_target else {
+                continue;
+            };
+
+            Self::write_file(&target, &entry.content)?;
+            state
+                .files
+                .insert(entry.name.clone(), Self::content_hash(&entry.content));
+
+            let name = entry.name.as_str();
             if name == "profiles.json" {
-                // Install profiles
-                let target = config_dir.join("profiles.json");
-                fs::create_dir_all(&config_dir)?;
-                fs::write(&target, &content)?;
                 // Count profiles in the file
-                if let Ok(json) = serde_json::from_slice::<serde_json::Value>(&content) {
+                if let Ok(json) = serde_json::from_slice::<serde_json::Value>(&entry.content) {
                     if let Some(profiles) = json["profiles"].as_array() {
-                        profiles_installed = profiles.len();
+                        result.profiles_installed = profiles.len();
                     }
                 }
-            } else if name.starts_with("skills/") {
-                // Install skill
-                let relative_path = name.trim_start_matches("skills/");
-                let target = plugin_dir.join("skills").join(relative_path);
-                if let Some(parent) = target.parent() {
-                    fs::create_dir_all(parent)?;
-                }
-                fs::write(&target, &content)?;
-                if name.ends_with("SKILL.md") {
-                    skills_installed += 1;
-                }
+            } else if name.starts_with("skills/") && name.ends_with("SKILL.md") {
+                result.skills_installed += 1;
             } else if name == "CLAUDE.md" {
-                // Install instructions
-                let target = plugin_dir.join("CLAUDE.md");
-                fs::write(&target, &content)?;
-                instructions_installed = true;
+                result.instructions_installed = true;
             } else if name == "settings.json" {
-                // Install settings
-                let target = plugin_dir.join("settings.json");
-                fs::write(&target, &content)?;
-                settings_installed = true;
-            } else if name.starts_with("output-styles/") {
-                // Install output style
-                let relative_path = name.trim_start_matches("output-styles/");
-                let target = plugin_dir.join("output-styles").join(relative_path);
-                if let Some(parent) = target.parent() {
-                    fs::create_dir_all(parent)?;
-                }
-                fs::write(&target, &content)?;
-                if name.ends_with(".md") {
-                    output_styles_installed += 1;
-                }
-            } else if name == ".mcp.json" {
-                // Install MCP config
-                let target = plugin_dir.join(".mcp.json");
-                fs::write(&target, &content)?;
-            } else if name == ".skills-config.json" {
-                // Install skills config
-                let target = plugin_dir.join(".skills-config.json");
-                fs::write(&target, &content)?;
+                result.settings_installed = true;
+            } else if name.starts_with("output-styles/") && name.ends_with(".md") {
+                result.output_styles_installed += 1;
             }
         }
 
-        Ok((
-            profiles_installed,
-            skills_installed,
-            instructions_installed,
-            settings_installed,
-            output_styles_installed,
-        ))
+        Self::save_sync_state(&state)?;
+
+        let mut conflicts: Vec<SyncConflict> = conflicted
+            .into_iter()
+            .map(|(item, mut files)| {
+                files.sort();
+                SyncConflict {
+                    resolution: options.strategy_for(&item),
+                    item,
+                    files,
+                }
+            })
+            .collect();
+        conflicts.sort_by(|a, b| a.item.cmp(&b.item));
+        result.conflicts = conflicts;
+
+        Ok(result)
+    }
+
+    /// Where write-remote puts the bundle's version of conflicting files.
+    /// Kept out of `skills/` so the copies don't count as skill files.
+    fn remote_copy_dir() -> Result<PathBuf> {
+        Ok(Paths::plugin_dir()?.join(".sync-remote"))
+    }
+
+    /// Write a file, creating parent directories as needed
+    fn write_file(target: &Path, content: &[u8]) -> Result<()> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, content)?;
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{skill_md, TestEnv, ENV_MUTEX};
+
+    /// Build an in-memory config bundle from (path, content) pairs
+    fn build_bundle(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = FileOptions::default();
+        for (name, content) in files {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
 
     #[test]
     fn test_config_manifest_serialization() {
         let manifest = ConfigManifest {
             version: "1.0.0".to_string(),
+            format_version: BUNDLE_FORMAT_VERSION,
+            min_cli_version: Some("0.1.0".to_string()),
             created_at: "2026-01-27T00:00:00Z".to_string(),
             profiles_count: 2,
             skills_count: 10,
@@ -537,5 +915,331 @@ mod tests {
         let json = serde_json::to_string(&manifest).unwrap();
         assert!(json.contains("profilesCount"));
         assert!(json.contains("skillsCount"));
+        assert!(json.contains("formatVersion"));
+        assert!(json.contains("minCliVersion"));
+    }
+
+    #[test]
+    fn test_legacy_manifest_defaults_format_version() {
+        let json = r#"{
+            "version": "1.0.0",
+            "createdAt": "2026-01-27T00:00:00Z",
+            "profilesCount": 1,
+            "skillsCount": 0,
+            "hasInstructions": false,
+            "hasSettings": true,
+            "outputStylesCount": 0
+        }"#;
+
+        let manifest: ConfigManifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.format_version, LEGACY_BUNDLE_FORMAT_VERSION);
+        assert!(manifest.min_cli_version.is_none());
+    }
+
+    #[test]
+    fn test_import_refuses_newer_bundle_format() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+
+        let manifest = format!(
+            r#"{{"formatVersion":{},"minCliVersion":"99.0.0"}}"#,
+            BUNDLE_FORMAT_VERSION + 1
+        );
+        let bundle = build_bundle(&[("manifest.json", &manifest), ("CLAUDE.md", "remote")]);
+
+        let err = Deploy::import_config(&bundle, &SyncOptions::default()).unwrap_err();
+
+        assert!(matches!(err, RhinolabsError::IncompatibleBundle(_)));
+        assert!(!env.plugin_dir().join("CLAUDE.md").exists());
+    }
+
+    #[test]
+    fn test_import_warns_when_bundle_needs_newer_cli() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let _env = TestEnv::new();
+
+        let manifest = format!(
+            r#"{{"formatVersion":{},"minCliVersion":"99.0.0"}}"#,
+            BUNDLE_FORMAT_VERSION
+        );
+        let bundle = build_bundle(&[("manifest.json", &manifest), ("CLAUDE.md", "remote")]);
+
+        let result = Deploy::import_config(&bundle, &SyncOptions::default()).unwrap();
+
+        assert!(result.instructions_installed);
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_import_upgrades_legacy_skills_config() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+
+        let bundle = build_bundle(&[(".skills-config.json", r#"{"disabled":["playwright"]}"#)]);
+        Deploy::import_config(&bundle, &SyncOptions::default()).unwrap();
+
+        let content = fs::read_to_string(env.plugin_dir().join(".skills-config.json")).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(value["sources"], serde_json::json!([]));
+        assert_eq!(value["disabled"], serde_json::json!(["playwright"]));
+    }
+
+    #[test]
+    fn test_conflict_strategy_from_str() {
+        assert_eq!(
+            "keep-local".parse::<ConflictStrategy>().unwrap(),
+            ConflictStrategy::KeepLocal
+        );
+        assert_eq!(
+            "take-remote".parse::<ConflictStrategy>().unwrap(),
+            ConflictStrategy::TakeRemote
+        );
+        assert_eq!(
+            "write-remote".parse::<ConflictStrategy>().unwrap(),
+            ConflictStrategy::WriteRemote
+        );
+        assert!("merge".parse::<ConflictStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_sync_options_item_override() {
+        let mut options = SyncOptions::default();
+        options
+            .item_strategies
+            .insert("CLAUDE.md".into(), ConflictStrategy::TakeRemote);
+
+        assert_eq!(
+            options.strategy_for("CLAUDE.md"),
+            ConflictStrategy::TakeRemote
+        );
+        assert_eq!(
+            options.strategy_for("settings.json"),
+            ConflictStrategy::KeepLocal
+        );
+    }
+
+    #[test]
+    fn test_item_id_groups_skill_files() {
+        assert_eq!(
+            Deploy::item_id("skills/react-patterns/SKILL.md"),
+            "skills/react-patterns"
+        );
+        assert_eq!(
+            Deploy::item_id("skills/react-patterns/references/hooks.md"),
+            "skills/react-patterns"
+        );
+        assert_eq!(Deploy::item_id("CLAUDE.md"), "CLAUDE.md");
+    }
+
+    #[test]
+    fn test_import_first_sync_reports_conflict() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        fs::write(env.plugin_dir().join("CLAUDE.md"), "local").unwrap();
+
+        let bundle = build_bundle(&[("CLAUDE.md", "remote")]);
+        let result = Deploy::import_config(&bundle, &SyncOptions::default()).unwrap();
+
+        assert!(!result.instructions_installed);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].item, "CLAUDE.md");
+        assert_eq!(
+            fs::read_to_string(env.plugin_dir().join("CLAUDE.md")).unwrap(),
+            "local"
+        );
+    }
+
+    #[test]
+    fn test_import_first_sync_uses_skill_install_hash() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let skills_dir = env.plugin_dir().join("skills");
+        for id in ["pristine", "edited"] {
+            env.write_skill(id, &skill_md("installed"));
+        }
+        let original = Skills::hash_dir(&skills_dir.join("pristine")).unwrap();
+        let config = serde_json::json!({
+            "skillMeta": {
+                "pristine": { "originalHash": original },
+                "edited": { "originalHash": original }
+            }
+        });
+        fs::write(
+            env.plugin_dir().join(".skills-config.json"),
+            config.to_string(),
+        )
+        .unwrap();
+        fs::write(skills_dir.join("edited/SKILL.md"), skill_md("local")).unwrap();
+
+        let bundle = build_bundle(&[
+            ("skills/pristine/SKILL.md", &skill_md("remote")),
+            ("skills/edited/SKILL.md", &skill_md("remote")),
+        ]);
+        let result = Deploy::import_config(&bundle, &SyncOptions::default()).unwrap();
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].item, "skills/edited");
+        assert_eq!(
+            fs::read_to_string(skills_dir.join("pristine/SKILL.md")).unwrap(),
+            skill_md("remote")
+        );
+        assert_eq!(
+            fs::read_to_string(skills_dir.join("edited/SKILL.md")).unwrap(),
+            skill_md("local")
+        );
+    }
+
+    #[test]
+    fn test_import_keeps_local_edit_when_remote_unchanged() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+
+        let bundle = build_bundle(&[("CLAUDE.md", "v1")]);
+        Deploy::import_config(&bundle, &SyncOptions::default()).unwrap();
+        fs::write(env.plugin_dir().join("CLAUDE.md"), "local edit").unwrap();
+
+        let result = Deploy::import_config(&bundle, &SyncOptions::default()).unwrap();
+
+        assert!(result.conflicts.is_empty());
+        assert!(!result.instructions_installed);
+        assert_eq!(
+            fs::read_to_string(env.plugin_dir().join("CLAUDE.md")).unwrap(),
+            "local edit"
+        );
+    }
+
+    #[test]
+    fn test_import_reports_conflict_and_keeps_local() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let skill_path = env.plugin_dir().join("skills/my-skill/SKILL.md");
+
+        let v1 = build_bundle(&[("skills/my-skill/SKILL.md", &skill_md("v1"))]);
+        Deploy::import_config(&v1, &SyncOptions::default()).unwrap();
+        fs::write(&skill_path, skill_md("local")).unwrap();
+
+        let v2 = build_bundle(&[("skills/my-skill/SKILL.md", &skill_md("v2"))]);
+        let result = Deploy::import_config(&v2, &SyncOptions::default()).unwrap();
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].item, "skills/my-skill");
+        assert_eq!(result.conflicts[0].resolution, ConflictStrategy::KeepLocal);
+        assert_eq!(result.skills_installed, 0);
+        assert_eq!(fs::read_to_string(&skill_path).unwrap(), skill_md("local"));
+    }
+
+    #[test]
+    fn test_import_take_remote_overwrites_conflict() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let settings_path = env.plugin_dir().join("settings.json");
+
+        Deploy::import_config(
+            &build_bundle(&[("settings.json", "{}")]),
+            &Default::default(),
+        )
+        .unwrap();
+        fs::write(&settings_path, "{\"local\":true}").unwrap();
+
+        let mut options = SyncOptions::default();
+        options
+            .item_strategies
+            .insert("settings.json".into(), ConflictStrategy::TakeRemote);
+        let bundle = build_bundle(&[("settings.json", "{\"remote\":true}")]);
+        let result = Deploy::import_config(&bundle, &options).unwrap();
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert!(result.settings_installed);
+        assert_eq!(
+            fs::read_to_string(&settings_path).unwrap(),
+            "{\"remote\":true}"
+        );
+    }
+
+    #[test]
+    fn test_import_take_remote_after_keeping_local() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let claude_md = env.plugin_dir().join("CLAUDE.md");
+
+        Deploy::import_config(&build_bundle(&[("CLAUDE.md", "v1")]), &Default::default()).unwrap();
+        fs::write(&claude_md, "local").unwrap();
+
+        let v2 = build_bundle(&[("CLAUDE.md", "v2")]);
+        let result = Deploy::import_config(&v2, &Default::default()).unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(fs::read_to_string(&claude_md).unwrap(), "local");
+
+        // The kept conflict is still pending on the next sync of the same bundle
+        let mut options = SyncOptions::default();
+        options
+            .item_strategies
+            .insert("CLAUDE.md".into(), ConflictStrategy::TakeRemote);
+        let result = Deploy::import_config(&v2, &options).unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert!(result.instructions_installed);
+        assert_eq!(fs::read_to_string(&claude_md).unwrap(), "v2");
+    }
+
+    #[test]
+    fn test_import_write_remote_copy() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let claude_md = env.plugin_dir().join("CLAUDE.md");
+
+        Deploy::import_config(&build_bundle(&[("CLAUDE.md", "v1")]), &Default::default()).unwrap();
+        fs::write(&claude_md, "local").unwrap();
+
+        let options = SyncOptions {
+            default_strategy: ConflictStrategy::WriteRemote,
+            ..Default::default()
+        };
+        let result =
+            Deploy::import_config(&build_bundle(&[("CLAUDE.md", "v2")]), &options).unwrap();
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(fs::read_to_string(&claude_md).unwrap(), "local");
+        assert_eq!(
+            fs::read_to_string(env.plugin_dir().join(".sync-remote/CLAUDE.md")).unwrap(),
+            "v2"
+        );
+    }
+
+    #[test]
+    fn test_import_write_remote_keeps_skill_unmodified() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let skill_dir = env.plugin_dir().join("skills/my-skill");
+
+        let v1 = build_bundle(&[("skills/my-skill/SKILL.md", &skill_md("v1"))]);
+        Deploy::import_config(&v1, &SyncOptions::default()).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), skill_md("local")).unwrap();
+        let original = Skills::hash_dir(&skill_dir).unwrap();
+        let config =
+            serde_json::json!({ "skillMeta": { "my-skill": { "originalHash": original } } });
+        fs::write(
+            env.plugin_dir().join(".skills-config.json"),
+            config.to_string(),
+        )
+        .unwrap();
+
+        let options = SyncOptions {
+            default_strategy: ConflictStrategy::WriteRemote,
+            ..Default::default()
+        };
+        let v2 = build_bundle(&[("skills/my-skill/SKILL.md", &skill_md("v2"))]);
+        let result = Deploy::import_config(&v2, &options).unwrap();
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(
+            fs::read_to_string(
+                env.plugin_dir()
+                    .join(".sync-remote/skills/my-skill/SKILL.md")
+            )
+            .unwrap(),
+            skill_md("v2")
+        );
+        assert_eq!(Skills::read_tree(&skill_dir).unwrap().len(), 1);
+        assert!(!Skills::get("my-skill").unwrap().unwrap().is_modified);
     }
 }
@@ -1,4 +1,4 @@
-use crate::{Paths, Result, Version};
+use crate::{Paths, Result, TokenEstimator, Version};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +45,9 @@ impl Doctor {
         // Check MCP config
         checks.push(Self::check_mcp_config());
 
+        // Check profile context sizes
+        checks.push(Self::check_token_budgets());
+
         // Check for updates
         checks.push(Self::check_updates().await);
 
@@ -156,6 +159,42 @@ impl Doctor {
         }
     }
 
+    fn check_token_budgets() -> DiagnosticCheck {
+        let reports = match TokenEstimator::profiles() {
+            Ok(reports) => reports,
+            Err(e) => {
+                return DiagnosticCheck {
+                    name: "Token Budgets".into(),
+                    status: CheckStatus::Warning,
+                    message: format!("Could not estimate profile sizes: {}", e),
+                }
+            }
+        };
+
+        let over: Vec<String> = reports
+            .iter()
+            .filter(|r| r.over_budget)
+            .map(|r| format!("{} (~{} / {})", r.profile_id, r.total, r.budget))
+            .collect();
+
+        if over.is_empty() {
+            DiagnosticCheck {
+                name: "Token Budgets".into(),
+                status: CheckStatus::Pass,
+                message: format!("{} profile(s) within budget", reports.len()),
+            }
+        } else {
+            DiagnosticCheck {
+                name: "Token Budgets".into(),
+                status: CheckStatus::Warning,
+                message: format!(
+                    "Over token budget: {}. Remove skills or raise the budget.",
+                    over.join(", ")
+                ),
+            }
+        }
+    }
+
     async fn check_updates() -> DiagnosticCheck {
         match Version::check_update().await {
             Ok(Some(version)) => DiagnosticCheck {
@@ -43,9 +43,24 @@ pub enum RhinolabsError {
     #[error("MCP sync failed: {0}")]
     McpSyncFailed(String),
 
+    #[error("GitHub authentication failed: {0}")]
+    GitHubAuth(String),
+
+    #[error("GitHub rate limit exceeded: {0}")]
+    RateLimited(String),
+
     #[error("Network error: {0}")]
     NetworkError(String),
 
+    #[error("Incompatible configuration bundle: {0}")]
+    IncompatibleBundle(String),
+
+    #[error("Blocked by security scan: {0}")]
+    SecurityBlocked(String),
+
+    #[error("Security review required: {0}")]
+    SecurityReviewRequired(String),
+
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
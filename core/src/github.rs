@@ -0,0 +1,649 @@
+//! GitHub module for authenticated API access
+//!
+//! This module handles:
+//! - Resolving a GitHub token (env vars, `gh` CLI, OS keyring, secret file)
+//! - Building authenticated requests for the API, contents and release assets
+//! - Turning 401/403/404 and rate limit responses into actionable errors
+//! - Revalidating cached responses with ETags, so unchanged files don't
+//!   count against the rate limit
+
+use crate::{Paths, Result, RhinolabsError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+
+const USER_AGENT: &str = "rhinolabs-ai";
+const KEYRING_SERVICE: &str = "rhinolabs-ai";
+const KEYRING_USER: &str = "github-token";
+
+/// Where a GitHub token was found
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenSource {
+    /// `GITHUB_TOKEN` environment variable
+    GithubTokenEnv,
+    /// `GH_TOKEN` environment variable
+    GhTokenEnv,
+    /// `gh auth token`
+    GhCli,
+    /// OS keyring (Keychain, Credential Manager, kernel keyring)
+    Keyring,
+    /// `github-token` file in the rhinolabs config dir
+    SecretFile,
+}
+
+impl std::fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::GithubTokenEnv => "GITHUB_TOKEN",
+            Self::GhTokenEnv => "GH_TOKEN",
+            Self::GhCli => "gh auth token",
+            Self::Keyring => "OS keyring",
+            Self::SecretFile => "secret file",
+        })
+    }
+}
+
+/// A resolved GitHub token
+#[derive(Debug, Clone)]
+pub struct GitHubToken {
+    pub token: String,
+    pub source: TokenSource,
+}
+
+/// Backend used to persist a token with `GitHubCredentials::store`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenStore {
+    Keyring,
+    SecretFile,
+}
+
+/// Tokens from `gh`, the keyring and the secret file are looked up once per process
+static STORED_TOKEN: Mutex<Option<Option<GitHubToken>>> = Mutex::new(None);
+
+// ============================================
+// Credential Provider
+// ============================================
+
+pub struct GitHubCredentials;
+
+impl GitHubCredentials {
+    /// Resolve a token, in order: `GITHUB_TOKEN`, `GH_TOKEN`, `gh auth token`,
+    /// OS keyring, secret file. Returns None when no token is configured.
+    pub fn resolve() -> Option<GitHubToken> {
+        if let Some(token) = Self::from_env() {
+            return Some(token);
+        }
+
+        let mut cached = STORED_TOKEN.lock().unwrap_or_else(|e| e.into_inner());
+        cached
+            .get_or_insert_with(|| {
+                Self::from_gh_cli()
+                    .or_else(Self::from_keyring)
+                    .or_else(Self::from_secret_file)
+            })
+            .clone()
+    }
+
+    /// Resolve a token or fail with instructions on how to configure one
+    pub fn require() -> Result<GitHubToken> {
+        Self::resolve().ok_or_else(|| {
+            RhinolabsError::GitHubAuth(
+                "no GitHub token found. Set GITHUB_TOKEN or GH_TOKEN, run 'gh auth login', \
+                 or run 'rhinolabs-ai auth login'"
+                    .into(),
+            )
+        })
+    }
+
+    /// Persist a token for later use
+    pub fn store(token: &str, store: TokenStore) -> Result<()> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(RhinolabsError::ConfigError("Token cannot be empty".into()));
+        }
+
+        match store {
+            TokenStore::Keyring => Self::keyring_entry()?
+                .set_password(token)
+                .map_err(|e| RhinolabsError::Other(format!("Keyring error: {}", e)))?,
+            TokenStore::SecretFile => Self::write_secret_file(token)?,
+        }
+
+        Self::invalidate();
+        Ok(())
+    }
+
+    /// Remove tokens stored by rhinolabs (keyring and secret file)
+    /// Tokens from the environment or `gh` are left alone.
+    pub fn clear() -> Result<()> {
+        if let Ok(entry) = Self::keyring_entry() {
+            let _ = entry.delete_credential();
+        }
+
+        Self::clear_secret_file()
+    }
+
+    /// Remove the secret file, leaving the keyring alone
+    fn clear_secret_file() -> Result<()> {
+        let path = Self::secret_file_path()?;
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+
+        Self::invalidate();
+        Ok(())
+    }
+
+    fn invalidate() {
+        *STORED_TOKEN.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    fn from_env() -> Option<GitHubToken> {
+        [
+            ("GITHUB_TOKEN", TokenSource::GithubTokenEnv),
+            ("GH_TOKEN", TokenSource::GhTokenEnv),
+        ]
+        .into_iter()
+        .find_map(|(var, source)| {
+            let token = std::env::var(var).ok()?;
+            let token = token.trim();
+            (!token.is_empty()).then(|| GitHubToken {
+                token: token.to_string(),
+                source,
+            })
+        })
+    }
+
+    fn from_gh_cli() -> Option<GitHubToken> {
+        let output = Command::new("gh")
+            .args(["auth", "token", "--hostname", "github.com"])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let token = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (!token.is_empty()).then_some(GitHubToken {
+            token,
+            source: TokenSource::GhCli,
+        })
+    }
+
+    fn from_keyring() -> Option<GitHubToken> {
+        let token = Self::keyring_entry().ok()?.get_password().ok()?;
+        (!token.is_empty()).then_some(GitHubToken {
+            token,
+            source: TokenSource::Keyring,
+        })
+    }
+
+    fn from_secret_file() -> Option<GitHubToken> {
+        let content = fs::read_to_string(Self::secret_file_path().ok()?).ok()?;
+        let token = content.trim().to_string();
+        (!token.is_empty()).then_some(GitHubToken {
+            token,
+            source: TokenSource::SecretFile,
+        })
+    }
+
+    fn keyring_entry() -> Result<keyring::Entry> {
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+            .map_err(|e| RhinolabsError::Other(format!("Keyring error: {}", e)))
+    }
+
+    fn secret_file_path() -> Result<PathBuf> {
+        Ok(Paths::rhinolabs_config_dir()?.join("github-token"))
+    }
+
+    fn write_secret_file(token: &str) -> Result<()> {
+        let path = Self::secret_file_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Owner-only from creation, so the token is never readable by others
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+
+        // An existing file keeps its mode when opened; tighten it before writing
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+
+        file.write_all(token.as_bytes())?;
+        Ok(())
+    }
+}
+
+// ============================================
+// Requests
+// ============================================
+
+/// A response stored for conditional requests
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    etag: String,
+    body: String,
+}
+
+pub struct GitHub;
+
+impl GitHub {
+    /// Build a GET request, authenticated when the URL points at GitHub
+    pub fn get(client: &reqwest::Client, url: &str) -> reqwest::RequestBuilder {
+        let request = client.get(url).header("User-Agent", USER_AGENT);
+        Self::authorize(request, url)
+    }
+
+    /// Build a GET request to the JSON API
+    pub fn api(client: &reqwest::Client, url: &str) -> reqwest::RequestBuilder {
+        Self::get(client, url).header("Accept", "application/vnd.github+json")
+    }
+
+    /// Build a POST request to the API; requires a token
+    pub fn post(client: &reqwest::Client, url: &str) -> Result<reqwest::RequestBuilder> {
+        let token = GitHubCredentials::require()?;
+        Ok(client
+            .post(url)
+            .header("User-Agent", USER_AGENT)
+            .header("Authorization", format!("Bearer {}", token.token))
+            .header("Accept", "application/vnd.github+json"))
+    }
+
+    /// Fetch a text file. Contents API URLs are requested raw.
+    pub async fn fetch_text(client: &reqwest::Client, url: &str) -> Result<String> {
+        let mut request = Self::get(client, url);
+        if url.starts_with("https://api.github.com/") {
+            request = request.header("Accept", "application/vnd.github.raw");
+        }
+
+        let response = Self::check(request.send().await?, url).await?;
+        Ok(response.text().await?)
+    }
+
+    /// Fetch a text file, revalidating the copy kept from the last fetch
+    /// with If-None-Match. Contents API URLs are requested raw.
+    pub async fn fetch_text_revalidated(client: &reqwest::Client, url: &str) -> Result<String> {
+        let accept = if url.starts_with("https://api.github.com/") {
+            "application/vnd.github.raw"
+        } else {
+            "*/*"
+        };
+        Self::fetch_revalidated(client, url, accept, url).await
+    }
+
+    /// GET `url`, answering from the stored copy when the server replies
+    /// 304 Not Modified. Responses without an ETag are not stored.
+    pub async fn fetch_revalidated(
+        client: &reqwest::Client,
+        url: &str,
+        accept: &str,
+        context: &str,
+    ) -> Result<String> {
+        let path = Self::cached_response_path(url, accept)?;
+        let cached: Option<CachedResponse> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+
+        let mut request = Self::get(client, url).header("Accept", accept);
+        if let Some(cached) = &cached {
+            request = request.header("If-None-Match", &cached.etag);
+        }
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                return Ok(cached.body);
+            }
+        }
+
+        let response = Self::check(response, context).await?;
+        let etag = response
+            .headers()
+            .get("etag")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let body = response.text().await?;
+
+        if let Some(etag) = etag {
+            let entry = CachedResponse {
+                etag,
+                body: body.clone(),
+            };
+            // Only a cache: failing to write it must not fail the fetch
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let _ = fs::write(&path, serde_json::to_string(&entry)?);
+        }
+
+        Ok(body)
+    }
+
+    fn cached_response_path(url: &str, accept: &str) -> Result<PathBuf> {
+        let digest = format!("{:x}", Sha256::digest(format!("{} {}", accept, url)));
+        Ok(Paths::rhinolabs_config_dir()?
+            .join("cache")
+            .join("http")
+            .join(format!("{}.json", &digest[..32])))
+    }
+
+    /// Download a release asset through the API asset endpoint,
+    /// which works for private repositories
+    pub async fn download_asset(
+        client: &reqwest::Client,
+        asset: &serde_json::Value,
+    ) -> Result<Vec<u8>> {
+        let (url, accept) = match asset["url"].as_str() {
+            Some(url) => (url, "application/octet-stream"),
+            None => (
+                asset["browser_download_url"]
+                    .as_str()
+                    .ok_or_else(|| RhinolabsError::Other("Invalid asset URL".into()))?,
+                "*/*",
+            ),
+        };
+
+        let name = asset["name"].as_str().unwrap_or("asset");
+        let request = Self::get(client, url).header("Accept", accept);
+        let response = Self::check(request.send().await?, name).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Check a response, mapping auth and rate limit failures to clear errors
+    pub async fn check(response: reqwest::Response, context: &str) -> Result<reqwest::Response> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let remaining = header("x-ratelimit-remaining");
+        let reset = header("x-ratelimit-reset");
+        let body = response.text().await.unwrap_or_default();
+
+        Err(Self::status_error(
+            status.as_u16(),
+            remaining.as_deref(),
+            reset.as_deref(),
+            &body,
+            context,
+            GitHubCredentials::resolve().map(|t| t.source),
+        ))
+    }
+
+    /// Build the error for a failed GitHub response
+    fn status_error(
+        status: u16,
+        rate_limit_remaining: Option<&str>,
+        rate_limit_reset: Option<&str>,
+        body: &str,
+        context: &str,
+        token: Option<TokenSource>,
+    ) -> RhinolabsError {
+        let rate_limited = status == 429
+            || (status == 403
+                && (rate_limit_remaining == Some("0") || body.contains("rate limit")));
+
+        if rate_limited {
+            let reset = rate_limit_reset
+                .and_then(|r| r.parse::<i64>().ok())
+                .and_then(|r| chrono::DateTime::from_timestamp(r, 0))
+                .map(|t| format!(" Resets at {}.", t.format("%H:%M UTC")))
+                .unwrap_or_default();
+            let hint = if token.is_none() {
+                " Configure a GitHub token to raise the limit."
+            } else {
+                ""
+            };
+            return RhinolabsError::RateLimited(format!("{}.{}{}", context, reset, hint));
+        }
+
+        let login_hint = "Set GITHUB_TOKEN or GH_TOKEN, run 'gh auth login', \
+                          or run 'rhinolabs-ai auth login'";
+
+        match (status, token) {
+            (401, Some(source)) => RhinolabsError::GitHubAuth(format!(
+                "{}: the token from {} was rejected (401). It may be expired or revoked",
+                context, source
+            )),
+            (401, None) => RhinolabsError::GitHubAuth(format!(
+                "{}: authentication required (401). {}",
+                context, login_hint
+            )),
+            (403, Some(source)) => RhinolabsError::GitHubAuth(format!(
+                "{}: access denied (403). The token from {} cannot access this repository \
+                 (private repositories need the 'repo' scope)",
+                context, source
+            )),
+            (403, None) => RhinolabsError::GitHubAuth(format!(
+                "{}: access denied (403). {}",
+                context, login_hint
+            )),
+            (404, None) => RhinolabsError::NetworkError(format!(
+                "{}: not found (404). If the repository is private, configure a token: {}",
+                context, login_hint
+            )),
+            _ => {
+                let message = serde_json::from_str::<serde_json::Value>(body)
+                    .ok()
+                    .and_then(|v| v["message"].as_str().map(|m| format!(": {}", m)))
+                    .unwrap_or_default();
+                RhinolabsError::NetworkError(format!(
+                    "{}: GitHub returned HTTP {}{}",
+                    context, status, message
+                ))
+            }
+        }
+    }
+
+    /// Add the token to requests for GitHub hosts only
+    fn authorize(request: reqwest::RequestBuilder, url: &str) -> reqwest::RequestBuilder {
+        let is_github = [
+            "https://api.github.com/",
+            "https://uploads.github.com/",
+            "https://raw.githubusercontent.com/",
+            "https://github.com/",
+        ]
+        .iter()
+        .any(|host| url.starts_with(host));
+
+        match GitHubCredentials::resolve() {
+            Some(token) if is_github => {
+                request.header("Authorization", format!("Bearer {}", token.token))
+            }
+            _ => request,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::await_holding_lock)] // async tests hold ENV_MUTEX for their whole body
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestEnv, ENV_MUTEX};
+
+    /// Clears token env vars for the duration of a test
+    struct TokenEnv {
+        original: Vec<(&'static str, Option<String>)>,
+    }
+
+    impl TokenEnv {
+        fn new() -> Self {
+            let original = ["GITHUB_TOKEN", "GH_TOKEN"]
+                .into_iter()
+                .map(|var| {
+                    let value = std::env::var(var).ok();
+                    std::env::remove_var(var);
+                    (var, value)
+                })
+                .collect();
+            TokenEnv { original }
+        }
+    }
+
+    impl Drop for TokenEnv {
+        fn drop(&mut self) {
+            for (var, value) in &self.original {
+                match value {
+                    Some(v) => std::env::set_var(var, v),
+                    None => std::env::remove_var(var),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_env_token_precedence() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let _env = TokenEnv::new();
+
+        std::env::set_var("GH_TOKEN", "gh-token");
+        let token = GitHubCredentials::from_env().unwrap();
+        assert_eq!(token.token, "gh-token");
+        assert_eq!(token.source, TokenSource::GhTokenEnv);
+
+        std::env::set_var("GITHUB_TOKEN", "github-token");
+        let token = GitHubCredentials::resolve().unwrap();
+        assert_eq!(token.token, "github-token");
+        assert_eq!(token.source, TokenSource::GithubTokenEnv);
+
+        std::env::set_var("GITHUB_TOKEN", "  ");
+        assert_eq!(GitHubCredentials::from_env().unwrap().token, "gh-token");
+    }
+
+    #[test]
+    fn test_secret_file_store_and_clear() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+
+        GitHubCredentials::store(" file-token\n", TokenStore::SecretFile).unwrap();
+
+        let path = env.config_dir().join("github-token");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "file-token");
+        let token = GitHubCredentials::from_secret_file().unwrap();
+        assert_eq!(token.token, "file-token");
+        assert_eq!(token.source, TokenSource::SecretFile);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // Not `clear()`: that also deletes the keyring entry of whoever runs the tests
+        GitHubCredentials::clear_secret_file().unwrap();
+        assert!(GitHubCredentials::from_secret_file().is_none());
+    }
+
+    #[test]
+    fn test_store_rejects_empty_token() {
+        assert!(GitHubCredentials::store("  ", TokenStore::SecretFile).is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_error() {
+        let err = GitHub::status_error(
+            403,
+            Some("0"),
+            Some("1700000000"),
+            "",
+            "Fetching releases",
+            None,
+        );
+
+        assert!(matches!(err, RhinolabsError::RateLimited(_)));
+        let msg = err.to_string();
+        assert!(msg.contains("Resets at"));
+        assert!(msg.contains("Configure a GitHub token"));
+
+        let err = GitHub::status_error(429, None, None, "", "x", Some(TokenSource::GhCli));
+        assert!(matches!(err, RhinolabsError::RateLimited(_)));
+        assert!(!err.to_string().contains("Configure a GitHub token"));
+    }
+
+    #[test]
+    fn test_auth_errors() {
+        let err = GitHub::status_error(401, None, None, "", "Sync", Some(TokenSource::Keyring));
+        assert!(matches!(err, RhinolabsError::GitHubAuth(_)));
+        assert!(err.to_string().contains("OS keyring"));
+
+        let err = GitHub::status_error(403, Some("42"), None, "", "Sync", None);
+        assert!(matches!(err, RhinolabsError::GitHubAuth(_)));
+        assert!(err.to_string().contains("GITHUB_TOKEN"));
+
+        let err = GitHub::status_error(404, None, None, "", "Sync", None);
+        assert!(err.to_string().contains("private"));
+
+        let err = GitHub::status_error(
+            422,
+            None,
+            None,
+            r#"{"message":"Validation Failed"}"#,
+            "Create release",
+            Some(TokenSource::GhCli),
+        );
+        assert!(matches!(err, RhinolabsError::NetworkError(_)));
+        assert!(err.to_string().contains("HTTP 422: Validation Failed"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_revalidated_answers_304_from_disk() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let _env = TestEnv::new();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/SKILL.md"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/SKILL.md"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_string("# Skill"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = reqwest::Client::new();
+        let url = format!("{}/SKILL.md", server.uri());
+        for _ in 0..2 {
+            assert_eq!(
+                GitHub::fetch_text_revalidated(&client, &url).await.unwrap(),
+                "# Skill"
+            );
+        }
+    }
+}
@@ -1,14 +1,18 @@
-use crate::{Paths, Result, RhinolabsError, Version};
+use crate::{Channels, GitHub, Paths, ReleaseChannel, Result, RhinolabsError, Version};
 use std::fs;
 use std::path::Path;
 
 pub struct Installer {
     dry_run: bool,
+    channel: Option<ReleaseChannel>,
 }
 
 impl Installer {
     pub fn new() -> Self {
-        Self { dry_run: false }
+        Self {
+            dry_run: false,
+            channel: None,
+        }
     }
 
     pub fn dry_run(mut self, enabled: bool) -> Self {
@@ -16,6 +20,12 @@ impl Installer {
         self
     }
 
+    /// Install from a release channel instead of this machine's channel
+    pub fn channel(mut self, channel: ReleaseChannel) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
     /// Install plugin from GitHub release
     pub async fn install(&self) -> Result<()> {
         // Check if Claude Code is installed
@@ -36,8 +46,12 @@ impl Installer {
             return Ok(());
         }
 
-        // Download plugin zip from latest release
-        let download_url = Version::get_latest_download_url().await?;
+        // Download plugin zip from the latest release on the channel
+        let channel = match self.channel {
+            Some(channel) => channel,
+            None => Channels::current()?,
+        };
+        let download_url = Version::get_latest_download_url_for(channel).await?;
         let zip_data = self.download_file(&download_url).await?;
 
         // Extract to plugin directory
@@ -111,14 +125,12 @@ impl Installer {
 
     /// Download file from URL
     async fn download_file(&self, url: &str) -> Result<Vec<u8>> {
-        let response = reqwest::get(url).await?;
-
-        if !response.status().is_success() {
-            return Err(RhinolabsError::DownloadFailed(format!(
-                "HTTP {}",
-                response.status()
-            )));
-        }
+        let client = reqwest::Client::new();
+        let response = GitHub::get(&client, url)
+            .header("Accept", "application/octet-stream")
+            .send()
+            .await?;
+        let response = GitHub::check(response, "Failed to download plugin").await?;
 
         let bytes = response.bytes().await?;
         Ok(bytes.to_vec())
@@ -1,33 +1,62 @@
+pub mod channels;
+pub mod config_diff;
 pub mod deploy;
 pub mod diagnostics;
 pub mod error;
 pub mod git;
+pub mod github;
 pub mod installer;
 pub mod instructions;
 pub mod manifest;
+pub mod marketplace;
 pub mod mcp_config;
 pub mod mcp_sync;
+pub mod merge;
+pub mod migrations;
 pub mod output_styles;
 pub mod paths;
 pub mod profiles;
 pub mod project;
 pub mod rag;
 pub mod settings;
+pub mod skill_catalog;
+pub mod skill_graph;
+pub mod skill_lint;
+pub mod skill_package;
+pub mod skill_scan;
+pub mod skill_search;
+pub mod skill_sources;
+pub mod skill_templates;
 pub mod skills;
+pub mod tokens;
 pub mod updater;
 pub mod version;
 
 #[cfg(test)]
 mod test_utils;
 
-pub use deploy::{ConfigManifest, Deploy, DeployResult, SyncResult};
+pub use channels::{Channels, ReleaseChannel};
+pub use config_diff::{
+    ChangeKind, ConfigDiff, ConfigSnapshot, McpServerChange, PermissionChange, ProfileSkillChanges,
+    SkillTextDiff,
+};
+pub use deploy::{
+    ConfigManifest, ConflictStrategy, Deploy, DeployResult, SyncConflict, SyncOptions, SyncResult,
+};
 pub use diagnostics::Doctor;
 pub use error::{Result, RhinolabsError};
+pub use github::{GitHub, GitHubCredentials, GitHubToken, TokenSource, TokenStore};
 pub use installer::Installer;
 pub use instructions::{Instructions, InstructionsManager};
 pub use manifest::{Author, Manifest, PluginManifest};
+pub use marketplace::{
+    Marketplace, MarketplaceCatalog, MarketplaceFetcher, MarketplacePlugin, PluginComponents,
+    PluginInstallOptions, PluginInstallResult, PluginReview,
+};
 pub use mcp_config::{McpConfig, McpConfigManager, McpServer, McpSettings};
 pub use mcp_sync::McpSync;
+pub use merge::{Merge, MergeStatus, TextMerge};
+pub use migrations::Migrations;
 pub use output_styles::{OutputStyle, OutputStyles};
 pub use paths::Paths;
 pub use profiles::{
@@ -39,9 +68,23 @@ pub use rag::{Rag, RagConfig, RagSettings};
 pub use settings::{
     AttributionConfig, PermissionConfig, PluginSettings, Settings, StatusLineConfig,
 };
+pub use skill_catalog::{CatalogSkill, SkillCatalog, SourceCatalog};
+pub use skill_graph::SkillGraph;
+pub use skill_lint::{LintFinding, LintReport, LintSeverity, SkillLinter};
+pub use skill_package::{SkillManifest, SkillPackage, SkillPackageContents};
+pub use skill_scan::{RiskFinding, RiskKind, RiskLevel, ScanPolicy, SecurityReport, SkillScanner};
+pub use skill_search::{SearchDocument, SearchOptions, SearchResult, SkillSearch};
+pub use skill_sources::{
+    ArchiveFetcher, FetchFuture, GitFetcher, GitHubFetcher, LocalFetcher, SkillFetcher,
+    SkillIdFrom, SkillLayout, SourceKind, SourceSkill,
+};
+pub use skill_templates::{SkillTemplate, SkillTemplates, TemplateVariable};
 pub use skills::{
-    CreateSkillInput, InstallSkillInput, RemoteSkill, RemoteSkillFile, Skill, SkillCategory,
-    SkillSchema, SkillSource, SkillSourceType, Skills, UpdateSkillInput,
+    CreateFromTemplateInput, CreateSkillInput, InstallSkillInput, RemoteSkill, RemoteSkillFile,
+    Skill, SkillCategory, SkillFileMerge, SkillFilter, SkillRenameResult, SkillSchema, SkillSource,
+    SkillSourceType, SkillUpdate, SkillUpdateCheck, SkillUpgradeResult, Skills, UpdateSkillInput,
+    CORPORATE_CATEGORY, CUSTOM_CATEGORY,
 };
+pub use tokens::{ProfileTokenReport, TokenEstimate, TokenEstimator, DEFAULT_TOKEN_BUDGET};
 pub use updater::Updater;
-pub use version::Version;
+pub use version::{Version, CLI_UPGRADE_HINT};
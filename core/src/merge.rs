@@ -0,0 +1,271 @@
+//! Merge module for three-way merges of skill files
+//!
+//! This module handles:
+//! - Line-based three-way text merges (base, ours, theirs) with conflict markers
+//! - Per-file merge decisions, including added, removed and binary files
+
+use serde::{Deserialize, Serialize};
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+const MARKER_OURS: &str = "<<<<<<< local";
+const MARKER_SEPARATOR: &str = "=======";
+const MARKER_THEIRS: &str = ">>>>>>> upstream";
+
+/// Outcome of merging a single file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStatus {
+    /// Nothing changed upstream (local edits, if any, are kept)
+    Unchanged,
+    /// Upstream change applied to an unmodified file
+    Updated,
+    /// New file upstream
+    Added,
+    /// File removed upstream and not modified locally
+    Removed,
+    /// Local and upstream changes merged cleanly
+    Merged,
+    /// Overlapping changes; conflict markers written (or local copy kept for
+    /// binary files and edit/delete conflicts)
+    Conflict,
+}
+
+/// Result of a line-based three-way merge
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMerge {
+    pub content: String,
+    pub conflicts: usize,
+}
+
+pub struct Merge;
+
+impl Merge {
+    /// Merge one file. `None` means the file does not exist on that side.
+    /// Returns the resulting content (`None` = delete) and the merge status.
+    pub fn file(
+        base: Option<&[u8]>,
+        ours: Option<&[u8]>,
+        theirs: Option<&[u8]>,
+    ) -> (Option<Vec<u8>>, MergeStatus) {
+        let owned = |side: Option<&[u8]>| side.map(|c| c.to_vec());
+
+        if ours == theirs || theirs == base {
+            return (owned(ours), MergeStatus::Unchanged);
+        }
+
+        if ours == base {
+            let status = match (base, theirs) {
+                (_, None) => MergeStatus::Removed,
+                (None, Some(_)) => MergeStatus::Added,
+                _ => MergeStatus::Updated,
+            };
+            return (owned(theirs), status);
+        }
+
+        // Both sides changed
+        let (Some(ours_content), Some(theirs_content)) = (ours, theirs) else {
+            // Edited on one side, deleted on the other: keep what exists
+            return (owned(ours.or(theirs)), MergeStatus::Conflict);
+        };
+
+        let texts = (
+            std::str::from_utf8(base.unwrap_or_default()),
+            std::str::from_utf8(ours_content),
+            std::str::from_utf8(theirs_content),
+        );
+        let (Ok(base_text), Ok(ours_text), Ok(theirs_text)) = texts else {
+            // Binary files can't be merged line by line
+            return (owned(ours), MergeStatus::Conflict);
+        };
+
+        let merged = Self::text(base_text, ours_text, theirs_text);
+        let status = if merged.conflicts == 0 {
+            MergeStatus::Merged
+        } else {
+            MergeStatus::Conflict
+        };
+        (Some(merged.content.into_bytes()), status)
+    }
+
+    /// Line-based three-way merge (diff3)
+    ///
+    /// Regions changed on only one side take that side; regions changed
+    /// identically on both sides are applied once; anything else is written
+    /// between `<<<<<<< local` / `=======` / `>>>>>>> upstream` markers.
+    pub fn text(base: &str, ours: &str, theirs: &str) -> TextMerge {
+        let base: Vec<&str> = base.split_inclusive('\n').collect();
+        let ours: Vec<&str> = ours.split_inclusive('\n').collect();
+        let theirs: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+        let to_ours = Self::matches(&base, &ours);
+        let to_theirs = Self::matches(&base, &theirs);
+
+        let mut content = String::new();
+        let mut conflicts = 0;
+        let (mut b, mut o, mut t) = (0, 0, 0);
+
+        loop {
+            // Next base line kept unchanged on both sides
+            let stable = (b..base.len()).find_map(|i| Some((i, to_ours[i]?, to_theirs[i]?)));
+            let (next_b, next_o, next_t) = stable.unwrap_or((base.len(), ours.len(), theirs.len()));
+
+            let base_chunk = &base[b..next_b];
+            let ours_chunk = &ours[o..next_o];
+            let theirs_chunk = &theirs[t..next_t];
+
+            if ours_chunk == theirs_chunk || theirs_chunk == base_chunk {
+                content.extend(ours_chunk.iter().copied());
+            } else if ours_chunk == base_chunk {
+                content.extend(theirs_chunk.iter().copied());
+            } else {
+                conflicts += 1;
+                Self::push_conflict(&mut content, ours_chunk, theirs_chunk);
+            }
+
+            let Some((stable_b, stable_o, stable_t)) = stable else {
+                break;
+            };
+            content.push_str(base[stable_b]);
+            b = stable_b + 1;
+            o = stable_o + 1;
+            t = stable_t + 1;
+        }
+
+        TextMerge { content, conflicts }
+    }
+
+    /// For each base line, the index of the matching line on the other side
+    fn matches(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+        let mut map = vec![None; base.len()];
+        for op in capture_diff_slices(Algorithm::Myers, base, other) {
+            if let DiffOp::Equal {
+                old_index,
+                new_index,
+                len,
+            } = op
+            {
+                for offset in 0..len {
+                    map[old_index + offset] = Some(new_index + offset);
+                }
+            }
+        }
+        map
+    }
+
+    fn push_conflict(content: &mut String, ours: &[&str], theirs: &[&str]) {
+        let push_lines = |content: &mut String, lines: &[&str]| {
+            for line in lines {
+                content.push_str(line);
+            }
+            if !content.ends_with('\n') {
+                content.push('\n');
+            }
+        };
+
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(MARKER_OURS);
+        content.push('\n');
+        push_lines(content, ours);
+        content.push_str(MARKER_SEPARATOR);
+        content.push('\n');
+        push_lines(content, theirs);
+        content.push_str(MARKER_THEIRS);
+        content.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "# Skill\n\nintro\n\n## Rules\n- one\n- two\n\n## Notes\nend\n";
+
+    #[test]
+    fn test_text_merge_non_overlapping_changes() {
+        let ours = BASE.replace("intro", "local intro");
+        let theirs = BASE.replace("- two\n", "- two\n- three\n");
+
+        let merged = Merge::text(BASE, &ours, &theirs);
+
+        assert_eq!(merged.conflicts, 0);
+        assert!(merged.content.contains("local intro"));
+        assert!(merged.content.contains("- three"));
+        assert!(!merged.content.contains(MARKER_OURS));
+    }
+
+    #[test]
+    fn test_text_merge_same_change_on_both_sides() {
+        let changed = BASE.replace("- one", "- uno");
+
+        let merged = Merge::text(BASE, &changed, &changed);
+
+        assert_eq!(merged.conflicts, 0);
+        assert_eq!(merged.content, changed);
+    }
+
+    #[test]
+    fn test_text_merge_conflict_markers() {
+        let ours = BASE.replace("- one", "- local one");
+        let theirs = BASE.replace("- one", "- upstream one");
+
+        let merged = Merge::text(BASE, &ours, &theirs);
+
+        assert_eq!(merged.conflicts, 1);
+        assert!(merged
+            .content
+            .contains("<<<<<<< local\n- local one\n=======\n- upstream one\n>>>>>>> upstream\n"));
+        assert!(merged.content.starts_with("# Skill\n"));
+        assert!(merged.content.ends_with("## Notes\nend\n"));
+    }
+
+    #[test]
+    fn test_text_merge_without_trailing_newline() {
+        let merged = Merge::text("a\nb", "a\nlocal", "a\nupstream");
+
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(
+            merged.content,
+            "a\n<<<<<<< local\nlocal\n=======\nupstream\n>>>>>>> upstream\n"
+        );
+    }
+
+    #[test]
+    fn test_file_merge_statuses() {
+        let base = Some(b"base\n".as_slice());
+        let local = Some(b"local\n".as_slice());
+        let upstream = Some(b"upstream\n".as_slice());
+
+        assert_eq!(
+            Merge::file(base, local, base),
+            (Some(b"local\n".to_vec()), MergeStatus::Unchanged)
+        );
+        assert_eq!(
+            Merge::file(base, base, upstream),
+            (Some(b"upstream\n".to_vec()), MergeStatus::Updated)
+        );
+        assert_eq!(
+            Merge::file(None, None, upstream),
+            (Some(b"upstream\n".to_vec()), MergeStatus::Added)
+        );
+        assert_eq!(Merge::file(base, base, None), (None, MergeStatus::Removed));
+        assert_eq!(
+            Merge::file(base, local, None),
+            (Some(b"local\n".to_vec()), MergeStatus::Conflict)
+        );
+        assert_eq!(Merge::file(base, local, upstream).1, MergeStatus::Conflict);
+    }
+
+    #[test]
+    fn test_file_merge_keeps_local_binary_on_conflict() {
+        let base = Some([0xff, 0x00].as_slice());
+        let local = Some([0xff, 0x01].as_slice());
+        let upstream = Some([0xff, 0x02].as_slice());
+
+        assert_eq!(
+            Merge::file(base, local, upstream),
+            (Some(vec![0xff, 0x01]), MergeStatus::Conflict)
+        );
+    }
+}
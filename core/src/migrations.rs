@@ -0,0 +1,555 @@
+//! Migrations module for upgrading older configuration shapes
+//!
+//! This module handles:
+//! - Compatibility checks for config bundles (formatVersion / minCliVersion)
+//! - Upgrading older bundle layouts on import
+//! - Upgrading older `profiles.json` and `.skills-config.json` shapes
+//!
+//! Each migration table is indexed by the version it upgrades from:
+//! entry `N` turns version `N` into `N + 1`.
+
+use crate::{
+    Paths, Result, RhinolabsError, SkillCategory, SkillPackage, Skills, Version, CLI_UPGRADE_HINT,
+};
+use semver::Version as SemVersion;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Bundle format written by this CLI
+pub const BUNDLE_FORMAT_VERSION: u32 = 2;
+
+/// Format assumed for bundles whose manifest predates `formatVersion`
+pub const LEGACY_BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Oldest CLI release able to read `BUNDLE_FORMAT_VERSION` bundles.
+/// Bump together with the format.
+pub const BUNDLE_MIN_CLI_VERSION: &str = "0.1.0";
+
+/// Current `profiles.json` schema
+pub const PROFILES_SCHEMA_VERSION: u32 = 1;
+
+/// Current `.skills-config.json` schema
+pub const SKILLS_CONFIG_SCHEMA_VERSION: u32 = 3;
+
+/// Key holding the schema version in JSON config files (absent = 0)
+const SCHEMA_VERSION_KEY: &str = "schemaVersion";
+
+/// Files of a bundle, keyed by their path in the zip
+pub type BundleFiles = BTreeMap<String, Vec<u8>>;
+
+type JsonMigration = fn(&mut Value) -> Result<()>;
+type BundleMigration = fn(&mut BundleFiles) -> Result<()>;
+
+const PROFILES_MIGRATIONS: &[JsonMigration] = &[profiles_v0_to_v1];
+const SKILLS_CONFIG_MIGRATIONS: &[JsonMigration] = &[
+    skills_config_v0_to_v1,
+    skills_config_v1_to_v2,
+    skills_config_v2_to_v3,
+];
+const BUNDLE_MIGRATIONS: &[BundleMigration] = &[bundle_v1_to_v2];
+
+pub struct Migrations;
+
+impl Migrations {
+    // ============================================
+    // Bundles
+    // ============================================
+
+    /// Check whether a bundle can be applied by this CLI
+    ///
+    /// Fails when the bundle format is newer than this CLI understands.
+    /// Returns warnings for bundles that ask for a newer CLI but are still readable.
+    pub fn check_bundle(format_version: u32, min_cli_version: Option<&str>) -> Result<Vec<String>> {
+        if format_version > BUNDLE_FORMAT_VERSION {
+            return Err(RhinolabsError::IncompatibleBundle(format!(
+                "bundle format {} is newer than the supported format {}{}",
+                format_version,
+                BUNDLE_FORMAT_VERSION,
+                min_cli_version
+                    .map(|v| format!(" (requires CLI {} or newer)", v))
+                    .unwrap_or_default()
+            )));
+        }
+
+        let mut warnings = Vec::new();
+
+        if let Some(required) = min_cli_version {
+            let current = Version::current();
+            let too_old = match (SemVersion::parse(required), SemVersion::parse(&current)) {
+                (Ok(required), Ok(current)) => current < required,
+                _ => false,
+            };
+            if too_old {
+                warnings.push(format!(
+                    "This configuration expects CLI {} or newer (installed: {}). {}.",
+                    required, current, CLI_UPGRADE_HINT
+                ));
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Upgrade the files of a bundle from `from_format` to the current format
+    pub fn upgrade_bundle(files: &mut BundleFiles, from_format: u32) -> Result<()> {
+        let from = from_format.max(LEGACY_BUNDLE_FORMAT_VERSION);
+        for version in from..BUNDLE_FORMAT_VERSION {
+            let step = BUNDLE_MIGRATIONS[(version - LEGACY_BUNDLE_FORMAT_VERSION) as usize];
+            step(files)?;
+        }
+        Ok(())
+    }
+
+    // ============================================
+    // Config files
+    // ============================================
+
+    /// Upgrade a `profiles.json` document in place
+    /// Returns true if anything was migrated
+    pub fn upgrade_profiles(value: &mut Value) -> Result<bool> {
+        Self::run(
+            value,
+            PROFILES_MIGRATIONS,
+            PROFILES_SCHEMA_VERSION,
+            "profiles.json",
+        )
+    }
+
+    /// Upgrade a `.skills-config.json` document in place
+    /// Returns true if anything was migrated
+    pub fn upgrade_skills_config(value: &mut Value) -> Result<bool> {
+        Self::run(
+            value,
+            SKILLS_CONFIG_MIGRATIONS,
+            SKILLS_CONFIG_SCHEMA_VERSION,
+            ".skills-config.json",
+        )
+    }
+
+    /// Record the current schema version in a config document before saving
+    pub fn stamp(value: &mut Value, version: u32) {
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert(SCHEMA_VERSION_KEY.into(), Value::from(version));
+        }
+    }
+
+    /// Schema version recorded in a config document (absent = 0)
+    pub fn schema_version(value: &Value) -> u32 {
+        value
+            .get(SCHEMA_VERSION_KEY)
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u32
+    }
+
+    /// Rehash the `originalHash` values of a local `.skills-config.json`
+    /// written before schema 3 (see `skills_config_v2_to_v3`)
+    ///
+    /// Reads the installed skills, so it only applies to this machine's own
+    /// config, never to a bundle. `from` is the schema the file had on disk.
+    /// - skills with a pristine upstream copy take the hash of that copy
+    /// - skills whose SKILL.md still matches the old hash take their current hash
+    /// - the others keep the old value, so they stay modified until reset or upgraded
+    pub(crate) fn rehash_skills(value: &mut Value, from: u32) -> Result<()> {
+        if from >= 3 {
+            return Ok(());
+        }
+        let Some(meta) = value
+            .get_mut("skillMeta")
+            .and_then(|meta| meta.as_object_mut())
+        else {
+            return Ok(());
+        };
+        if meta.is_empty() {
+            return Ok(());
+        }
+
+        let plugin_dir = Paths::plugin_dir()?;
+        for (skill_id, entry) in meta.iter_mut() {
+            // Ids are joined into paths
+            if SkillPackage::validate_id(skill_id).is_err() {
+                continue;
+            }
+            let Some(old) = entry.get("originalHash").and_then(|h| h.as_str()) else {
+                continue;
+            };
+            let skill_dir = plugin_dir.join("skills").join(skill_id);
+            let upstream = plugin_dir.join(".skills-upstream").join(skill_id);
+
+            let original = if upstream.is_dir() {
+                upstream
+            } else if std::fs::read_to_string(skill_dir.join("SKILL.md"))
+                .is_ok_and(|content| legacy_content_hash(&content) == old)
+            {
+                skill_dir
+            } else {
+                continue;
+            };
+            entry["originalHash"] = Value::from(Skills::hash_dir(&original)?);
+        }
+
+        Ok(())
+    }
+
+    /// Apply the pending steps of a migration table
+    fn run(value: &mut Value, steps: &[JsonMigration], current: u32, file: &str) -> Result<bool> {
+        let from = Self::schema_version(value);
+
+        if from > current {
+            return Err(RhinolabsError::ConfigError(format!(
+                "{} uses schema {} but this CLI only supports up to {}. {}.",
+                file, from, current, CLI_UPGRADE_HINT
+            )));
+        }
+
+        for step in &steps[from as usize..current as usize] {
+            step(value)?;
+        }
+        Self::stamp(value, current);
+
+        Ok(from < current)
+    }
+
+    /// Upgrade a JSON config file stored in a bundle, if present
+    fn upgrade_bundle_json(
+        files: &mut BundleFiles,
+        name: &str,
+        upgrade: fn(&mut Value) -> Result<bool>,
+    ) -> Result<()> {
+        if let Some(content) = files.get_mut(name) {
+            let mut value: Value = serde_json::from_slice(content)?;
+            if upgrade(&mut value)? {
+                *content = serde_json::to_string_pretty(&value)?.into_bytes();
+            }
+        }
+        Ok(())
+    }
+}
+
+// ============================================
+// Migration steps
+// ============================================
+
+/// Format 1 -> 2: legacy exports copied `profiles.json` and `.skills-config.json`
+/// verbatim, so they may carry unversioned shapes.
+fn bundle_v1_to_v2(files: &mut BundleFiles) -> Result<()> {
+    Migrations::upgrade_bundle_json(files, "profiles.json", Migrations::upgrade_profiles)?;
+    Migrations::upgrade_bundle_json(
+        files,
+        ".skills-config.json",
+        Migrations::upgrade_skills_config,
+    )?;
+    Ok(())
+}
+
+/// profiles.json 0 -> 1: missing `name`, `description`, `skills` and
+/// timestamps get defaults
+fn profiles_v0_to_v1(value: &mut Value) -> Result<()> {
+    let obj = value
+        .as_object_mut()
+        .ok_or_else(|| RhinolabsError::ConfigError("profiles.json must be an object".into()))?;
+
+    let profiles = obj
+        .entry("profiles")
+        .or_insert_with(|| Value::Array(Vec::new()));
+
+    let now = chrono::Utc::now().to_rfc3339();
+
+    for profile in profiles.as_array_mut().into_iter().flatten() {
+        let Some(profile) = profile.as_object_mut() else {
+            continue;
+        };
+
+        let id = profile.get("id").cloned().unwrap_or_default();
+        profile.entry("name").or_insert(id);
+        profile
+            .entry("description")
+            .or_insert_with(|| Value::String(String::new()));
+        profile
+            .entry("skills")
+            .or_insert_with(|| Value::Array(Vec::new()));
+        profile
+            .entry("createdAt")
+            .or_insert_with(|| Value::String(now.clone()));
+        profile
+            .entry("updatedAt")
+            .or_insert_with(|| Value::String(now.clone()));
+    }
+
+    Ok(())
+}
+
+/// .skills-config.json 0 -> 1: `disabled`, `custom` and `sources` became required
+fn skills_config_v0_to_v1(value: &mut Value) -> Result<()> {
+    let obj = value.as_object_mut().ok_or_else(|| {
+        RhinolabsError::ConfigError(".skills-config.json must be an object".into())
+    })?;
+
+    for key in ["disabled", "custom", "sources"] {
+        obj.entry(key).or_insert_with(|| Value::Array(Vec::new()));
+    }
+
+    Ok(())
+}
+
+/// Categories of the bundled skills, hardcoded before schema 2
+const BUILTIN_SKILL_CATEGORIES: &[(&str, &str)] = &[
+    ("rhinolabs-standards", "corporate"),
+    ("rhinolabs-architecture", "corporate"),
+    ("rhinolabs-security", "corporate"),
+    ("react-patterns", "frontend"),
+    ("typescript-best-practices", "frontend"),
+    ("tailwind-4", "frontend"),
+    ("zod-4", "frontend"),
+    ("zustand-5", "frontend"),
+    ("testing-strategies", "testing"),
+    ("playwright", "testing"),
+    ("ai-sdk-core", "ai-sdk"),
+    ("ai-sdk-react", "ai-sdk"),
+    ("nextjs-integration", "ai-sdk"),
+    ("skill-creator", "utilities"),
+];
+
+/// .skills-config.json 1 -> 2: categories became data
+/// - the built-in categories are written to `categories`
+/// - bundled skills get their former hardcoded category in `categoryMap`
+/// - the enum value `aisdk` becomes the category id `ai-sdk`
+fn skills_config_v1_to_v2(value: &mut Value) -> Result<()> {
+    let obj = value.as_object_mut().ok_or_else(|| {
+        RhinolabsError::ConfigError(".skills-config.json must be an object".into())
+    })?;
+
+    if !obj.contains_key("categories") {
+        obj.insert(
+            "categories".into(),
+            serde_json::to_value(SkillCategory::defaults())?,
+        );
+    }
+
+    let map = obj
+        .entry("categoryMap")
+        .or_insert_with(|| Value::Object(Default::default()));
+    let Some(map) = map.as_object_mut() else {
+        return Err(RhinolabsError::ConfigError(
+            ".skills-config.json categoryMap must be an object".into(),
+        ));
+    };
+
+    for category in map.values_mut() {
+        if category == "aisdk" {
+            *category = Value::from("ai-sdk");
+        }
+    }
+    for (skill_id, category) in BUILTIN_SKILL_CATEGORIES {
+        map.entry(*skill_id)
+            .or_insert_with(|| Value::from(*category));
+    }
+
+    Ok(())
+}
+
+/// Hash stored in `originalHash` before schema 3: std's `DefaultHasher`
+/// over SKILL.md. Its output isn't stable across Rust versions, so it is
+/// only compared here, by the binary that still computes it the same way.
+fn legacy_content_hash(content: &str) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+/// .skills-config.json 2 -> 3: `originalHash` became `Skills::hash_files` of
+/// the whole skill directory. The document keeps its values: they can only be
+/// rehashed against the installed skills, which `Migrations::rehash_skills`
+/// does for the local file when `Skills` loads it.
+fn skills_config_v2_to_v3(_value: &mut Value) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestEnv, ENV_MUTEX};
+    use serde_json::json;
+
+    #[test]
+    fn test_check_bundle_rejects_newer_format() {
+        let err = Migrations::check_bundle(BUNDLE_FORMAT_VERSION + 1, Some("99.0.0")).unwrap_err();
+
+        assert!(matches!(err, RhinolabsError::IncompatibleBundle(_)));
+        assert!(err.to_string().contains("99.0.0"));
+    }
+
+    #[test]
+    fn test_check_bundle_warns_on_newer_min_cli() {
+        let warnings = Migrations::check_bundle(BUNDLE_FORMAT_VERSION, Some("99.0.0")).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("99.0.0"));
+
+        let warnings =
+            Migrations::check_bundle(BUNDLE_FORMAT_VERSION, Some(BUNDLE_MIN_CLI_VERSION)).unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_upgrade_legacy_profiles_fills_defaults() {
+        let mut value = json!({
+            "profiles": [
+                { "id": "frontend", "profileType": "project", "skills": ["react-patterns"] }
+            ]
+        });
+
+        assert!(Migrations::upgrade_profiles(&mut value).unwrap());
+
+        assert_eq!(value["schemaVersion"], PROFILES_SCHEMA_VERSION);
+        let profile = &value["profiles"][0];
+        assert_eq!(profile["name"], "frontend");
+        assert_eq!(profile["description"], "");
+        assert!(profile["createdAt"].is_string());
+
+        let profiles: Vec<crate::Profile> =
+            serde_json::from_value(value["profiles"].clone()).unwrap();
+        assert_eq!(profiles[0].skills, vec!["react-patterns"]);
+    }
+
+    #[test]
+    fn test_upgrade_is_noop_for_current_schema() {
+        let mut value = json!({ "schemaVersion": PROFILES_SCHEMA_VERSION, "profiles": [] });
+        let before = value.clone();
+
+        assert!(!Migrations::upgrade_profiles(&mut value).unwrap());
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn test_upgrade_rejects_newer_schema() {
+        let mut value = json!({ "schemaVersion": SKILLS_CONFIG_SCHEMA_VERSION + 1 });
+        assert!(Migrations::upgrade_skills_config(&mut value).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_skills_config_fills_required_lists() {
+        let mut value = json!({ "disabled": ["playwright"] });
+
+        assert!(Migrations::upgrade_skills_config(&mut value).unwrap());
+        assert_eq!(value["disabled"], json!(["playwright"]));
+        assert_eq!(value["custom"], json!([]));
+        assert_eq!(value["sources"], json!([]));
+    }
+
+    #[test]
+    fn test_upgrade_skills_config_moves_categories_to_data() {
+        let mut value = json!({
+            "schemaVersion": 1,
+            "disabled": [],
+            "custom": ["my-ai"],
+            "sources": [],
+            "categoryMap": { "my-ai": "aisdk", "react-patterns": "testing" }
+        });
+
+        assert!(Migrations::upgrade_skills_config(&mut value).unwrap());
+        assert_eq!(value["schemaVersion"], SKILLS_CONFIG_SCHEMA_VERSION);
+        assert_eq!(value["categoryMap"]["my-ai"], "ai-sdk");
+        // User overrides of built-in skills are kept
+        assert_eq!(value["categoryMap"]["react-patterns"], "testing");
+        assert_eq!(value["categoryMap"]["rhinolabs-security"], "corporate");
+        let ids: Vec<&str> = value["categories"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "corporate",
+                "frontend",
+                "testing",
+                "ai-sdk",
+                "utilities",
+                "custom"
+            ]
+        );
+
+        // Categories defined by the config are left alone
+        let mut value = json!({ "schemaVersion": 1, "categories": [] });
+        Migrations::upgrade_skills_config(&mut value).unwrap();
+        assert_eq!(value["categories"], json!([]));
+    }
+
+    #[test]
+    fn test_upgrade_skills_config_rehashes_skills() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let skills_dir = env.plugin_dir().join("skills");
+        let write = |dir: &std::path::Path, content: &str| {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("SKILL.md"), content).unwrap();
+        };
+
+        let skill_md = "---\nname: a\ndescription: A\n---\n\n# A\n";
+        write(&skills_dir.join("unmodified"), skill_md);
+        write(
+            &skills_dir.join("edited"),
+            "---\nname: a\ndescription: A\n---\n\n# Edited\n",
+        );
+        write(
+            &skills_dir.join("upgraded"),
+            "---\nname: a\ndescription: A\n---\n\n# Merged\n",
+        );
+        write(
+            &env.plugin_dir().join(".skills-upstream/upgraded"),
+            skill_md,
+        );
+
+        let legacy = legacy_content_hash(skill_md);
+        let mut value = json!({
+            "schemaVersion": 2,
+            "skillMeta": {
+                "unmodified": { "sourceId": "s", "originalHash": legacy },
+                "edited": { "sourceId": "s", "originalHash": legacy },
+                "upgraded": { "sourceId": "s", "originalHash": legacy },
+                "untracked": { "sourceId": "s" },
+                "../unmodified": { "sourceId": "s", "originalHash": legacy }
+            }
+        });
+
+        // The JSON migration leaves the hashes alone; only the local rehash
+        // reads the installed skills
+        let from = Migrations::schema_version(&value);
+        assert!(Migrations::upgrade_skills_config(&mut value).unwrap());
+        assert_eq!(
+            value["skillMeta"]["unmodified"]["originalHash"],
+            legacy.as_str()
+        );
+        Migrations::rehash_skills(&mut value, from).unwrap();
+        let original = Skills::hash_dir(&skills_dir.join("unmodified")).unwrap();
+        let meta = &value["skillMeta"];
+        assert_eq!(meta["unmodified"]["originalHash"], original.as_str());
+        assert_eq!(meta["upgraded"]["originalHash"], original.as_str());
+        // Edited skills can't be rehashed and stay modified
+        assert_eq!(meta["edited"]["originalHash"], legacy.as_str());
+        assert!(meta["untracked"].get("originalHash").is_none());
+        assert_eq!(meta["../unmodified"]["originalHash"], legacy.as_str());
+    }
+
+    #[test]
+    fn test_upgrade_legacy_bundle() {
+        let mut files = BundleFiles::new();
+        files.insert(
+            "profiles.json".into(),
+            br#"{"profiles":[{"id":"main","profileType":"user"}]}"#.to_vec(),
+        );
+        files.insert(".skills-config.json".into(), b"{}".to_vec());
+        files.insert("CLAUDE.md".into(), b"# Rules".to_vec());
+
+        Migrations::upgrade_bundle(&mut files, LEGACY_BUNDLE_FORMAT_VERSION).unwrap();
+
+        let profiles: Value = serde_json::from_slice(&files["profiles.json"]).unwrap();
+        assert_eq!(profiles["schemaVersion"], PROFILES_SCHEMA_VERSION);
+        assert_eq!(profiles["profiles"][0]["name"], "main");
+        let skills: Value = serde_json::from_slice(&files[".skills-config.json"]).unwrap();
+        assert_eq!(skills["sources"], json!([]));
+        assert_eq!(files["CLAUDE.md"], b"# Rules".to_vec());
+    }
+}
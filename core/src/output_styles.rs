@@ -1,5 +1,5 @@
 use crate::settings::Settings;
-use crate::{Paths, Result, RhinolabsError};
+use crate::{Paths, Profiles, Result, RhinolabsError};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -250,6 +250,7 @@ impl OutputStyles {
         }
 
         fs::remove_file(&path)?;
+        Profiles::clear_output_style(id)?;
 
         Ok(())
     }
@@ -1,5 +1,7 @@
+use crate::migrations::PROFILES_SCHEMA_VERSION;
 use crate::{
-    InstructionsManager, OutputStyle, OutputStyles, Result, RhinolabsError, Settings, Skill, Skills,
+    InstructionsManager, Migrations, OutputStyle, OutputStyles, Paths, Result, RhinolabsError,
+    Settings, Skill, SkillGraph, Skills,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -60,6 +62,13 @@ pub struct Profile {
     /// Generate AGENTS.md as master file
     #[serde(default)]
     pub generate_agents: bool,
+    /// Token budget for the profile's context (defaults to `DEFAULT_TOKEN_BUDGET`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<usize>,
+    /// Output style installed with a project profile (the Main-Profile
+    /// uses the active style)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_style: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -83,6 +92,12 @@ pub struct CreateProfileInput {
     pub generate_copilot: bool,
     #[serde(default)]
     pub generate_agents: bool,
+    /// Token budget; `None` or 0 uses the default
+    #[serde(default)]
+    pub token_budget: Option<usize>,
+    /// Output style id
+    #[serde(default)]
+    pub output_style: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +109,12 @@ pub struct UpdateProfileInput {
     pub instructions: Option<String>,
     pub generate_copilot: Option<bool>,
     pub generate_agents: Option<bool>,
+    /// New token budget; 0 goes back to the default
+    #[serde(default)]
+    pub token_budget: Option<usize>,
+    /// New output style id; empty removes it
+    #[serde(default)]
+    pub output_style: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -160,14 +181,11 @@ pub struct Profiles;
 impl Profiles {
     /// Get the rhinolabs config directory: ~/.config/rhinolabs-ai/
     pub fn config_dir() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| RhinolabsError::Other("Could not find config directory".into()))?
-            .join("rhinolabs-ai");
-        Ok(config_dir)
+        Paths::rhinolabs_config_dir()
     }
 
     /// Get the profiles config file path
-    fn config_path() -> Result<PathBuf> {
+    pub(crate) fn config_path() -> Result<PathBuf> {
         Ok(Self::config_dir()?.join("profiles.json"))
     }
 
@@ -198,6 +216,8 @@ impl Profiles {
             instructions: None,
             generate_copilot: false, // Main-Profile doesn't generate copilot (user-level)
             generate_agents: false,
+            token_budget: None,
+            output_style: None,
             created_at: now.clone(),
             updated_at: now,
         }
@@ -310,7 +330,10 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
         }
 
         let content = fs::read_to_string(&path)?;
-        let mut config: ProfilesConfig = serde_json::from_str(&content)?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)?;
+        // Older shapes are upgraded in memory; the file is rewritten on next save
+        Migrations::upgrade_profiles(&mut value)?;
+        let mut config: ProfilesConfig = serde_json::from_value(value)?;
 
         // Ensure Main-Profile exists (migration for existing configs)
         if !config.profiles.iter().any(|p| p.id == "main") {
@@ -333,7 +356,9 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
             fs::create_dir_all(parent)?;
         }
 
-        let content = serde_json::to_string_pretty(config)?;
+        let mut value = serde_json::to_value(config)?;
+        Migrations::stamp(&mut value, PROFILES_SCHEMA_VERSION);
+        let content = serde_json::to_string_pretty(&value)?;
         fs::write(&path, content)?;
         Ok(())
     }
@@ -374,6 +399,10 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
             ));
         }
 
+        if let Some(style_id) = &input.output_style {
+            Self::check_output_style(style_id)?;
+        }
+
         let now = chrono::Utc::now().to_rfc3339();
 
         // Generate template instructions for new profiles (if not provided)
@@ -396,6 +425,8 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
             instructions,
             generate_copilot: input.generate_copilot,
             generate_agents: input.generate_agents,
+            token_budget: input.token_budget.filter(|budget| *budget > 0),
+            output_style: input.output_style,
             created_at: now.clone(),
             updated_at: now,
         };
@@ -417,6 +448,9 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
 
     /// Update an existing profile
     pub fn update(id: &str, input: UpdateProfileInput) -> Result<Profile> {
+        if let Some(style_id) = input.output_style.as_deref().filter(|s| !s.is_empty()) {
+            Self::check_output_style(style_id)?;
+        }
         let mut config = Self::load_config()?;
 
         let profile = config
@@ -440,6 +474,17 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
         if let Some(generate_agents) = input.generate_agents {
             profile.generate_agents = generate_agents;
         }
+        if let Some(token_budget) = input.token_budget {
+            profile.token_budget = (token_budget > 0).then_some(token_budget);
+        }
+        if let Some(style_id) = input.output_style {
+            if !style_id.is_empty() && profile.profile_type == ProfileType::User {
+                return Err(RhinolabsError::ConfigError(
+                    "The Main-Profile uses the active output style".into(),
+                ));
+            }
+            profile.output_style = (!style_id.is_empty()).then_some(style_id);
+        }
         // Note: profile_type is intentionally NOT updated.
         // Main-Profile is User, all others are Project. This cannot be changed.
 
@@ -481,12 +526,91 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
         Ok(())
     }
 
+    /// Error unless the output style exists
+    fn check_output_style(style_id: &str) -> Result<()> {
+        if OutputStyles::get(style_id)?.is_none() {
+            return Err(RhinolabsError::ConfigError(format!(
+                "Output style '{}' not found",
+                style_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Output style Claude uses with a profile: the active style for the
+    /// Main-Profile, the profile's own for project profiles
+    pub fn output_style(profile: &Profile) -> Result<Option<OutputStyle>> {
+        match (&profile.profile_type, &profile.output_style) {
+            (ProfileType::User, _) => OutputStyles::get_active(),
+            (ProfileType::Project, Some(style_id)) => OutputStyles::get(style_id),
+            (ProfileType::Project, None) => Ok(None),
+        }
+    }
+
+    /// Unset a deleted output style on the profiles that used it
+    pub(crate) fn clear_output_style(style_id: &str) -> Result<()> {
+        let mut config = Self::load_config()?;
+        let mut changed = false;
+        for profile in config
+            .profiles
+            .iter_mut()
+            .filter(|p| p.output_style.as_deref() == Some(style_id))
+        {
+            profile.output_style = None;
+            profile.updated_at = chrono::Utc::now().to_rfc3339();
+            changed = true;
+        }
+        if changed {
+            Self::save_config(&config)?;
+        }
+        Ok(())
+    }
+
+    /// Point the profiles' skill lists and auto-invoke rules at a renamed
+    /// skill. Returns the ids of the profiles that changed.
+    pub(crate) fn rename_skill(old_id: &str, new_id: &str) -> Result<Vec<String>> {
+        let mut config = Self::load_config()?;
+        let mut updated = Vec::new();
+        for profile in config.profiles.iter_mut() {
+            let mut changed = false;
+            if profile.skills.iter().any(|s| s == old_id) {
+                let mut skills: Vec<String> = Vec::new();
+                for skill in &profile.skills {
+                    let skill = if skill == old_id { new_id } else { skill };
+                    if !skills.iter().any(|s| s == skill) {
+                        skills.push(skill.to_string());
+                    }
+                }
+                profile.skills = skills;
+                changed = true;
+            }
+            for rule in profile
+                .auto_invoke_rules
+                .iter_mut()
+                .filter(|r| r.skill_id == old_id)
+            {
+                rule.skill_id = new_id.to_string();
+                changed = true;
+            }
+            if changed {
+                profile.updated_at = chrono::Utc::now().to_rfc3339();
+                updated.push(profile.id.clone());
+            }
+        }
+        if !updated.is_empty() {
+            Self::save_config(&config)?;
+        }
+        Ok(updated)
+    }
+
     // ============================================
     // Skill Assignment
     // ============================================
 
-    /// Assign skills to a profile (replaces existing skills)
+    /// Assign skills to a profile (replaces existing skills).
+    /// Skills they require are added; conflicting skills are refused.
     pub fn assign_skills(profile_id: &str, skill_ids: Vec<String>) -> Result<Profile> {
+        let skill_ids = SkillGraph::load()?.resolve(&skill_ids)?;
         let mut config = Self::load_config()?;
 
         let profile = config
@@ -628,10 +752,13 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
     ///   - Skills → target_path/.claude/skills/
     ///   - CLAUDE.md → target_path/CLAUDE.md (generated from profile)
     pub fn install(profile_id: &str, target_path: Option<&Path>) -> Result<ProfileInstallResult> {
-        let profile = Self::get(profile_id)?.ok_or_else(|| {
+        let mut profile = Self::get(profile_id)?.ok_or_else(|| {
             RhinolabsError::ConfigError(format!("Profile '{}' not found", profile_id))
         })?;
 
+        // Dependencies declared after the skills were assigned are installed too
+        profile.skills = SkillGraph::load()?.resolve(&profile.skills)?;
+
         let (base_target, claude_target, skills_target) = match profile.profile_type {
             ProfileType::User => {
                 let claude_dir = Self::claude_user_dir()?;
@@ -695,6 +822,7 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
     ///   - CLAUDE.md (with auto-invoke table and instructions)
     ///   - .github/copilot-instructions.md (if generate_copilot is true)
     ///   - AGENTS.md (if generate_agents is true)
+    ///   - .claude/output-styles/ and .claude/settings.json (if the profile has an output style)
     fn install_project_profile_as_plugin(
         target_path: &Path,
         profile: &Profile,
@@ -748,7 +876,70 @@ IMPORTANT: When you detect any of these contexts, IMMEDIATELY read the correspon
             fs::write(&agents_path, &content.agents_md)?;
         }
 
-        Ok((Some(true), None, None))
+        // 6. Install and select the profile's output style
+        let output_style_installed = match &profile.output_style {
+            Some(style_id) => {
+                let style = OutputStyles::get(style_id)?.ok_or_else(|| {
+                    RhinolabsError::ConfigError(format!(
+                        "Output style '{}' of profile '{}' not found",
+                        style_id, profile.id
+                    ))
+                })?;
+                Self::install_project_output_style(&Self::claude_project_dir(target_path), &style)?;
+                Some(style.name)
+            }
+            None => None,
+        };
+
+        Ok((Some(true), None, output_style_installed))
+    }
+
+    /// Write a style to .claude/output-styles/ and select it in
+    /// .claude/settings.json, keeping the project's other settings
+    fn install_project_output_style(claude_dir: &Path, style: &OutputStyle) -> Result<()> {
+        let styles_dir = claude_dir.join("output-styles");
+        fs::create_dir_all(&styles_dir)?;
+        fs::write(
+            styles_dir.join(format!("{}.md", style.id)),
+            Self::generate_output_style_content(style),
+        )?;
+
+        let settings_path = claude_dir.join("settings.json");
+        let mut settings = if settings_path.exists() {
+            serde_json::from_str(&fs::read_to_string(&settings_path)?)?
+        } else {
+            serde_json::json!({})
+        };
+        let Some(object) = settings.as_object_mut() else {
+            return Err(RhinolabsError::ConfigError(format!(
+                "{} is not a JSON object",
+                settings_path.display()
+            )));
+        };
+        object.insert("outputStyle".into(), style.name.clone().into());
+        fs::write(&settings_path, serde_json::to_string_pretty(&settings)?)?;
+        Ok(())
+    }
+
+    /// Instruction files Claude reads for a profile, as (file name, content):
+    /// the user CLAUDE.md for the Main-Profile, the generated CLAUDE.md (and
+    /// AGENTS.md when enabled) for project profiles
+    pub(crate) fn context_files(profile: &Profile) -> Result<Vec<(String, String)>> {
+        if profile.profile_type == ProfileType::User {
+            let instructions = InstructionsManager::get()?;
+            return Ok(if instructions.content.is_empty() {
+                Vec::new()
+            } else {
+                vec![("CLAUDE.md".to_string(), instructions.content)]
+            });
+        }
+
+        let content = Self::generate_ai_instructions_content(profile);
+        let mut files = vec![("CLAUDE.md".to_string(), content.claude_md)];
+        if profile.generate_agents {
+            files.push(("AGENTS.md".to_string(), content.agents_md));
+        }
+        Ok(files)
     }
 
     /// Generate content for CLAUDE.md, copilot-instructions.md, and AGENTS.md
@@ -1262,8 +1453,11 @@ mod tests {
             let base = BaseTestEnv::new();
             let config_dir = tempfile::tempdir().expect("Failed to create temp config dir");
 
-            // Override config dir for tests
-            std::env::set_var("RHINOLABS_CONFIG_PATH", config_dir.path());
+            // Override config dir for tests (Paths uses the parent of this file)
+            std::env::set_var(
+                "RHINOLABS_CONFIG_PATH",
+                config_dir.path().join("profiles.json"),
+            );
 
             TestEnv { base, config_dir }
         }
@@ -1285,11 +1479,23 @@ mod tests {
         }
 
         fn create_skill(&self, id: &str, name: &str, description: &str, content: &str) {
+            self.create_skill_with_frontmatter(id, name, description, "", content);
+        }
+
+        /// Skill with extra frontmatter lines (e.g. "requires: [a, b]\n")
+        fn create_skill_with_frontmatter(
+            &self,
+            id: &str,
+            name: &str,
+            description: &str,
+            extra: &str,
+            content: &str,
+        ) {
             let skill_dir = self.skills_dir().join(id);
             fs::create_dir_all(&skill_dir).expect("Failed to create skill dir");
             let skill_content = format!(
-                "---\nname: {}\ndescription: {}\n---\n\n{}",
-                name, description, content
+                "---\nname: {}\ndescription: {}\n{}---\n\n{}",
+                name, description, extra, content
             );
             fs::write(skill_dir.join("SKILL.md"), skill_content)
                 .expect("Failed to write skill file");
@@ -1323,6 +1529,36 @@ mod tests {
         assert!(config.default_user_profile.is_none());
     }
 
+    #[test]
+    fn test_create_profile_with_token_budget() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let _env = TestEnv::new();
+
+        let input = |id: &str, token_budget| CreateProfileInput {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            profile_type: ProfileType::Project,
+            skills: Vec::new(),
+            instructions: None,
+            generate_copilot: false,
+            generate_agents: false,
+            token_budget,
+            output_style: None,
+        };
+
+        let profile = Profiles::create(input("small", Some(5000))).unwrap();
+        assert_eq!(profile.token_budget, Some(5000));
+        assert_eq!(
+            Profiles::get("small").unwrap().unwrap().token_budget,
+            Some(5000)
+        );
+
+        // 0 means the default, like in updates
+        let profile = Profiles::create(input("default", Some(0))).unwrap();
+        assert_eq!(profile.token_budget, None);
+    }
+
     #[test]
     fn test_create_profile() {
         let _lock = ENV_MUTEX.lock().unwrap();
@@ -1344,6 +1580,8 @@ mod tests {
             instructions: None,
             generate_copilot: true,
             generate_agents: false,
+            token_budget: None,
+            output_style: None,
         };
 
         // Note: This test would need proper config path override mechanism
@@ -1359,6 +1597,8 @@ mod tests {
             instructions: Some("# Test Instructions".to_string()),
             generate_copilot: input.generate_copilot,
             generate_agents: input.generate_agents,
+            token_budget: None,
+            output_style: None,
             created_at: now.clone(),
             updated_at: now,
         };
@@ -1388,6 +1628,8 @@ mod tests {
             instructions: Some("# My Instructions".to_string()),
             generate_copilot: true,
             generate_agents: false,
+            token_budget: None,
+            output_style: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
         };
@@ -1552,6 +1794,8 @@ mod tests {
             instructions: None,
             generate_copilot: true,
             generate_agents: false,
+            token_budget: None,
+            output_style: None,
         };
 
         let json = serde_json::to_string(&input).unwrap();
@@ -1559,4 +1803,181 @@ mod tests {
         assert!(json.contains("\"generateCopilot\":true"));
         assert!(json.contains("\"generateAgents\":false"));
     }
+
+    fn project_profile(id: &str, skills: &[&str]) -> Profile {
+        let now = chrono::Utc::now().to_rfc3339();
+        Profile {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: "Test profile".to_string(),
+            profile_type: ProfileType::Project,
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            auto_invoke_rules: Vec::new(),
+            instructions: None,
+            generate_copilot: false,
+            generate_agents: false,
+            token_budget: None,
+            output_style: None,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    fn create_dependency_skills(env: &TestEnv) {
+        env.setup_skills_dir();
+        env.create_skill("ai-sdk-core", "AI SDK Core", "Core", "# Core");
+        env.create_skill_with_frontmatter(
+            "ai-sdk-react",
+            "AI SDK React",
+            "React hooks",
+            "requires: ai-sdk-core\n",
+            "# React",
+        );
+        env.create_skill("react-patterns", "React Patterns", "Patterns", "# Patterns");
+        env.create_skill_with_frontmatter(
+            "nextjs-integration",
+            "Next.js",
+            "Next.js",
+            "requires: [react-patterns]\n",
+            "# Next",
+        );
+        env.create_skill_with_frontmatter(
+            "vue-patterns",
+            "Vue Patterns",
+            "Vue",
+            "conflicts:\n  - react-patterns\n",
+            "# Vue",
+        );
+    }
+
+    #[test]
+    fn test_assign_skills_resolves_dependencies() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        create_dependency_skills(&env);
+        env.create_profiles_config(&ProfilesConfig {
+            profiles: vec![project_profile("frontend", &[])],
+            default_user_profile: None,
+        });
+
+        let profile = Profiles::assign_skills(
+            "frontend",
+            vec!["ai-sdk-react".to_string(), "nextjs-integration".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            profile.skills,
+            vec![
+                "ai-sdk-react",
+                "nextjs-integration",
+                "ai-sdk-core",
+                "react-patterns"
+            ]
+        );
+
+        // A conflict pulled in through a dependency is refused
+        let result = Profiles::assign_skills(
+            "frontend",
+            vec!["vue-patterns".to_string(), "nextjs-integration".to_string()],
+        );
+        assert!(result.unwrap_err().to_string().contains("conflict"));
+        assert_eq!(Profiles::get("frontend").unwrap().unwrap().skills.len(), 4);
+    }
+
+    #[test]
+    fn test_install_includes_dependencies_declared_later() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        create_dependency_skills(&env);
+        env.create_profiles_config(&ProfilesConfig {
+            profiles: vec![
+                project_profile("web", &["nextjs-integration", "react-patterns"]),
+                project_profile("broken", &["nextjs-integration"]),
+            ],
+            default_user_profile: None,
+        });
+
+        // react-patterns starts requiring a skill after it was assigned
+        env.create_skill("typescript", "TypeScript", "Types", "# TS");
+        env.create_skill_with_frontmatter(
+            "react-patterns",
+            "React Patterns",
+            "Patterns",
+            "requires: [typescript]\n",
+            "# Patterns",
+        );
+
+        let project = tempfile::tempdir().unwrap();
+        let result = Profiles::install("web", Some(project.path())).unwrap();
+        assert_eq!(
+            result.skills_installed,
+            vec!["nextjs-integration", "react-patterns", "typescript"]
+        );
+        assert!(project
+            .path()
+            .join(".claude/skills/typescript/SKILL.md")
+            .exists());
+
+        // Missing dependencies fail the install before anything is copied
+        fs::remove_dir_all(env.skills_dir().join("typescript")).unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let error = Profiles::install("broken", Some(other.path())).unwrap_err();
+        assert!(error.to_string().contains("'typescript'"));
+        assert!(!other.path().join(".claude").exists());
+    }
+
+    #[test]
+    fn test_install_project_output_style() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        env.setup_skills_dir();
+        env.create_skill("typescript", "TypeScript", "Types", "# TS");
+        env.create_profiles_config(&ProfilesConfig {
+            profiles: vec![project_profile("web", &["typescript"])],
+            default_user_profile: None,
+        });
+        OutputStyles::create("Terse", "Short answers", true, "Be brief.").unwrap();
+
+        let update = |style: &str| UpdateProfileInput {
+            name: None,
+            description: None,
+            profile_type: None,
+            instructions: None,
+            generate_copilot: None,
+            generate_agents: None,
+            token_budget: None,
+            output_style: Some(style.to_string()),
+        };
+        assert!(Profiles::update("web", update("missing")).is_err());
+        Profiles::update("web", update("terse")).unwrap();
+
+        // Existing project settings are kept
+        let project = tempfile::tempdir().unwrap();
+        fs::create_dir_all(project.path().join(".claude")).unwrap();
+        fs::write(
+            project.path().join(".claude/settings.json"),
+            r#"{"model": "opus"}"#,
+        )
+        .unwrap();
+
+        let result = Profiles::install("web", Some(project.path())).unwrap();
+        assert_eq!(result.output_style_installed.as_deref(), Some("Terse"));
+        let style =
+            fs::read_to_string(project.path().join(".claude/output-styles/terse.md")).unwrap();
+        assert!(style.contains("Be brief."));
+        let settings: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(project.path().join(".claude/settings.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(settings["outputStyle"], "Terse");
+        assert_eq!(settings["model"], "opus");
+
+        // Deleting the style unsets it
+        OutputStyles::delete("terse").unwrap();
+        assert!(Profiles::get("web")
+            .unwrap()
+            .unwrap()
+            .output_style
+            .is_none());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Paths, Result, RhinolabsError};
+use crate::{Channels, GitHub, Paths, ReleaseChannel, Result, RhinolabsError};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -234,11 +234,7 @@ impl Project {
         );
 
         let client = reqwest::Client::new();
-        let response = client
-            .get(&url)
-            .header("User-Agent", "rhinolabs-gui")
-            .send()
-            .await;
+        let response = GitHub::api(&client, &url).send().await;
 
         match response {
             Ok(resp) if resp.status().is_success() => {
@@ -265,11 +261,6 @@ impl Project {
             ));
         }
 
-        // Get GitHub token from environment
-        let token = std::env::var("GITHUB_TOKEN").map_err(|_| {
-            RhinolabsError::ConfigError("GITHUB_TOKEN environment variable not set".into())
-        })?;
-
         let url = format!(
             "https://api.github.com/repos/{}/{}/releases",
             config.github.owner, config.github.repo
@@ -285,22 +276,8 @@ impl Project {
         });
 
         let client = reqwest::Client::new();
-        let response = client
-            .post(&url)
-            .header("User-Agent", "rhinolabs-gui")
-            .header("Authorization", format!("Bearer {}", token))
-            .header("Accept", "application/vnd.github+json")
-            .json(&body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(RhinolabsError::Other(format!(
-                "Failed to create release: {}",
-                error_text
-            )));
-        }
+        let response = GitHub::post(&client, &url)?.json(&body).send().await?;
+        let response = GitHub::check(response, "Failed to create release").await?;
 
         let release: serde_json::Value = response.json().await?;
         let release_url = release["html_url"].as_str().unwrap_or("").to_string();
@@ -308,6 +285,32 @@ impl Project {
         Ok(release_url)
     }
 
+    /// Promote a plugin release to a more stable channel (e.g. `v1.2.0-beta` -> `v1.2.0`)
+    /// Returns the URL of the promoted release
+    pub async fn promote_release(
+        version: &str,
+        from: ReleaseChannel,
+        to: ReleaseChannel,
+    ) -> Result<String> {
+        let config = Self::get_config()?;
+
+        if config.github.owner.is_empty() || config.github.repo.is_empty() {
+            return Err(RhinolabsError::ConfigError(
+                "GitHub repository not configured".into(),
+            ));
+        }
+
+        Channels::promote_release(
+            &config.github.owner,
+            &config.github.repo,
+            "v",
+            version,
+            from,
+            to,
+        )
+        .await
+    }
+
     /// Bump version in plugin.json
     pub fn bump_version(bump_type: &str) -> Result<String> {
         let plugin_dir = Paths::plugin_dir()?;
@@ -0,0 +1,302 @@
+//! Remote skill catalog module
+//!
+//! This module handles:
+//! - Keeping the last fetched catalog of every skill source on disk,
+//!   keyed by source and ref
+//! - Deciding when a catalog is stale (TTL per kind of source)
+//! - Serving those catalogs without network access (search, offline browsing)
+
+use crate::skill_sources::SourceKind;
+use crate::{Paths, Result, SkillSource};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+/// How long a catalog is used before the source is fetched again.
+/// Remote requests are revalidated with ETags, so refetching an
+/// unchanged GitHub source costs little; local sources are always rescanned.
+const GITHUB_TTL_MINUTES: i64 = 60;
+const GIT_TTL_MINUTES: i64 = 60;
+const ARCHIVE_TTL_MINUTES: i64 = 24 * 60;
+
+// ============================================
+// Types
+// ============================================
+
+/// A skill listed by a source, as last fetched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogSkill {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub url: String,
+    /// SKILL.md without its frontmatter
+    #[serde(default)]
+    pub body: String,
+}
+
+/// Every skill of one source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceCatalog {
+    pub source_id: String,
+    pub source_name: String,
+    pub source_url: String,
+    /// Hash of the URL, schema and layout the catalog was listed with
+    #[serde(default)]
+    pub source_key: String,
+    /// Ref configured on the source when fetched (None = default branch)
+    #[serde(default)]
+    pub git_ref: Option<String>,
+    /// Ref the catalog was listed at
+    #[serde(default)]
+    pub resolved_ref: String,
+    /// RFC 3339 time of the fetch
+    pub fetched_at: String,
+    pub skills: Vec<CatalogSkill>,
+}
+
+// ============================================
+// Catalog Store
+// ============================================
+
+pub struct SkillCatalog;
+
+impl SkillCatalog {
+    fn catalogs_dir() -> Result<PathBuf> {
+        Ok(Paths::rhinolabs_config_dir()?
+            .join("cache")
+            .join("catalogs"))
+    }
+
+    /// `<source>@<ref>.json`, so switching a source between refs keeps
+    /// both catalogs
+    fn catalog_path(source: &SkillSource) -> Result<PathBuf> {
+        let git_ref: String = source
+            .git_ref
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .unwrap_or("default")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        Ok(Self::catalogs_dir()?.join(format!("{}@{}.json", source.id, git_ref)))
+    }
+
+    /// Replace the stored catalog of a source
+    pub fn save(
+        source: &SkillSource,
+        resolved_ref: &str,
+        skills: Vec<CatalogSkill>,
+    ) -> Result<SourceCatalog> {
+        let catalog = SourceCatalog {
+            source_id: source.id.clone(),
+            source_name: source.name.clone(),
+            source_url: source.url.clone(),
+            source_key: Self::source_key(source),
+            git_ref: source.git_ref.clone(),
+            resolved_ref: resolved_ref.to_string(),
+            fetched_at: Utc::now().to_rfc3339(),
+            skills,
+        };
+
+        let path = Self::catalog_path(source)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string(&catalog)?)?;
+        Ok(catalog)
+    }
+
+    /// Stored catalog of a source at its configured ref, fresh or not
+    /// A catalog listed from another URL or with another layout is ignored
+    pub fn load(source: &SkillSource) -> Result<Option<SourceCatalog>> {
+        let path = Self::catalog_path(source)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let catalog: SourceCatalog = match serde_json::from_str(&fs::read_to_string(&path)?) {
+            Ok(catalog) => catalog,
+            // A corrupt cache is refetched, not an error
+            Err(_) => return Ok(None),
+        };
+        Ok((catalog.source_key == Self::source_key(source)).then_some(catalog))
+    }
+
+    /// What a catalog depends on besides the ref
+    fn source_key(source: &SkillSource) -> String {
+        let key = serde_json::json!([source.url, source.schema, source.layout]).to_string();
+        format!("{:x}", Sha256::digest(key))[..16].to_string()
+    }
+
+    /// How long a catalog of this source stays fresh
+    pub fn ttl(source: &SkillSource) -> Duration {
+        match SourceKind::detect(&source.url) {
+            SourceKind::GitHub => Duration::minutes(GITHUB_TTL_MINUTES),
+            SourceKind::Git => Duration::minutes(GIT_TTL_MINUTES),
+            SourceKind::Archive => Duration::minutes(ARCHIVE_TTL_MINUTES),
+            SourceKind::Local => Duration::zero(),
+        }
+    }
+
+    /// Whether a catalog can be used without fetching the source again
+    pub fn is_fresh(catalog: &SourceCatalog, source: &SkillSource) -> bool {
+        DateTime::parse_from_rfc3339(&catalog.fetched_at)
+            .map(|fetched_at| Utc::now() - fetched_at.with_timezone(&Utc) < Self::ttl(source))
+            .unwrap_or(false)
+    }
+
+    /// Stored catalogs of the given sources
+    pub fn load_all(sources: &[SkillSource]) -> Result<Vec<SourceCatalog>> {
+        let mut catalogs = Vec::new();
+        for source in sources {
+            if let Some(catalog) = Self::load(source)? {
+                catalogs.push(catalog);
+            }
+        }
+        Ok(catalogs)
+    }
+
+    /// Drop every stored catalog of a source (all refs)
+    pub fn remove(source_id: &str) -> Result<()> {
+        let dir = Self::catalogs_dir()?;
+        if !dir.exists() {
+            return Ok(());
+        }
+
+        let prefix = format!("{}@", source_id);
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name.starts_with(&prefix) && name.ends_with(".json") {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestEnv, ENV_MUTEX};
+    use crate::SkillSourceType;
+
+    fn source(url: &str) -> SkillSource {
+        SkillSource {
+            id: "community".into(),
+            name: "Community".into(),
+            source_type: SkillSourceType::Community,
+            url: url.into(),
+            description: String::new(),
+            enabled: true,
+            fetchable: true,
+            schema: Default::default(),
+            git_ref: None,
+            layout: None,
+            skill_count: None,
+        }
+    }
+
+    #[test]
+    fn test_catalog_roundtrip_and_url_change() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let _env = TestEnv::new();
+
+        let github = source("https://github.com/acme/skills");
+        assert!(SkillCatalog::load(&github).unwrap().is_none());
+
+        SkillCatalog::save(
+            &github,
+            "main",
+            vec![CatalogSkill {
+                id: "docker".into(),
+                name: "Docker".into(),
+                description: "Containers".into(),
+                tags: vec!["devops".into()],
+                url: "https://example.com/SKILL.md".into(),
+                body: "# Docker".into(),
+            }],
+        )
+        .unwrap();
+
+        let catalog = SkillCatalog::load(&github).unwrap().unwrap();
+        assert_eq!(catalog.skills.len(), 1);
+        assert_eq!(catalog.skills[0].tags, vec!["devops"]);
+        assert_eq!(catalog.resolved_ref, "main");
+
+        // Catalogs are kept per ref
+        let pinned = SkillSource {
+            git_ref: Some("v1.0".into()),
+            ..github.clone()
+        };
+        assert!(SkillCatalog::load(&pinned).unwrap().is_none());
+        SkillCatalog::save(&pinned, "v1.0", vec![]).unwrap();
+        assert!(SkillCatalog::load(&pinned)
+            .unwrap()
+            .unwrap()
+            .skills
+            .is_empty());
+        assert_eq!(
+            SkillCatalog::load(&github).unwrap().unwrap().skills.len(),
+            1
+        );
+
+        // Pointing the source elsewhere or changing its layout invalidates the catalog
+        let moved = source("https://github.com/acme/other-skills");
+        assert!(SkillCatalog::load(&moved).unwrap().is_none());
+        let custom = SkillSource {
+            schema: crate::SkillSchema::Custom,
+            ..github.clone()
+        };
+        assert!(SkillCatalog::load(&custom).unwrap().is_none());
+
+        SkillCatalog::remove("community").unwrap();
+        assert!(SkillCatalog::load(&github).unwrap().is_none());
+        assert!(SkillCatalog::load(&pinned).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_catalog_freshness_depends_on_source_kind() {
+        let github = source("https://github.com/acme/skills");
+        let local = source("/srv/skills");
+        let catalog = |age: Duration| SourceCatalog {
+            source_id: "community".into(),
+            source_name: "Community".into(),
+            source_url: String::new(),
+            source_key: String::new(),
+            git_ref: None,
+            resolved_ref: "main".into(),
+            fetched_at: (Utc::now() - age).to_rfc3339(),
+            skills: vec![],
+        };
+
+        assert!(SkillCatalog::is_fresh(
+            &catalog(Duration::minutes(5)),
+            &github
+        ));
+        assert!(!SkillCatalog::is_fresh(
+            &catalog(Duration::hours(2)),
+            &github
+        ));
+        // Local directories are cheap to rescan
+        assert!(!SkillCatalog::is_fresh(&catalog(Duration::zero()), &local));
+
+        let mut corrupt = catalog(Duration::zero());
+        corrupt.fetched_at = "yesterday".into();
+        assert!(!SkillCatalog::is_fresh(&corrupt, &github));
+    }
+}
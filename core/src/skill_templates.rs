@@ -0,0 +1,469 @@
+//! Skill templates module
+//!
+//! This module handles:
+//! - Built-in templates scaffolding a skill's directory tree
+//! - User templates in `<config dir>/skill-templates/<id>/`, with an
+//!   optional `template.json` naming them and declaring their variables
+//! - `{{variable}}` substitution in file contents and paths
+//!
+//! Creating a skill from a template is done by `Skills::create_from_template`.
+
+use crate::skill_package::SkillPackage;
+use crate::{Paths, Result, RhinolabsError, Skills};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Template used when none is given
+pub const DEFAULT_TEMPLATE: &str = "basic";
+
+/// Variables every template can use; set by `Skills::create_from_template`
+pub const BUILTIN_VARIABLES: &[&str] = &[
+    "id",
+    "name",
+    "description",
+    "category",
+    "date",
+    "precedence",
+];
+
+/// Manifest file of a user template (not copied into the skill)
+const TEMPLATE_MANIFEST: &str = "template.json";
+
+// ============================================
+// Types
+// ============================================
+
+/// A variable a template expects besides the built-in ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateVariable {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Value used when none is given; variables without one are required
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillTemplate {
+    /// Directory name of user templates
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
+    /// Shipped with rhinolabs-ai (user templates with the same id replace it)
+    #[serde(default)]
+    pub builtin: bool,
+    /// Directory of a user template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+// ============================================
+// Built-in Templates
+// ============================================
+
+const BASIC_SKILL: &str = "---
+name: {{name}}
+description: >
+  {{description}}
+---
+
+# {{name}}
+
+Your skill instructions here.
+";
+
+const STANDARD_SKILL: &str = "---
+name: {{name}}
+description: >
+  {{description}}
+---
+
+## Precedence
+
+This skill defers to:
+{{precedence}}
+
+## When to Use
+
+Use this skill when:
+- {Condition 1}
+- {Condition 2}
+
+## Critical Patterns
+
+{The most important rules - what the AI MUST follow}
+
+## Code Examples
+
+```
+{Minimal, focused example}
+```
+
+## Resources
+
+- [References](references/README.md)
+";
+
+const FULL_SKILL: &str = "---
+name: {{name}}
+description: >
+  {{description}}
+version: \"0.1.0\"
+---
+
+## Precedence
+
+This skill defers to:
+{{precedence}}
+
+## When to Use
+
+Use this skill when:
+- {Condition 1}
+- {Condition 2}
+
+## Critical Patterns
+
+{The most important rules - what the AI MUST follow}
+
+## Decision Tree
+
+```
+{Question 1}? -> {Action A}
+Otherwise     -> {Default action}
+```
+
+## Code Examples
+
+See [examples](examples/README.md).
+
+## Commands
+
+See [scripts](scripts/README.md).
+
+## Resources
+
+- [References](references/README.md) - documentation this skill relies on
+- [Assets](assets/README.md) - templates and schemas
+";
+
+const REFERENCES_README: &str = "# {{name}} references
+
+List the documentation this skill relies on, one link per line,
+with what each document covers.
+";
+
+const SCRIPTS_README: &str = "# {{name}} scripts
+
+Helper scripts the skill tells the AI to run. Keep them small and
+document what each one does.
+";
+
+const ASSETS_README: &str = "# {{name}} assets
+
+Templates, schemas and other files the skill refers to.
+";
+
+const EXAMPLES_README: &str = "# {{name}} examples
+
+Minimal, focused examples of the patterns in SKILL.md.
+";
+
+/// (relative path, content) of a built-in template's files
+type TemplateFiles = &'static [(&'static str, &'static str)];
+
+/// (id, name, description, files)
+const BUILTIN_TEMPLATES: &[(&str, &str, &str, TemplateFiles)] = &[
+    (
+        "basic",
+        "Basic",
+        "A single SKILL.md",
+        &[("SKILL.md", BASIC_SKILL)],
+    ),
+    (
+        "standard",
+        "Standard",
+        "SKILL.md with the guideline sections and a references/ folder",
+        &[
+            ("SKILL.md", STANDARD_SKILL),
+            ("references/README.md", REFERENCES_README),
+        ],
+    ),
+    (
+        "full",
+        "Full",
+        "The skill-creator structure: references/, scripts/, assets/ and examples/",
+        &[
+            ("SKILL.md", FULL_SKILL),
+            ("references/README.md", REFERENCES_README),
+            ("scripts/README.md", SCRIPTS_README),
+            ("assets/README.md", ASSETS_README),
+            ("examples/README.md", EXAMPLES_README),
+        ],
+    ),
+];
+
+// ============================================
+// Templates
+// ============================================
+
+pub struct SkillTemplates;
+
+impl SkillTemplates {
+    /// Directory of user templates: `<config dir>/skill-templates/`
+    pub fn templates_dir() -> Result<PathBuf> {
+        Ok(Paths::rhinolabs_config_dir()?.join("skill-templates"))
+    }
+
+    /// Built-in and user templates, built-ins first
+    pub fn list() -> Result<Vec<SkillTemplate>> {
+        let mut templates: Vec<SkillTemplate> = BUILTIN_TEMPLATES
+            .iter()
+            .map(|(id, name, description, _)| SkillTemplate {
+                id: id.to_string(),
+                name: name.to_string(),
+                description: description.to_string(),
+                variables: Vec::new(),
+                builtin: true,
+                path: None,
+            })
+            .collect();
+
+        for template in Self::user_templates()? {
+            match templates.iter_mut().find(|t| t.id == template.id) {
+                Some(builtin) => *builtin = template,
+                None => templates.push(template),
+            }
+        }
+        Ok(templates)
+    }
+
+    pub fn get(id: &str) -> Result<Option<SkillTemplate>> {
+        Ok(Self::list()?.into_iter().find(|t| t.id == id))
+    }
+
+    /// User templates, sorted by id
+    fn user_templates() -> Result<Vec<SkillTemplate>> {
+        let dir = Self::templates_dir()?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut templates = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let id = entry.file_name().to_string_lossy().to_string();
+            if !entry.file_type()?.is_dir() || id.starts_with('.') {
+                continue;
+            }
+
+            let manifest = entry.path().join(TEMPLATE_MANIFEST);
+            let mut template: SkillTemplate = if manifest.exists() {
+                serde_json::from_str(&fs::read_to_string(&manifest)?).map_err(|e| {
+                    RhinolabsError::ConfigError(format!("Invalid {}: {}", manifest.display(), e))
+                })?
+            } else {
+                SkillTemplate::default()
+            };
+            if template.name.is_empty() {
+                template.name = id.clone();
+            }
+            template.id = id;
+            template.builtin = false;
+            template.path = Some(entry.path().display().to_string());
+            templates.push(template);
+        }
+
+        templates.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(templates)
+    }
+
+    /// The files of a template, before substitution
+    fn files(template: &SkillTemplate) -> Result<BTreeMap<String, Vec<u8>>> {
+        match &template.path {
+            Some(path) => {
+                let mut files = Skills::read_tree(std::path::Path::new(path))?;
+                files.remove(TEMPLATE_MANIFEST);
+                Ok(files)
+            }
+            None => Ok(BUILTIN_TEMPLATES
+                .iter()
+                .find(|(id, ..)| *id == template.id)
+                .map(|(.., files)| {
+                    files
+                        .iter()
+                        .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()))
+                        .collect()
+                })
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Render a template: substitute the variables in every path and text
+    /// file. Declared variables fall back to their default; missing ones
+    /// without a default are an error.
+    pub fn render(
+        id: &str,
+        variables: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, Vec<u8>>> {
+        let template = Self::get(id)?.ok_or_else(|| {
+            RhinolabsError::ConfigError(format!("Skill template '{}' not found", id))
+        })?;
+
+        let mut variables = variables.clone();
+        let mut missing = Vec::new();
+        for variable in &template.variables {
+            if variables.contains_key(&variable.name) {
+                continue;
+            }
+            match &variable.default {
+                Some(default) => {
+                    let default = Self::substitute(default, &variables);
+                    variables.insert(variable.name.clone(), default);
+                }
+                None => missing.push(variable.name.as_str()),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(RhinolabsError::ConfigError(format!(
+                "Skill template '{}' needs a value for: {}",
+                id,
+                missing.join(", ")
+            )));
+        }
+
+        let mut rendered = BTreeMap::new();
+        for (path, content) in Self::files(&template)? {
+            let path = Self::substitute(&path, &variables);
+            if !SkillPackage::is_safe_path(&path) {
+                return Err(RhinolabsError::ConfigError(format!(
+                    "Skill template '{}' renders the path '{}', which is outside the skill",
+                    id, path
+                )));
+            }
+            // Binary files are copied as they are
+            let content = match String::from_utf8(content) {
+                Ok(text) => Self::substitute(&text, &variables).into_bytes(),
+                Err(e) => e.into_bytes(),
+            };
+            rendered.insert(path, content);
+        }
+
+        if !rendered.contains_key("SKILL.md") {
+            return Err(RhinolabsError::ConfigError(format!(
+                "Skill template '{}' has no SKILL.md",
+                id
+            )));
+        }
+        Ok(rendered)
+    }
+
+    /// Replace `{{ name }}` placeholders of known variables; others are kept,
+    /// so braces in code examples survive
+    pub fn substitute(text: &str, variables: &BTreeMap<String, String>) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            let key = rest[start + 2..start + 2 + len].trim();
+            result.push_str(&rest[..start]);
+            match variables.get(key) {
+                Some(value) => result.push_str(value),
+                None => result.push_str(&rest[start..start + len + 4]),
+            }
+            rest = &rest[start + len + 4..];
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestEnv, ENV_MUTEX};
+
+    fn variables(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_substitute() {
+        let vars = variables(&[("name", "Docker"), ("id", "docker")]);
+        assert_eq!(
+            SkillTemplates::substitute("# {{name}} ({{ id }})", &vars),
+            "# Docker (docker)"
+        );
+        // Unknown placeholders and unclosed braces are kept
+        assert_eq!(
+            SkillTemplates::substitute("{{ props.value }} and {{name", &vars),
+            "{{ props.value }} and {{name"
+        );
+    }
+
+    #[test]
+    fn test_user_templates_render_with_variables() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let _env = TestEnv::new();
+
+        let dir = SkillTemplates::templates_dir().unwrap().join("api");
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(
+            dir.join(TEMPLATE_MANIFEST),
+            r#"{"name": "API client", "variables": [
+                {"name": "language", "default": "typescript"},
+                {"name": "base_url"}
+            ]}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: {{name}}\ndescription: {{description}}\n---\n\nCall {{base_url}} from {{language}}.\n",
+        )
+        .unwrap();
+        fs::write(dir.join("scripts").join("{{id}}.sh"), "echo {{id}}\n").unwrap();
+
+        let templates = SkillTemplates::list().unwrap();
+        let ids: Vec<&str> = templates.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["basic", "standard", "full", "api"]);
+        assert_eq!(templates[3].name, "API client");
+
+        let mut vars = variables(&[
+            ("id", "billing"),
+            ("name", "billing"),
+            ("description", "Billing API"),
+        ]);
+        let error = SkillTemplates::render("api", &vars).unwrap_err();
+        assert!(error.to_string().contains("needs a value for: base_url"));
+
+        vars.insert("base_url".into(), "https://api.example.com".into());
+        let files = SkillTemplates::render("api", &vars).unwrap();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec!["SKILL.md", "scripts/billing.sh"]
+        );
+        assert!(String::from_utf8_lossy(&files["SKILL.md"])
+            .contains("Call https://api.example.com from typescript."));
+
+        // Variables can't move files outside the skill
+        vars.insert("id".into(), "../evil".into());
+        assert!(SkillTemplates::render("api", &vars).is_err());
+
+        assert!(SkillTemplates::render("missing", &vars).is_err());
+    }
+}
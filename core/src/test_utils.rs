@@ -1,5 +1,5 @@
 #[cfg(test)]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 /// Test utilities for managing the test environment
 ///
 /// Since multiple modules use the RHINOLABS_DEV_PATH environment variable,
@@ -17,11 +17,13 @@ use tempfile::TempDir;
 pub static ENV_MUTEX: Mutex<()> = Mutex::new(());
 
 /// Helper struct to manage test environment with a temp directory.
-/// Sets RHINOLABS_DEV_PATH on creation and restores original value on drop.
+/// Sets RHINOLABS_DEV_PATH (plugin dir) and RHINOLABS_CONFIG_PATH (config dir)
+/// on creation and restores the original values on drop.
 #[cfg(test)]
 pub struct TestEnv {
     pub temp_dir: TempDir,
     original_env: Option<String>,
+    original_config_env: Option<String>,
 }
 
 #[cfg(test)]
@@ -31,10 +33,17 @@ impl TestEnv {
     pub fn new() -> Self {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
         let original_env = std::env::var("RHINOLABS_DEV_PATH").ok();
+        let original_config_env = std::env::var("RHINOLABS_CONFIG_PATH").ok();
         std::env::set_var("RHINOLABS_DEV_PATH", temp_dir.path());
+        // Paths::rhinolabs_config_dir() uses the parent of this file path
+        std::env::set_var(
+            "RHINOLABS_CONFIG_PATH",
+            temp_dir.path().join("config").join("profiles.json"),
+        );
         TestEnv {
             temp_dir,
             original_env,
+            original_config_env,
         }
     }
 
@@ -42,6 +51,18 @@ impl TestEnv {
     pub fn plugin_dir(&self) -> PathBuf {
         self.temp_dir.path().to_path_buf()
     }
+
+    /// Get the rhinolabs config directory path
+    pub fn config_dir(&self) -> PathBuf {
+        self.temp_dir.path().join("config")
+    }
+
+    /// Write a skill's SKILL.md and return the skill directory
+    pub fn write_skill(&self, id: &str, content: &str) -> PathBuf {
+        let skills_dir = self.plugin_dir().join("skills");
+        write_files(&skills_dir, &[(&format!("{}/SKILL.md", id), content)]);
+        skills_dir.join(id)
+    }
 }
 
 #[cfg(test)]
@@ -51,5 +72,28 @@ impl Drop for TestEnv {
             Some(val) => std::env::set_var("RHINOLABS_DEV_PATH", val),
             None => std::env::remove_var("RHINOLABS_DEV_PATH"),
         }
+        match &self.original_config_env {
+            Some(val) => std::env::set_var("RHINOLABS_CONFIG_PATH", val),
+            None => std::env::remove_var("RHINOLABS_CONFIG_PATH"),
+        }
+    }
+}
+
+/// Write `(relative path, content)` pairs under `root`, creating parent directories
+#[cfg(test)]
+pub fn write_files(root: &Path, files: &[(&str, &str)]) {
+    for (path, content) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
     }
 }
+
+/// Minimal SKILL.md with the given name
+#[cfg(test)]
+pub fn skill_md(name: &str) -> String {
+    format!(
+        "---\nname: {}\ndescription: Test skill\n---\n\n# {}\n",
+        name, name
+    )
+}
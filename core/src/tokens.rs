@@ -0,0 +1,299 @@
+//! Token estimation module
+//!
+//! This module handles:
+//! - Approximating token counts offline, without a tokenizer model
+//! - Sizing skills (SKILL.md plus the files it links), output styles and
+//!   the generated CLAUDE.md/AGENTS.md
+//! - Per-profile totals checked against the profile's token budget
+
+use crate::skill_lint::{escapes_dir, link_path, relative_links};
+use crate::{OutputStyle, Profile, Profiles, Result, RhinolabsError, SkillGraph, Skills};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Budget for profiles that don't set `tokenBudget`
+pub const DEFAULT_TOKEN_BUDGET: usize = 30_000;
+
+// ============================================
+// Types
+// ============================================
+
+/// Estimated size of one skill, output style or instruction file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenEstimate {
+    pub id: String,
+    pub tokens: usize,
+    /// Files counted, relative to the skill directory for skills
+    pub files: Vec<String>,
+}
+
+/// Estimated context a profile adds to every session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileTokenReport {
+    pub profile_id: String,
+    /// Skills of the profile, including the dependencies it pulls in
+    pub skills: Vec<TokenEstimate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_style: Option<TokenEstimate>,
+    /// CLAUDE.md and AGENTS.md
+    pub instructions: Vec<TokenEstimate>,
+    pub total: usize,
+    pub budget: usize,
+    pub over_budget: bool,
+}
+
+// ============================================
+// Estimator
+// ============================================
+
+pub struct TokenEstimator;
+
+impl TokenEstimator {
+    /// Approximate the number of tokens in `text`.
+    ///
+    /// Follows how BPE tokenizers split English and code: runs of letters and
+    /// digits take about one token per four characters, runs of punctuation
+    /// one per two, and other scripts one per character. Whitespace is folded
+    /// into the neighbouring tokens. Expect it to be within ~15% of Claude's
+    /// count for markdown.
+    pub fn estimate(text: &str) -> usize {
+        let mut tokens = 0;
+        let mut word: usize = 0;
+        let mut symbols: usize = 0;
+
+        for c in text.chars() {
+            if c.is_ascii_alphanumeric() {
+                tokens += symbols.div_ceil(2);
+                symbols = 0;
+                word += 1;
+            } else if c.is_whitespace() {
+                tokens += word.div_ceil(4) + symbols.div_ceil(2);
+                word = 0;
+                symbols = 0;
+            } else if c.is_ascii() {
+                tokens += word.div_ceil(4);
+                word = 0;
+                symbols += 1;
+            } else {
+                tokens += word.div_ceil(4) + symbols.div_ceil(2) + 1;
+                word = 0;
+                symbols = 0;
+            }
+        }
+
+        tokens + word.div_ceil(4) + symbols.div_ceil(2)
+    }
+
+    /// SKILL.md plus the supporting files it links to
+    pub fn skill(skill_id: &str) -> Result<TokenEstimate> {
+        let dir = Skills::get_skill_path(skill_id)?;
+        let content = fs::read_to_string(dir.join("SKILL.md"))?;
+
+        let mut tokens = Self::estimate(&content);
+        let mut files = vec!["SKILL.md".to_string()];
+
+        for (_, target) in relative_links(&content) {
+            let path = link_path(&target).trim_start_matches("./");
+            let resolved = dir.join(path);
+            if path.is_empty()
+                || files.iter().any(|f| f == path)
+                || escapes_dir(&resolved, &dir)
+                || !resolved.is_file()
+            {
+                continue;
+            }
+            // Binary files aren't read into the context
+            if let Ok(text) = fs::read_to_string(&resolved) {
+                tokens += Self::estimate(&text);
+                files.push(path.to_string());
+            }
+        }
+
+        Ok(TokenEstimate {
+            id: skill_id.to_string(),
+            tokens,
+            files,
+        })
+    }
+
+    /// Every installed skill
+    pub fn skills() -> Result<Vec<TokenEstimate>> {
+        Skills::list()?
+            .iter()
+            .map(|skill| Self::skill(&skill.id))
+            .collect()
+    }
+
+    pub fn output_style(style: &OutputStyle) -> TokenEstimate {
+        TokenEstimate {
+            id: style.id.clone(),
+            tokens: Self::estimate(&style.content),
+            files: vec![format!("{}.md", style.id)],
+        }
+    }
+
+    /// Skills, output style and instruction files of a profile against its budget
+    pub fn profile(profile_id: &str) -> Result<ProfileTokenReport> {
+        let profile = Profiles::get(profile_id)?.ok_or_else(|| {
+            RhinolabsError::ConfigError(format!("Profile '{}' not found", profile_id))
+        })?;
+        Self::profile_report(&profile)
+    }
+
+    /// Report for every profile
+    pub fn profiles() -> Result<Vec<ProfileTokenReport>> {
+        Profiles::list()?.iter().map(Self::profile_report).collect()
+    }
+
+    fn profile_report(profile: &Profile) -> Result<ProfileTokenReport> {
+        // Same skill set the install copies; missing dependencies are left out
+        let skill_ids = SkillGraph::load()?
+            .resolve(&profile.skills)
+            .unwrap_or_else(|_| profile.skills.clone());
+        let skills: Vec<TokenEstimate> = skill_ids
+            .iter()
+            .filter_map(|id| Self::skill(id).ok())
+            .collect();
+
+        let output_style = Profiles::output_style(profile)?.map(|style| Self::output_style(&style));
+
+        let instructions: Vec<TokenEstimate> = Profiles::context_files(profile)?
+            .into_iter()
+            .map(|(file, content)| TokenEstimate {
+                id: file.clone(),
+                tokens: Self::estimate(&content),
+                files: vec![file],
+            })
+            .collect();
+
+        let total = skills
+            .iter()
+            .chain(output_style.iter())
+            .chain(instructions.iter())
+            .map(|e| e.tokens)
+            .sum();
+        let budget = profile.token_budget.unwrap_or(DEFAULT_TOKEN_BUDGET);
+
+        Ok(ProfileTokenReport {
+            profile_id: profile.id.clone(),
+            skills,
+            output_style,
+            instructions,
+            total,
+            budget,
+            over_budget: total > budget,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{skill_md, write_files, TestEnv, ENV_MUTEX};
+    use crate::{CreateProfileInput, ProfileType, UpdateProfileInput};
+
+    #[test]
+    fn test_estimate() {
+        assert_eq!(TokenEstimator::estimate(""), 0);
+        assert_eq!(TokenEstimator::estimate("the cat sat"), 3);
+        // Long words and symbol runs take several tokens
+        assert_eq!(TokenEstimator::estimate("internationalization"), 5);
+        assert_eq!(TokenEstimator::estimate("## Title"), 3);
+        assert_eq!(TokenEstimator::estimate("fn(x)"), 4);
+        assert_eq!(TokenEstimator::estimate("日本語"), 3);
+
+        // Roughly 3-5 characters per token on English prose
+        let prose = "Use this skill when writing React components. It covers \
+                     composition, hooks patterns and prop design, and defers to \
+                     the corporate standards for everything else.";
+        let tokens = TokenEstimator::estimate(prose);
+        assert!(tokens > prose.len() / 5 && tokens < prose.len() / 3);
+    }
+
+    #[test]
+    fn test_skill_includes_linked_files() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        let dir = env.write_skill(
+            "react",
+            &format!(
+                "{}See [hooks](references/hooks.md), [again](./references/hooks.md#state), [missing](gone.md) and [docs](https://react.dev).",
+                skill_md("react")
+            ),
+        );
+        write_files(
+            &dir,
+            &[
+                ("references/hooks.md", &"word ".repeat(100)),
+                ("references/unlinked.md", &"word ".repeat(1000)),
+            ],
+        );
+
+        let estimate = TokenEstimator::skill("react").unwrap();
+        assert_eq!(estimate.files, vec!["SKILL.md", "references/hooks.md"]);
+        let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert_eq!(estimate.tokens, TokenEstimator::estimate(&content) + 100);
+    }
+
+    #[test]
+    fn test_profile_report_and_budget() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let env = TestEnv::new();
+        env.write_skill(
+            "big",
+            &format!("{}{}", skill_md("big"), "word ".repeat(2000)),
+        );
+        // Dependencies are counted like the install copies them
+        env.write_skill(
+            "small",
+            "---\nname: small\ndescription: Test skill\nrequires: big\n---\n\nA small skill.",
+        );
+
+        Profiles::create(CreateProfileInput {
+            id: "web".into(),
+            name: "Web".into(),
+            description: "Web projects".into(),
+            profile_type: ProfileType::Project,
+            skills: vec!["small".into()],
+            instructions: None,
+            generate_copilot: false,
+            generate_agents: true,
+            token_budget: None,
+            output_style: None,
+        })
+        .unwrap();
+
+        let report = TokenEstimator::profile("web").unwrap();
+        let ids: Vec<&str> = report.skills.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["small", "big"]);
+        let files: Vec<&str> = report.instructions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(files, vec!["CLAUDE.md", "AGENTS.md"]);
+        assert!(report.output_style.is_none());
+        assert_eq!(report.budget, DEFAULT_TOKEN_BUDGET);
+        assert!(report.total > 2000);
+        assert!(!report.over_budget);
+
+        let update = |budget| UpdateProfileInput {
+            name: None,
+            description: None,
+            profile_type: None,
+            instructions: None,
+            generate_copilot: None,
+            generate_agents: None,
+            token_budget: Some(budget),
+            output_style: None,
+        };
+        Profiles::update("web", update(1000)).unwrap();
+        let report = TokenEstimator::profile("web").unwrap();
+        assert_eq!(report.budget, 1000);
+        assert!(report.over_budget);
+
+        Profiles::update("web", update(0)).unwrap();
+        assert_eq!(
+            TokenEstimator::profile("web").unwrap().budget,
+            DEFAULT_TOKEN_BUDGET
+        );
+    }
+}
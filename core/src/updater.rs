@@ -1,12 +1,16 @@
-use crate::{Installer, Paths, Result, RhinolabsError, Version};
+use crate::{Channels, Installer, Paths, ReleaseChannel, Result, RhinolabsError, Version};
 
 pub struct Updater {
     dry_run: bool,
+    channel: Option<ReleaseChannel>,
 }
 
 impl Updater {
     pub fn new() -> Self {
-        Self { dry_run: false }
+        Self {
+            dry_run: false,
+            channel: None,
+        }
     }
 
     pub fn dry_run(mut self, enabled: bool) -> Self {
@@ -14,6 +18,12 @@ impl Updater {
         self
     }
 
+    /// Update from a release channel instead of this machine's channel
+    pub fn channel(mut self, channel: ReleaseChannel) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
     /// Update plugin to latest version
     pub async fn update(&self) -> Result<()> {
         // Check if plugin is installed
@@ -21,8 +31,13 @@ impl Updater {
             return Err(RhinolabsError::PluginNotInstalled);
         }
 
+        let channel = match self.channel {
+            Some(channel) => channel,
+            None => Channels::current()?,
+        };
+
         // Check for updates
-        let latest_version = Version::check_update().await?;
+        let latest_version = Version::check_update_for(channel).await?;
 
         if latest_version.is_none() {
             return Err(RhinolabsError::UpdateFailed(
@@ -42,7 +57,7 @@ impl Updater {
         self.backup_current()?;
 
         // Uninstall current version
-        let installer = Installer::new();
+        let installer = Installer::new().channel(channel);
         installer.uninstall()?;
 
         // Install latest version
@@ -97,6 +112,13 @@ mod tests {
     fn test_updater_default() {
         let updater: Updater = Default::default();
         assert!(!updater.dry_run);
+        assert!(updater.channel.is_none());
+    }
+
+    #[test]
+    fn test_updater_channel_override() {
+        let updater = Updater::new().channel(ReleaseChannel::Beta);
+        assert_eq!(updater.channel, Some(ReleaseChannel::Beta));
     }
 
     #[test]
@@ -1,10 +1,15 @@
-use crate::{Result, RhinolabsError};
+use crate::{Channels, GitHub, ReleaseChannel, Result, RhinolabsError};
 use semver::Version as SemVersion;
 use serde::{Deserialize, Serialize};
 use std::fs;
 
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// How to upgrade the CLI binary itself (`rhinolabs-ai update` only
+/// reinstalls the plugin)
+pub const CLI_UPGRADE_HINT: &str =
+    "Upgrade the CLI with 'brew upgrade rhinolabs-ai', or reinstall it from source";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Version {
     pub version: String,
@@ -38,22 +43,19 @@ impl Version {
         Ok(())
     }
 
-    /// Check if update is available
+    /// Check if update is available on this machine's release channel
     pub async fn check_update() -> Result<Option<String>> {
-        let url = "https://api.github.com/repos/rhinolabs/rhinolabs-ai/releases/latest";
-
-        let client = reqwest::Client::new();
-        let response = client
-            .get(url)
-            .header("User-Agent", "rhinolabs-cli")
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(RhinolabsError::Other("Failed to check for updates".into()));
-        }
+        Self::check_update_for(Channels::current()?).await
+    }
 
-        let release: serde_json::Value = response.json().await?;
+    /// Check if update is available on a release channel
+    pub async fn check_update_for(channel: ReleaseChannel) -> Result<Option<String>> {
+        let release = Self::fetch_channel_release(channel)
+            .await
+            .map_err(|e| match e {
+                RhinolabsError::GitHubAuth(_) | RhinolabsError::RateLimited(_) => e,
+                _ => RhinolabsError::Other("Failed to check for updates".into()),
+            })?;
         let latest_tag = release["tag_name"]
             .as_str()
             .ok_or_else(|| RhinolabsError::Other("Invalid release response".into()))?;
@@ -74,24 +76,19 @@ impl Version {
         }
     }
 
-    /// Get download URL for latest release
+    /// Get download URL for the latest release on this machine's channel
     pub async fn get_latest_download_url() -> Result<String> {
-        let url = "https://api.github.com/repos/rhinolabs/rhinolabs-ai/releases/latest";
-
-        let client = reqwest::Client::new();
-        let response = client
-            .get(url)
-            .header("User-Agent", "rhinolabs-cli")
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(RhinolabsError::DownloadFailed(
-                "Could not fetch release info".into(),
-            ));
-        }
+        Self::get_latest_download_url_for(Channels::current()?).await
+    }
 
-        let release: serde_json::Value = response.json().await?;
+    /// Get download URL for the latest release on a channel
+    pub async fn get_latest_download_url_for(channel: ReleaseChannel) -> Result<String> {
+        let release = Self::fetch_channel_release(channel)
+            .await
+            .map_err(|e| match e {
+                RhinolabsError::GitHubAuth(_) | RhinolabsError::RateLimited(_) => e,
+                _ => RhinolabsError::DownloadFailed("Could not fetch release info".into()),
+            })?;
         let assets = release["assets"]
             .as_array()
             .ok_or_else(|| RhinolabsError::DownloadFailed("No assets found".into()))?;
@@ -100,7 +97,11 @@ impl Version {
         for asset in assets {
             if let Some(name) = asset["name"].as_str() {
                 if name.starts_with("rhinolabs-claude") && name.ends_with(".zip") {
-                    if let Some(download_url) = asset["browser_download_url"].as_str() {
+                    // Prefer the API asset endpoint, which also works for private repos
+                    let url = asset["url"]
+                        .as_str()
+                        .or_else(|| asset["browser_download_url"].as_str());
+                    if let Some(download_url) = url {
                         return Ok(download_url.to_string());
                     }
                 }
@@ -111,6 +112,23 @@ impl Version {
             "Plugin asset not found in release".into(),
         ))
     }
+
+    /// Fetch the newest plugin release a channel accepts
+    /// Config releases (`config-v*`) share the repository and are skipped
+    async fn fetch_channel_release(channel: ReleaseChannel) -> Result<serde_json::Value> {
+        let url = "https://api.github.com/repos/rhinolabs/rhinolabs-ai/releases";
+
+        let client = reqwest::Client::new();
+        let response = GitHub::api(&client, url).send().await?;
+        let response = GitHub::check(response, "Failed to fetch releases").await?;
+
+        let releases: Vec<serde_json::Value> = response.json().await?;
+        Channels::select_release(&releases, channel, |tag| !tag.starts_with("config-"))
+            .cloned()
+            .ok_or_else(|| {
+                RhinolabsError::Other(format!("No release found on the {} channel", channel))
+            })
+    }
 }
 
 #[cfg(test)]
@@ -1,12 +1,16 @@
 use rhinolabs_core::diagnostics::DiagnosticReport;
 use rhinolabs_core::{
-    AutoInvokeRule, ConfigManifest, CreateProfileInput, CreateSkillInput, Deploy, DeployResult,
-    Doctor, Installer, Instructions, InstructionsManager, Manifest, McpConfig, McpConfigManager,
-    McpServer, McpSettings, McpSync, OutputStyle, OutputStyles, Paths, PermissionConfig,
-    PluginManifest, PluginSettings, Profile, ProfileInstallResult, Profiles, Project,
-    ProjectConfig, ProjectStatus, RemoteSkill, RemoteSkillFile, Settings, Skill, SkillCategory,
-    SkillSchema, SkillSource, SkillSourceType, Skills, StatusLineConfig, SyncResult,
-    UpdateProfileInput, UpdateSkillInput, Updater, Version,
+    AutoInvokeRule, Channels, ConfigDiff, ConfigManifest, ConfigSnapshot, CreateProfileInput,
+    CreateSkillInput, Deploy, DeployResult, Doctor, Installer, Instructions, InstructionsManager,
+    Manifest, Marketplace, MarketplaceCatalog, McpConfig, McpConfigManager, McpServer, McpSettings,
+    McpSync, OutputStyle, OutputStyles, Paths, PermissionConfig, PluginInstallOptions,
+    PluginInstallResult, PluginManifest, PluginReview, PluginSettings, Profile,
+    ProfileInstallResult, ProfileTokenReport, Profiles, Project, ProjectConfig, ProjectStatus,
+    ReleaseChannel, RemoteSkill, RemoteSkillFile, ScanPolicy, SearchOptions, SearchResult,
+    SecurityReport, Settings, Skill, SkillCategory, SkillFilter, SkillLayout, SkillRenameResult,
+    SkillSchema, SkillSource, SkillSourceType, SkillUpdateCheck, SkillUpgradeResult, Skills,
+    StatusLineConfig, SyncOptions, SyncResult, TokenEstimate, TokenEstimator, UpdateProfileInput,
+    UpdateSkillInput, Updater, Version,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -293,8 +297,8 @@ pub fn delete_output_style(id: String) -> Result<(), String> {
 // ============================================
 
 #[tauri::command]
-pub fn list_skills() -> Result<Vec<Skill>, String> {
-    Skills::list().map_err(|e| e.to_string())
+pub fn list_skills(filter: Option<SkillFilter>) -> Result<Vec<Skill>, String> {
+    Skills::list_filtered(&filter.unwrap_or_default()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -318,22 +322,56 @@ pub fn toggle_skill(id: String, enabled: bool) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn delete_skill(id: String) -> Result<(), String> {
-    Skills::delete(&id).map_err(|e| e.to_string())
+pub fn delete_skill(id: String, force: Option<bool>) -> Result<(), String> {
+    Skills::delete(&id, force.unwrap_or(false)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn rename_skill(id: String, new_id: String) -> Result<SkillRenameResult, String> {
+    Skills::rename(&id, &new_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn clone_skill(id: String, new_id: String) -> Result<Skill, String> {
+    Skills::clone(&id, &new_id).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub fn set_skill_category(skill_id: String, category: String) -> Result<(), String> {
-    let category_enum = match category.to_lowercase().as_str() {
-        "corporate" => SkillCategory::Corporate,
-        "frontend" => SkillCategory::Frontend,
-        "testing" => SkillCategory::Testing,
-        "aisdk" | "ai-sdk" => SkillCategory::AiSdk,
-        "utilities" => SkillCategory::Utilities,
-        _ => SkillCategory::Custom,
-    };
+    Skills::set_category(&skill_id, &category.to_lowercase()).map_err(|e| e.to_string())
+}
 
-    Skills::set_category(&skill_id, category_enum).map_err(|e| e.to_string())
+#[tauri::command]
+pub fn set_skill_tags(skill_id: String, tags: Vec<String>) -> Result<(), String> {
+    Skills::set_tags(&skill_id, &tags).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_skill_tags() -> Result<Vec<String>, String> {
+    Skills::list_tags().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn search_skills(
+    query: String,
+    options: Option<SearchOptions>,
+) -> Result<Vec<SearchResult>, String> {
+    Skills::search(&query, &options.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_skill_categories() -> Result<Vec<SkillCategory>, String> {
+    Skills::list_categories().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn save_skill_category(category: SkillCategory) -> Result<(), String> {
+    Skills::save_category(category).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn remove_skill_category(id: String) -> Result<(), String> {
+    Skills::remove_category(&id).map_err(|e| e.to_string())
 }
 
 // ============================================
@@ -357,6 +395,10 @@ pub struct AddSkillSourceInput {
     fetchable: bool,
     #[serde(default)]
     schema: String,
+    #[serde(default)]
+    git_ref: Option<String>,
+    #[serde(default)]
+    layout: Option<SkillLayout>,
 }
 
 #[tauri::command]
@@ -370,6 +412,7 @@ pub fn add_skill_source(input: AddSkillSourceInput) -> Result<(), String> {
 
     let schema = match input.schema.as_str() {
         "standard" => SkillSchema::Standard,
+        "marketplace" => SkillSchema::Marketplace,
         _ => SkillSchema::Custom,
     };
 
@@ -382,6 +425,8 @@ pub fn add_skill_source(input: AddSkillSourceInput) -> Result<(), String> {
         enabled: true,
         fetchable: input.fetchable,
         schema,
+        git_ref: input.git_ref.filter(|r| !r.trim().is_empty()),
+        layout: input.layout,
         skill_count: None,
     };
 
@@ -400,12 +445,23 @@ pub fn update_skill_source(
 ) -> Result<(), String> {
     let schema = schema.map(|s| match s.as_str() {
         "standard" => SkillSchema::Standard,
+        "marketplace" => SkillSchema::Marketplace,
         _ => SkillSchema::Custom,
     });
     Skills::update_source(&id, enabled, name, url, description, fetchable, schema)
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn set_skill_source_ref(id: String, git_ref: Option<String>) -> Result<(), String> {
+    Skills::set_source_ref(&id, git_ref).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_skill_source_layout(id: String, layout: Option<SkillLayout>) -> Result<(), String> {
+    Skills::set_source_layout(&id, layout).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn remove_skill_source(id: String) -> Result<(), String> {
     Skills::remove_source(&id).map_err(|e| e.to_string())
@@ -454,13 +510,70 @@ pub async fn install_skill_from_remote(
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn scan_remote_skill(
+    source_url: String,
+    skill_id: String,
+    source_id: String,
+) -> Result<SecurityReport, String> {
+    Skills::scan_remote(&source_url, &skill_id, &source_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn pack_skill(skill_id: String, dest: String) -> Result<String, String> {
+    Skills::pack(&skill_id, Path::new(&dest))
+        .map(|path| path.display().to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn scan_skill_package(path: String) -> Result<SecurityReport, String> {
+    Skills::scan_package(Path::new(&path)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn unpack_skill(path: String) -> Result<Skill, String> {
+    Skills::unpack(Path::new(&path)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn acknowledge_skill_risks(skill_id: String, fingerprint: String) -> Result<(), String> {
+    Skills::acknowledge_risks(&skill_id, &fingerprint).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_scan_policies() -> Result<HashMap<SkillSourceType, ScanPolicy>, String> {
+    [
+        SkillSourceType::Official,
+        SkillSourceType::Marketplace,
+        SkillSourceType::Community,
+        SkillSourceType::Local,
+    ]
+    .into_iter()
+    .map(|source_type| {
+        let policy = Skills::scan_policy(&source_type).map_err(|e| e.to_string())?;
+        Ok((source_type, policy))
+    })
+    .collect()
+}
+
+#[tauri::command]
+pub fn set_scan_policy(source_type: SkillSourceType, policy: ScanPolicy) -> Result<(), String> {
+    Skills::set_scan_policy(source_type, policy).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_installed_skill_ids() -> Result<Vec<String>, String> {
     Skills::installed_ids().map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn fetch_remote_skills(source_id: String) -> Result<Vec<RemoteSkill>, String> {
+pub async fn fetch_remote_skills(
+    source_id: String,
+    refresh: Option<bool>,
+) -> Result<Vec<RemoteSkill>, String> {
     let sources = Skills::list_sources().map_err(|e| e.to_string())?;
 
     let source = sources
@@ -468,7 +581,7 @@ pub async fn fetch_remote_skills(source_id: String) -> Result<Vec<RemoteSkill>,
         .find(|s| s.id == source_id)
         .ok_or_else(|| format!("Source '{}' not found", source_id))?;
 
-    Skills::fetch_from_github(&source)
+    Skills::fetch_from_source(&source, refresh.unwrap_or(false))
         .await
         .map_err(|e| e.to_string())
 }
@@ -485,17 +598,57 @@ pub async fn fetch_skill_content(url: String) -> Result<String, String> {
 pub struct FetchRemoteSkillFilesInput {
     source_url: String,
     skill_id: String,
+    #[serde(default)]
+    git_ref: Option<String>,
 }
 
 #[tauri::command]
 pub async fn fetch_remote_skill_files(
     input: FetchRemoteSkillFilesInput,
 ) -> Result<Vec<RemoteSkillFile>, String> {
-    Skills::fetch_remote_skill_files(&input.source_url, &input.skill_id)
+    Skills::fetch_remote_skill_files(&input.source_url, &input.skill_id, input.git_ref.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn list_marketplace_plugins(source_id: String) -> Result<MarketplaceCatalog, String> {
+    Marketplace::list(&source_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn review_marketplace_plugin(
+    source_id: String,
+    plugin: String,
+) -> Result<PluginReview, String> {
+    Marketplace::review_plugin(&source_id, &plugin)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn install_marketplace_plugin(
+    source_id: String,
+    plugin: String,
+    options: PluginInstallOptions,
+) -> Result<PluginInstallResult, String> {
+    Marketplace::install_plugin(&source_id, &plugin, &options)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_skill_updates() -> Result<SkillUpdateCheck, String> {
+    Skills::check_updates().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn upgrade_skill(id: String, force: bool) -> Result<SkillUpgradeResult, String> {
+    Skills::upgrade(&id, force).await.map_err(|e| e.to_string())
+}
+
 // ============================================
 // Instructions Commands
 // ============================================
@@ -766,6 +919,16 @@ pub fn update_profile(id: String, input: UpdateProfileInput) -> Result<Profile,
     Profiles::update(&id, input).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_profile_token_report(profile_id: String) -> Result<ProfileTokenReport, String> {
+    TokenEstimator::profile(&profile_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn estimate_skill_tokens() -> Result<Vec<TokenEstimate>, String> {
+    TokenEstimator::skills().map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn delete_profile(id: String) -> Result<(), String> {
     Profiles::delete(&id).map_err(|e| e.to_string())
@@ -847,13 +1010,72 @@ pub fn export_config(output_path: String) -> Result<(String, ConfigManifest), St
 }
 
 #[tauri::command]
-pub async fn deploy_config(version: String, changelog: String) -> Result<DeployResult, String> {
-    Deploy::deploy(&version, &changelog)
+pub async fn deploy_config(
+    version: String,
+    changelog: String,
+    channel: Option<ReleaseChannel>,
+) -> Result<DeployResult, String> {
+    Deploy::deploy_to_channel(&version, &changelog, channel.unwrap_or_default())
         .await
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn sync_config() -> Result<SyncResult, String> {
-    Deploy::sync().await.map_err(|e| e.to_string())
+pub async fn promote_config_release(
+    version: String,
+    from: ReleaseChannel,
+    to: ReleaseChannel,
+) -> Result<String, String> {
+    Deploy::promote(&version, from, to)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn promote_plugin_release(
+    version: String,
+    from: ReleaseChannel,
+    to: ReleaseChannel,
+) -> Result<String, String> {
+    Project::promote_release(&version, from, to)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ============================================
+// Release Channel Commands
+// ============================================
+
+#[tauri::command]
+pub fn get_release_channel() -> Result<ReleaseChannel, String> {
+    Channels::current().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_release_channel(channel: ReleaseChannel) -> Result<(), String> {
+    Channels::set(channel).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn sync_config(options: Option<SyncOptions>) -> Result<SyncResult, String> {
+    Deploy::sync_with_options(&options.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Diff a config bundle (or the latest release when omitted) against the local config
+#[tauri::command]
+pub async fn diff_config(bundle_path: Option<String>) -> Result<ConfigDiff, String> {
+    match bundle_path {
+        Some(path) => {
+            let old =
+                ConfigSnapshot::from_bundle_file(Path::new(&path)).map_err(|e| e.to_string())?;
+            let local = ConfigSnapshot::local().map_err(|e| e.to_string())?;
+            Ok(ConfigDiff::compare(&old, &local))
+        }
+        None => ConfigDiff::local_vs_latest()
+            .await
+            .map(|(_, diff)| diff)
+            .map_err(|e| e.to_string()),
+    }
 }
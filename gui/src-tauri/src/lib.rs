@@ -88,18 +88,40 @@ pub fn run() {
             update_skill,
             toggle_skill,
             delete_skill,
+            rename_skill,
+            clone_skill,
             set_skill_category,
+            set_skill_tags,
+            list_skill_tags,
+            search_skills,
+            list_skill_categories,
+            save_skill_category,
+            remove_skill_category,
             // Skill Sources
             list_skill_sources,
             add_skill_source,
             update_skill_source,
+            set_skill_source_ref,
+            set_skill_source_layout,
             remove_skill_source,
             install_skill_from_source,
             install_skill_from_remote,
+            scan_remote_skill,
+            pack_skill,
+            scan_skill_package,
+            unpack_skill,
+            acknowledge_skill_risks,
+            get_scan_policies,
+            set_scan_policy,
             get_installed_skill_ids,
             fetch_remote_skills,
             fetch_skill_content,
             fetch_remote_skill_files,
+            list_marketplace_plugins,
+            review_marketplace_plugin,
+            install_marketplace_plugin,
+            check_skill_updates,
+            upgrade_skill,
             // Instructions
             get_instructions,
             update_instructions,
@@ -125,6 +147,8 @@ pub fn run() {
             get_profile,
             create_profile,
             update_profile,
+            get_profile_token_report,
+            estimate_skill_tokens,
             delete_profile,
             assign_skills_to_profile,
             get_profile_skills,
@@ -140,7 +164,12 @@ pub fn run() {
             // Deploy & Sync
             export_config,
             deploy_config,
+            promote_config_release,
+            promote_plugin_release,
+            get_release_channel,
+            set_release_channel,
             sync_config,
+            diff_config,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
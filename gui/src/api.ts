@@ -12,8 +12,22 @@ import type {
   CreateSkillInput,
   UpdateSkillInput,
   SkillSource,
+  SkillUpdateCheck,
+  SkillUpgradeResult,
+  SkillRenameResult,
+  MarketplaceCatalog,
+  PluginInstallOptions,
+  PluginInstallResult,
+  PluginReview,
   SkillSourceType,
+  SkillCategory,
+  SkillFilter,
+  SearchOptions,
+  SearchResult,
+  ScanPolicy,
+  SecurityReport,
   SkillSchema,
+  SkillLayout,
   Instructions,
   DiagnosticReport,
   PermissionConfig,
@@ -28,9 +42,14 @@ import type {
   UpdateProfileInput,
   AutoInvokeRule,
   ProfileInstallResult,
+  ProfileTokenReport,
+  TokenEstimate,
   ConfigManifest,
   DeployResult,
   SyncResult,
+  SyncOptions,
+  ReleaseChannel,
+  ConfigDiff,
 } from './types';
 
 export const api = {
@@ -211,8 +230,8 @@ export const api = {
   // Skills
   // ============================================
 
-  listSkills(): Promise<Skill[]> {
-    return invoke('list_skills');
+  listSkills(filter?: SkillFilter): Promise<Skill[]> {
+    return invoke('list_skills', { filter: filter ?? null });
   },
 
   getSkill(id: string): Promise<Skill | null> {
@@ -231,14 +250,47 @@ export const api = {
     return invoke('toggle_skill', { id, enabled });
   },
 
-  deleteSkill(id: string): Promise<void> {
-    return invoke('delete_skill', { id });
+  deleteSkill(id: string, force = false): Promise<void> {
+    return invoke('delete_skill', { id, force });
+  },
+
+  renameSkill(id: string, newId: string): Promise<SkillRenameResult> {
+    return invoke('rename_skill', { id, newId });
+  },
+
+  cloneSkill(id: string, newId: string): Promise<Skill> {
+    return invoke('clone_skill', { id, newId });
   },
 
   setSkillCategory(skillId: string, category: string): Promise<void> {
     return invoke('set_skill_category', { skillId, category });
   },
 
+  setSkillTags(skillId: string, tags: string[]): Promise<void> {
+    return invoke('set_skill_tags', { skillId, tags });
+  },
+
+  listSkillTags(): Promise<string[]> {
+    return invoke('list_skill_tags');
+  },
+
+  /** Full-text search of installed skills and cached source catalogs (works offline) */
+  searchSkills(query: string, options?: SearchOptions): Promise<SearchResult[]> {
+    return invoke('search_skills', { query, options });
+  },
+
+  listSkillCategories(): Promise<SkillCategory[]> {
+    return invoke('list_skill_categories');
+  },
+
+  saveSkillCategory(category: SkillCategory): Promise<void> {
+    return invoke('save_skill_category', { category });
+  },
+
+  removeSkillCategory(id: string): Promise<void> {
+    return invoke('remove_skill_category', { id });
+  },
+
   // ============================================
   // Skill Sources
   // ============================================
@@ -255,6 +307,8 @@ export const api = {
     description: string;
     fetchable: boolean;
     schema: string;
+    gitRef?: string;
+    layout?: SkillLayout;
   }): Promise<void> {
     return invoke('add_skill_source', { input: source });
   },
@@ -273,6 +327,14 @@ export const api = {
     return invoke('update_skill_source', { id, ...updates });
   },
 
+  setSkillSourceRef(id: string, gitRef: string | null): Promise<void> {
+    return invoke('set_skill_source_ref', { id, gitRef });
+  },
+
+  setSkillSourceLayout(id: string, layout: SkillLayout | null): Promise<void> {
+    return invoke('set_skill_source_layout', { id, layout });
+  },
+
   removeSkillSource(id: string): Promise<void> {
     return invoke('remove_skill_source', { id });
   },
@@ -295,20 +357,77 @@ export const api = {
     return invoke('install_skill_from_remote', { input });
   },
 
+  scanRemoteSkill(sourceUrl: string, skillId: string, sourceId: string): Promise<SecurityReport> {
+    return invoke('scan_remote_skill', { sourceUrl, skillId, sourceId });
+  },
+
+  packSkill(skillId: string, dest: string): Promise<string> {
+    return invoke('pack_skill', { skillId, dest });
+  },
+
+  scanSkillPackage(path: string): Promise<SecurityReport> {
+    return invoke('scan_skill_package', { path });
+  },
+
+  unpackSkill(path: string): Promise<Skill> {
+    return invoke('unpack_skill', { path });
+  },
+
+  acknowledgeSkillRisks(skillId: string, fingerprint: string): Promise<void> {
+    return invoke('acknowledge_skill_risks', { skillId, fingerprint });
+  },
+
+  getScanPolicies(): Promise<Record<SkillSourceType, ScanPolicy>> {
+    return invoke('get_scan_policies');
+  },
+
+  setScanPolicy(sourceType: SkillSourceType, policy: ScanPolicy): Promise<void> {
+    return invoke('set_scan_policy', { sourceType, policy });
+  },
+
   getInstalledSkillIds(): Promise<string[]> {
     return invoke('get_installed_skill_ids');
   },
 
-  fetchRemoteSkills(sourceId: string): Promise<import('./types').RemoteSkill[]> {
-    return invoke('fetch_remote_skills', { sourceId });
+  /** Served from the catalog cache while fresh; `refresh` refetches the source */
+  fetchRemoteSkills(sourceId: string, refresh = false): Promise<import('./types').RemoteSkill[]> {
+    return invoke('fetch_remote_skills', { sourceId, refresh });
   },
 
   fetchSkillContent(url: string): Promise<string> {
     return invoke('fetch_skill_content', { url });
   },
 
-  fetchRemoteSkillFiles(sourceUrl: string, skillId: string): Promise<RemoteSkillFile[]> {
-    return invoke('fetch_remote_skill_files', { input: { sourceUrl, skillId } });
+  fetchRemoteSkillFiles(
+    sourceUrl: string,
+    skillId: string,
+    gitRef?: string
+  ): Promise<RemoteSkillFile[]> {
+    return invoke('fetch_remote_skill_files', { input: { sourceUrl, skillId, gitRef } });
+  },
+
+  listMarketplacePlugins(sourceId: string): Promise<MarketplaceCatalog> {
+    return invoke('list_marketplace_plugins', { sourceId });
+  },
+
+  reviewMarketplacePlugin(sourceId: string, plugin: string): Promise<PluginReview> {
+    return invoke('review_marketplace_plugin', { sourceId, plugin });
+  },
+
+  installMarketplacePlugin(
+    sourceId: string,
+    plugin: string,
+    options: PluginInstallOptions
+  ): Promise<PluginInstallResult> {
+    return invoke('install_marketplace_plugin', { sourceId, plugin, options });
+  },
+
+  checkSkillUpdates(): Promise<SkillUpdateCheck> {
+    return invoke('check_skill_updates');
+  },
+
+  upgradeSkill(id: string, force = false): Promise<SkillUpgradeResult> {
+    return invoke('upgrade_skill', { id, force });
   },
 
   // ============================================
@@ -395,6 +514,14 @@ export const api = {
     return invoke('update_profile', { id, input });
   },
 
+  getProfileTokenReport(profileId: string): Promise<ProfileTokenReport> {
+    return invoke('get_profile_token_report', { profileId });
+  },
+
+  estimateSkillTokens(): Promise<TokenEstimate[]> {
+    return invoke('estimate_skill_tokens');
+  },
+
   deleteProfile(id: string): Promise<void> {
     return invoke('delete_profile', { id });
   },
@@ -458,11 +585,36 @@ export const api = {
     return invoke('export_config', { outputPath });
   },
 
-  deployConfig(version: string, changelog: string): Promise<DeployResult> {
-    return invoke('deploy_config', { version, changelog });
+  deployConfig(
+    version: string,
+    changelog: string,
+    channel: ReleaseChannel = 'stable'
+  ): Promise<DeployResult> {
+    return invoke('deploy_config', { version, changelog, channel });
+  },
+
+  promoteConfigRelease(version: string, from: ReleaseChannel, to: ReleaseChannel): Promise<string> {
+    return invoke('promote_config_release', { version, from, to });
+  },
+
+  promotePluginRelease(version: string, from: ReleaseChannel, to: ReleaseChannel): Promise<string> {
+    return invoke('promote_plugin_release', { version, from, to });
+  },
+
+  // Release Channel
+  getReleaseChannel(): Promise<ReleaseChannel> {
+    return invoke('get_release_channel');
+  },
+
+  setReleaseChannel(channel: ReleaseChannel): Promise<void> {
+    return invoke('set_release_channel', { channel });
+  },
+
+  syncConfig(options?: SyncOptions): Promise<SyncResult> {
+    return invoke('sync_config', { options: options ?? null });
   },
 
-  syncConfig(): Promise<SyncResult> {
-    return invoke('sync_config');
+  diffConfig(bundlePath?: string): Promise<ConfigDiff> {
+    return invoke('diff_config', { bundlePath: bundlePath ?? null });
   },
 };
@@ -1,6 +1,6 @@
 import { useEffect, useState } from 'react';
 import { api } from '../api';
-import type { Profile, ProfileType, CreateProfileInput, UpdateProfileInput, Skill, IdeInfo, PermissionConfig, StatusLineConfig, OutputStyle } from '../types';
+import type { Profile, ProfileType, CreateProfileInput, UpdateProfileInput, Skill, IdeInfo, PermissionConfig, StatusLineConfig, OutputStyle, ProfileTokenReport } from '../types';
 import toast from 'react-hot-toast';
 import { Prism as SyntaxHighlighter } from 'react-syntax-highlighter';
 import { vscDarkPlus } from 'react-syntax-highlighter/dist/esm/styles/prism';
@@ -22,12 +22,17 @@ const SCROLLABLE_LIST_STYLE: React.CSSProperties = {
 
 type EditSection = 'basic' | 'instructions' | 'skills' | 'settings' | 'output-style';
 
+function formatTokens(tokens: number): string {
+  return tokens >= 1000 ? `~${(tokens / 1000).toFixed(1)}k tokens` : `~${tokens} tokens`;
+}
+
 export default function Profiles() {
   const [profiles, setProfiles] = useState<Profile[]>([]);
   const [skills, setSkills] = useState<Skill[]>([]);
   const [loading, setLoading] = useState(true);
   const [defaultUserProfile, setDefaultUserProfile] = useState<string | null>(null);
   const [availableIdes, setAvailableIdes] = useState<IdeInfo[]>([]);
+  const [outputStyles, setOutputStyles] = useState<OutputStyle[]>([]);
 
   // Create/Edit state
   const [creating, setCreating] = useState(false);
@@ -63,6 +68,11 @@ export default function Profiles() {
   const [outputStyle, setOutputStyle] = useState<OutputStyle | null>(null);
   const [outputStyleLoading, setOutputStyleLoading] = useState(false);
 
+  // Token estimates
+  const [skillTokens, setSkillTokens] = useState<Record<string, number>>({});
+  const [tokenReport, setTokenReport] = useState<ProfileTokenReport | null>(null);
+  const [tokenBudget, setTokenBudget] = useState('');
+
   // Computed values
   const categories = [...new Set(skills.map(s => s.category))].sort();
   const filteredSkills = categoryFilter
@@ -75,21 +85,38 @@ export default function Profiles() {
 
   async function loadData() {
     try {
-      const [profileList, skillList, defaultProfile, ides] = await Promise.all([
+      const [profileList, skillList, defaultProfile, ides, styles] = await Promise.all([
         api.listProfiles(),
         api.listSkills(),
         api.getDefaultUserProfile(),
         api.listAvailableIdes(),
+        api.listOutputStyles(),
       ]);
       setProfiles(profileList);
       setSkills(skillList);
       setDefaultUserProfile(defaultProfile?.id ?? null);
       setAvailableIdes(ides.filter((ide) => ide.available));
+      setOutputStyles(styles);
     } catch (err) {
       toast.error('Failed to load profiles data');
     } finally {
       setLoading(false);
     }
+
+    try {
+      const estimates = await api.estimateSkillTokens();
+      setSkillTokens(Object.fromEntries(estimates.map((e) => [e.id, e.tokens])));
+    } catch {
+      setSkillTokens({});
+    }
+  }
+
+  async function loadTokenReport(profileId: string) {
+    try {
+      setTokenReport(await api.getProfileTokenReport(profileId));
+    } catch {
+      setTokenReport(null);
+    }
   }
 
   // ============================================
@@ -125,10 +152,14 @@ export default function Profiles() {
         name: formData.name,
         description: formData.description,
         profileType: formData.profileType,
+        tokenBudget: Number(tokenBudget) || 0,
+        // The Main-Profile uses the active output style
+        ...(formData.profileType === 'project' && { outputStyle: formData.outputStyle ?? '' }),
       };
       await api.updateProfile(editing.id, input);
       toast.success('Profile updated');
       loadData();
+      loadTokenReport(editing.id);
     } catch (err) {
       toast.error('Failed to update profile');
     }
@@ -166,10 +197,13 @@ export default function Profiles() {
       name: profile.name,
       description: profile.description,
       profileType: profile.profileType,
+      outputStyle: profile.outputStyle,
     });
     setAssignedSkills(new Set(profile.skills));
     setActiveSection('basic');
     setCategoryFilter(null);
+    setTokenBudget(profile.tokenBudget ? String(profile.tokenBudget) : '');
+    loadTokenReport(profile.id);
 
     // Load instructions
     setInstructionsLoading(true);
@@ -202,6 +236,8 @@ export default function Profiles() {
     setInstructionsContent('');
     setActiveSection('basic');
     setCategoryFilter(null);
+    setTokenBudget('');
+    setTokenReport(null);
   }
 
   // ============================================
@@ -227,6 +263,7 @@ export default function Profiles() {
       await api.assignSkillsToProfile(editing.id, Array.from(assignedSkills));
       toast.success('Skills saved');
       loadData();
+      loadTokenReport(editing.id);
     } catch (err) {
       toast.error('Failed to save skills');
     } finally {
@@ -497,6 +534,50 @@ export default function Profiles() {
                 />
               </div>
             )}
+            {formData.profileType === 'project' && (
+              <div className="form-group">
+                <label>Output style</label>
+                <select
+                  value={formData.outputStyle ?? ''}
+                  onChange={(e) => setFormData({ ...formData, outputStyle: e.target.value || undefined })}
+                >
+                  <option value="">None</option>
+                  {outputStyles.map((style) => (
+                    <option key={style.id} value={style.id}>{style.name}</option>
+                  ))}
+                </select>
+                <div style={{ fontSize: '12px', marginTop: '6px', color: 'var(--text-secondary)' }}>
+                  Installed to the project's .claude/output-styles/ and selected in .claude/settings.json
+                </div>
+              </div>
+            )}
+            {editing && (
+              <div className="form-group">
+                <label>Token budget</label>
+                <input
+                  type="number"
+                  min={0}
+                  value={tokenBudget}
+                  onChange={(e) => setTokenBudget(e.target.value)}
+                  placeholder={tokenReport ? String(tokenReport.budget) : '30000'}
+                />
+                {tokenReport && (
+                  <div
+                    style={{
+                      fontSize: '12px',
+                      marginTop: '6px',
+                      color: tokenReport.overBudget ? 'var(--error)' : 'var(--text-secondary)',
+                    }}
+                  >
+                    Estimated context: {formatTokens(tokenReport.total)} of {tokenReport.budget}
+                    {' '}({tokenReport.skills.length} skills
+                    {tokenReport.outputStyle ? ', output style' : ''}
+                    {tokenReport.instructions.map((f) => `, ${f.id}`).join('')})
+                    {tokenReport.overBudget && ' - over budget'}
+                  </div>
+                )}
+              </div>
+            )}
           </div>
         )}
 
@@ -504,7 +585,15 @@ export default function Profiles() {
         {activeSection === 'skills' && (
           <div className="card" style={{ padding: '20px', marginBottom: '16px' }}>
             <div style={{ display: 'flex', justifyContent: 'space-between', alignItems: 'center', marginBottom: '16px' }}>
-              <h3 style={{ margin: 0 }}>Assign Skills</h3>
+              <div>
+                <h3 style={{ margin: 0 }}>Assign Skills</h3>
+                <div style={{ fontSize: '12px', color: 'var(--text-tertiary)', marginTop: '4px' }}>
+                  Selected: {formatTokens(
+                    Array.from(assignedSkills).reduce((sum, id) => sum + (skillTokens[id] ?? 0), 0)
+                  )}
+                  {tokenReport && ` (budget ${tokenReport.budget})`}
+                </div>
+              </div>
               {editing && (
                 <button
                   className="btn btn-primary"
@@ -555,7 +644,14 @@ export default function Profiles() {
                     style={{ marginRight: '12px', marginTop: '4px' }}
                   />
                   <div style={{ flex: 1, minWidth: 0 }}>
-                    <div style={{ fontWeight: 500 }}>{skill.name}</div>
+                    <div style={{ fontWeight: 500 }}>
+                      {skill.name}
+                      {skillTokens[skill.id] !== undefined && (
+                        <span style={{ fontSize: '11px', fontWeight: 400, color: 'var(--text-tertiary)', marginLeft: '8px' }}>
+                          {formatTokens(skillTokens[skill.id])}
+                        </span>
+                      )}
+                    </div>
                     <div
                       title={skill.description || ''}
                       style={{
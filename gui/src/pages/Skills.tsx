@@ -1,14 +1,14 @@
 import { useEffect, useState } from 'react';
 import { api } from '../api';
-import type { Skill, SkillCategory, CreateSkillInput, SkillSource, SkillSourceType, SkillSchema, RemoteSkill, IdeInfo, SkillFile, RemoteSkillFile } from '../types';
+import type { Skill, SkillCategory, CreateSkillInput, SkillSource, SkillSourceType, SkillSchema, ScanPolicy, SecurityReport, SearchResult, SkillLayout, SkillIdFrom, RemoteSkill, MarketplacePlugin, IdeInfo, SkillFile, RemoteSkillFile } from '../types';
 import toast from 'react-hot-toast';
+import { getCurrentWebview } from '@tauri-apps/api/webview';
+import { open, save } from '@tauri-apps/plugin-dialog';
 import { Prism as SyntaxHighlighter } from 'react-syntax-highlighter';
 import { vscDarkPlus } from 'react-syntax-highlighter/dist/esm/styles/prism';
 
 type TabType = 'rhinolabs-skills' | 'sources' | 'browse';
 
-const CATEGORIES: SkillCategory[] = ['corporate', 'frontend', 'testing', 'ai-sdk', 'utilities', 'custom'];
-
 const SOURCE_TYPE_COLORS: Record<SkillSourceType, string> = {
   official: '#10b981',
   marketplace: '#8b5cf6',
@@ -16,6 +16,34 @@ const SOURCE_TYPE_COLORS: Record<SkillSourceType, string> = {
   local: '#6b7280',
 };
 
+const SOURCE_TYPES: SkillSourceType[] = ['official', 'marketplace', 'community', 'local'];
+
+/** Tinted badge in the category's color */
+function categoryStyle(category?: SkillCategory): React.CSSProperties {
+  if (!category) return { background: '#4a5568' };
+  return {
+    color: category.color,
+    backgroundColor: `color-mix(in srgb, ${category.color} 12%, transparent)`,
+  };
+}
+
+/** "docker, k8s " -> ['docker', 'k8s'] */
+function parseTags(input: string): string[] {
+  return input
+    .split(/[,\s]+/)
+    .map((t) => t.trim().toLowerCase())
+    .filter((t) => t.length > 0);
+}
+
+/** One line per finding, for the acknowledgement prompt */
+function describeFindings(report: SecurityReport): string {
+  return report.findings
+    .map((f) => `[${f.level}] ${f.line ? `${f.file}:${f.line}` : f.file} — ${f.message}`)
+    .join('\n');
+}
+
+const PACKAGE_FILTERS = [{ name: 'Skill package', extensions: ['skill'] }];
+
 const SCROLLABLE_LIST_STYLE: React.CSSProperties = {
   maxHeight: '400px',
   overflowY: 'auto',
@@ -29,7 +57,9 @@ export default function Skills() {
   const [skills, setSkills] = useState<Skill[]>([]);
   const [sources, setSources] = useState<SkillSource[]>([]);
   const [loading, setLoading] = useState(true);
-  const [filter, setFilter] = useState<SkillCategory | 'all'>('all');
+  const [categories, setCategories] = useState<SkillCategory[]>([]);
+  const [filter, setFilter] = useState<string>('all');
+  const [tagFilter, setTagFilter] = useState<string | null>(null);
 
   // Viewing/Creating state
   const [viewing, setViewing] = useState<Skill | null>(null);
@@ -52,12 +82,19 @@ export default function Skills() {
     description: '',
     fetchable: false,
     schema: 'standard' as SkillSchema,
+    layoutRoots: '',
+    skillFile: 'SKILL.md',
+    idFrom: 'dirName' as SkillIdFrom,
   });
 
   // Browse state
   const [selectedSource, setSelectedSource] = useState<string | null>(null);
   const [remoteSkills, setRemoteSkills] = useState<RemoteSkill[]>([]);
+  const [marketplacePlugins, setMarketplacePlugins] = useState<MarketplacePlugin[]>([]);
+  const [installingPlugin, setInstallingPlugin] = useState<string | null>(null);
   const [browseLoading, setBrowseLoading] = useState(false);
+  const [searchQuery, setSearchQuery] = useState('');
+  const [searchResults, setSearchResults] = useState<SearchResult[] | null>(null);
   const [addingSkill, setAddingSkill] = useState<string | null>(null);
   const [previewingSkillId, setPreviewingSkillId] = useState<string | null>(null);
 
@@ -69,7 +106,10 @@ export default function Skills() {
     description?: string;
     remote?: RemoteSkill;
   } | null>(null);
-  const [selectedCategory, setSelectedCategory] = useState<SkillCategory>('custom');
+  const [selectedCategory, setSelectedCategory] = useState<string>('custom');
+  const [tagsInput, setTagsInput] = useState('');
+  const [scanPolicies, setScanPolicies] = useState<Record<SkillSourceType, ScanPolicy> | null>(null);
+  const [draggingPackage, setDraggingPackage] = useState(false);
 
 
   // IDE and skill files state
@@ -105,14 +145,38 @@ export default function Skills() {
     loadIdes();
   }, []);
 
+  // Install .skill files dropped on the window
+  useEffect(() => {
+    const unlisten = getCurrentWebview().onDragDropEvent(async (event) => {
+      const { type } = event.payload;
+      if (type === 'enter') {
+        setDraggingPackage(event.payload.paths.some((p) => p.endsWith('.skill')));
+      } else if (type === 'leave') {
+        setDraggingPackage(false);
+      } else if (type === 'drop') {
+        setDraggingPackage(false);
+        for (const path of event.payload.paths.filter((p) => p.endsWith('.skill'))) {
+          await handleInstallPackage(path);
+        }
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   async function loadData() {
     try {
-      const [skillList, sourceList] = await Promise.all([
+      const [skillList, sourceList, policies, categoryList] = await Promise.all([
         api.listSkills(),
         api.listSkillSources(),
+        api.getScanPolicies(),
+        api.listSkillCategories(),
       ]);
       setSkills(skillList);
+      setCategories(categoryList);
       setSources(sourceList);
+      setScanPolicies(policies);
     } catch (err) {
       toast.error('Failed to load skills data');
     } finally {
@@ -178,7 +242,8 @@ export default function Skills() {
       // Change category of existing skill
       try {
         await api.setSkillCategory(categoryPopup.id, selectedCategory);
-        toast.success(`Category changed to ${selectedCategory}`);
+        await api.setSkillTags(categoryPopup.id, parseTags(tagsInput));
+        toast.success('Category and tags saved');
         setCategoryPopup(null);
         loadData();
       } catch (err) {
@@ -192,6 +257,22 @@ export default function Skills() {
         const source = sources.find((s) => s.id === categoryPopup.remote!.sourceId);
         if (!source) throw new Error('Source not found');
 
+        const report = await api.scanRemoteSkill(source.url, categoryPopup.remote.id, source.id);
+        if (report.blocked) {
+          toast.error(
+            `"${categoryPopup.name}" was blocked by the security scan (${report.findings.length} finding(s))`
+          );
+          return;
+        }
+        if (report.findings.length > 0) {
+          const acknowledged = confirm(
+            `Security scan found ${report.findings.length} risk(s) in "${categoryPopup.name}":\n\n` +
+              `${describeFindings(report)}\n\nInstall it anyway?`
+          );
+          if (!acknowledged) return;
+          await api.acknowledgeSkillRisks(report.skillId, report.fingerprint);
+        }
+
         await api.installSkillFromRemote({
           sourceUrl: source.url,
           skillId: categoryPopup.remote.id,
@@ -221,6 +302,54 @@ export default function Skills() {
     }
   }
 
+  async function handleInstallPackage(path: string) {
+    const file = path.split(/[\\/]/).pop();
+    try {
+      const report = await api.scanSkillPackage(path);
+      if (report.blocked) {
+        toast.error(
+          `"${report.skillId}" was blocked by the security scan (${report.findings.length} finding(s))`
+        );
+        return;
+      }
+      if (report.findings.length > 0) {
+        const acknowledged = confirm(
+          `Security scan found ${report.findings.length} risk(s) in "${report.skillId}":\n\n` +
+            `${describeFindings(report)}\n\nInstall it anyway?`
+        );
+        if (!acknowledged) return;
+        await api.acknowledgeSkillRisks(report.skillId, report.fingerprint);
+      }
+
+      const skill = await api.unpackSkill(path);
+      toast.success(`Installed "${skill.name}" from ${file}`);
+      loadData();
+    } catch (err) {
+      toast.error(`Failed to install ${file}: ${err}`);
+    }
+  }
+
+  async function handlePickPackage() {
+    const selected = await open({ multiple: false, filters: PACKAGE_FILTERS });
+    if (typeof selected === 'string') {
+      await handleInstallPackage(selected);
+    }
+  }
+
+  async function handleExportPackage(skill: Skill) {
+    const dest = await save({
+      defaultPath: skill.version ? `${skill.id}-${skill.version}.skill` : `${skill.id}.skill`,
+      filters: PACKAGE_FILTERS,
+    });
+    if (!dest) return;
+    try {
+      const path = await api.packSkill(skill.id, dest);
+      toast.success(`Exported to ${path}`);
+    } catch (err) {
+      toast.error(`Failed to export skill: ${err}`);
+    }
+  }
+
   async function handleCreate() {
     if (!formData.id.trim() || !formData.name.trim()) {
       toast.error('ID and name are required');
@@ -245,12 +374,50 @@ export default function Skills() {
       return;
     }
     if (!confirm(`Delete skill "${skill.name}"?`)) return;
+    const dependents = skills.filter((s) => s.requires?.includes(skill.id)).map((s) => s.name);
+    if (
+      dependents.length > 0 &&
+      !confirm(`${dependents.join(', ')} require "${skill.name}". Delete it anyway?`)
+    ) {
+      return;
+    }
     try {
-      await api.deleteSkill(skill.id);
+      await api.deleteSkill(skill.id, dependents.length > 0);
       toast.success('Skill deleted');
       loadData();
     } catch (err) {
-      toast.error('Failed to delete skill');
+      toast.error(`Failed to delete skill: ${err}`);
+    }
+  }
+
+  async function handleRename(skill: Skill) {
+    const newId = prompt(`Rename "${skill.id}" to:`, skill.id)?.trim();
+    if (!newId || newId === skill.id) return;
+    try {
+      const result = await api.renameSkill(skill.id, newId);
+      toast.success(
+        result.profiles.length > 0
+          ? `Renamed to "${newId}" and updated ${result.profiles.join(', ')}`
+          : `Renamed to "${newId}"`
+      );
+      if (result.references.length > 0) {
+        toast(`Updated requires/conflicts in ${result.references.join(', ')}`);
+      }
+      loadData();
+    } catch (err) {
+      toast.error(`Failed to rename skill: ${err}`);
+    }
+  }
+
+  async function handleClone(skill: Skill) {
+    const newId = prompt(`Clone "${skill.id}" as a custom skill:`, `${skill.id}-custom`)?.trim();
+    if (!newId) return;
+    try {
+      const clone = await api.cloneSkill(skill.id, newId);
+      toast.success(`Cloned "${skill.name}" to "${clone.id}"`);
+      loadData();
+    } catch (err) {
+      toast.error(`Failed to clone skill: ${err}`);
     }
   }
 
@@ -276,9 +443,21 @@ export default function Skills() {
       description: '',
       fetchable: false,
       schema: 'standard',
+      layoutRoots: '',
+      skillFile: 'SKILL.md',
+      idFrom: 'dirName',
     });
   }
 
+  function sourceLayout(): SkillLayout | undefined {
+    if (sourceForm.schema !== 'custom') return undefined;
+    const roots = sourceForm.layoutRoots
+      .split(/[\n,]/)
+      .map((r) => r.trim())
+      .filter(Boolean);
+    return { roots, skillFile: sourceForm.skillFile.trim() || 'SKILL.md', idFrom: sourceForm.idFrom };
+  }
+
   function startEditSource(source: SkillSource) {
     setEditingSource(source);
     setSourceForm({
@@ -288,6 +467,9 @@ export default function Skills() {
       description: source.description,
       fetchable: source.fetchable,
       schema: source.schema,
+      layoutRoots: source.layout?.roots.join('\n') ?? '',
+      skillFile: source.layout?.skillFile ?? 'SKILL.md',
+      idFrom: source.layout?.idFrom ?? 'dirName',
     });
   }
 
@@ -298,8 +480,14 @@ export default function Skills() {
     }
     try {
       await api.addSkillSource({
-        ...sourceForm,
+        id: sourceForm.id,
+        name: sourceForm.name,
+        url: sourceForm.url,
+        description: sourceForm.description,
+        fetchable: sourceForm.fetchable,
+        schema: sourceForm.schema,
         sourceType: 'community', // Default, not user-visible
+        layout: sourceLayout(),
       });
       toast.success('Source added');
       setAddingSource(false);
@@ -323,6 +511,9 @@ export default function Skills() {
         fetchable: sourceForm.fetchable,
         schema: sourceForm.schema,
       });
+      if (sourceForm.schema === 'custom') {
+        await api.setSkillSourceLayout(editingSource.id, sourceLayout() ?? null);
+      }
       toast.success('Source updated');
       setEditingSource(null);
       resetSourceForm();
@@ -342,6 +533,16 @@ export default function Skills() {
     }
   }
 
+  async function handleScanPolicyChange(sourceType: SkillSourceType, policy: ScanPolicy) {
+    try {
+      await api.setScanPolicy(sourceType, policy);
+      setScanPolicies((prev) => (prev ? { ...prev, [sourceType]: policy } : prev));
+      toast.success(`Scan policy for ${sourceType} sources set to ${policy}`);
+    } catch (err) {
+      toast.error('Failed to update scan policy');
+    }
+  }
+
   async function handleRemoveSource(source: SkillSource) {
     if (!confirm(`Remove source "${source.name}"?`)) return;
     try {
@@ -358,14 +559,19 @@ export default function Skills() {
   // Browse Actions
   // ============================================
 
-  async function handleSelectSource(sourceId: string) {
+  async function handleSelectSource(sourceId: string, refresh = false) {
     setSelectedSource(sourceId);
     setBrowseLoading(true);
     setRemoteSkills([]);
+    setMarketplacePlugins([]);
 
     try {
-      const skills = await api.fetchRemoteSkills(sourceId);
+      const skills = await api.fetchRemoteSkills(sourceId, refresh);
       setRemoteSkills(skills);
+      if (sources.find((s) => s.id === sourceId)?.schema === 'marketplace') {
+        const catalog = await api.listMarketplacePlugins(sourceId);
+        setMarketplacePlugins(catalog.plugins);
+      }
     } catch (err: unknown) {
       const message = err instanceof Error ? err.message : 'Failed to fetch skills';
       toast.error(message);
@@ -374,6 +580,84 @@ export default function Skills() {
     }
   }
 
+  async function handleSearch() {
+    if (!searchQuery.trim()) {
+      setSearchResults(null);
+      return;
+    }
+    try {
+      setSearchResults(await api.searchSkills(searchQuery));
+    } catch (err: unknown) {
+      const message = err instanceof Error ? err.message : 'Search failed';
+      toast.error(message);
+    }
+  }
+
+  function handleOpenSearchResult(result: SearchResult) {
+    if (result.installed) {
+      const skill = skills.find((s) => s.id === result.skillId);
+      if (skill) {
+        setActiveTab('rhinolabs-skills');
+        setViewing(skill);
+      }
+    } else if (result.sourceId) {
+      setSearchResults(null);
+      handleSelectSource(result.sourceId);
+    }
+  }
+
+  async function handleInstallPlugin(plugin: MarketplacePlugin) {
+    if (!selectedSource) return;
+    setInstallingPlugin(plugin.name);
+    try {
+      const review = await api.reviewMarketplacePlugin(selectedSource, plugin.name);
+      if (review.report.blocked) {
+        toast.error(
+          `"${plugin.name}" was blocked by the security scan (${review.report.findings.length} finding(s))`
+        );
+        return;
+      }
+      if (review.report.findings.length > 0) {
+        const acknowledged = confirm(
+          `Security scan found ${review.report.findings.length} risk(s) in the commands and agents of "${plugin.name}":\n\n` +
+            `${describeFindings(review.report)}\n\nInstall it anyway?`
+        );
+        if (!acknowledged) return;
+        await api.acknowledgeSkillRisks(review.report.skillId, review.report.fingerprint);
+      }
+
+      const servers = Object.entries(review.mcpServers);
+      const mcpServers =
+        servers.length > 0 &&
+        confirm(
+          `"${plugin.name}" also provides MCP servers, which run these commands:\n\n` +
+            servers
+              .map(([name, server]) =>
+                `${name}: ${server.command ? [server.command, ...(server.args ?? [])].join(' ') : server.url ?? ''}`
+              )
+              .join('\n') +
+            '\n\nAdd them to the MCP config?'
+        );
+
+      const result = await api.installMarketplacePlugin(selectedSource, plugin.name, { mcpServers });
+      const installed =
+        result.skills.length +
+        result.commands.length +
+        result.agents.length +
+        Object.keys(result.mcpServers).length;
+      toast.success(`Installed ${installed} component(s) from ${result.plugin}`);
+      if (result.skipped.length > 0) {
+        toast(`Skipped: ${result.skipped.join(', ')}`);
+      }
+      loadData();
+      handleSelectSource(selectedSource);
+    } catch (err) {
+      toast.error(`Failed to install plugin: ${err}`);
+    } finally {
+      setInstallingPlugin(null);
+    }
+  }
+
   function handleAddFromSource(remote: RemoteSkill) {
     setSelectedCategory('custom');
     setCategoryPopup({
@@ -401,7 +685,7 @@ export default function Skills() {
       }
 
       // Fetch the file structure
-      const files = await api.fetchRemoteSkillFiles(source.url, remote.id);
+      const files = await api.fetchRemoteSkillFiles(source.url, remote.id, source.gitRef);
 
       // Only update state if this is still the skill we're previewing
       setPreviewFiles(files);
@@ -471,7 +755,12 @@ export default function Skills() {
     return <span className="category-badge" style={{ background: '#4a5568' }}>Built-in</span>;
   }
 
-  const filteredSkills = filter === 'all' ? skills : skills.filter((s) => s.category === filter);
+  const categoryById = (id: string) => categories.find((c) => c.id === id);
+  const categoryName = (id: string) => categoryById(id)?.name ?? id;
+  const allTags = [...new Set(skills.flatMap((s) => s.tags))].sort();
+  const filteredSkills = skills.filter(
+    (s) => (filter === 'all' || s.category === filter) && (!tagFilter || s.tags.includes(tagFilter))
+  );
   const enabledSources = sources.filter((s) => s.enabled);
 
   // ============================================
@@ -591,6 +880,9 @@ export default function Skills() {
                 ))}
               </select>
             )}
+            <button className="btn btn-secondary" onClick={() => handleExportPackage(viewing)}>
+              Export .skill
+            </button>
             <button
               className="btn btn-secondary"
               onClick={() => {
@@ -607,7 +899,14 @@ export default function Skills() {
 
         {/* Badges */}
         <div style={{ display: 'flex', gap: '0.5rem', marginBottom: '1rem', flexWrap: 'wrap', flexShrink: 0 }}>
-          <span className={`category-badge ${viewing.category}`}>{viewing.category}</span>
+          <span className="category-badge" style={categoryStyle(categoryById(viewing.category))}>
+            {categoryName(viewing.category)}
+          </span>
+          {viewing.tags.map((tag) => (
+            <span key={tag} className="category-badge" style={{ background: 'var(--bg-secondary)' }}>
+              #{tag}
+            </span>
+          ))}
           <span className={`status-badge ${viewing.enabled ? 'success' : ''}`}>
             {viewing.enabled ? 'Enabled' : 'Disabled'}
           </span>
@@ -773,11 +1072,11 @@ export default function Skills() {
             <label>Category</label>
             <select
               value={formData.category}
-              onChange={(e) => setFormData({ ...formData, category: e.target.value as SkillCategory })}
+              onChange={(e) => setFormData({ ...formData, category: e.target.value })}
             >
-              {CATEGORIES.map((cat) => (
-                <option key={cat} value={cat}>
-                  {cat.charAt(0).toUpperCase() + cat.slice(1)}
+              {categories.map((cat) => (
+                <option key={cat.id} value={cat.id}>
+                  {cat.name}
                 </option>
               ))}
             </select>
@@ -847,7 +1146,7 @@ export default function Skills() {
           </div>
 
           <div className="form-group">
-            <label>URL (GitHub repository or website)</label>
+            <label>URL (git repository, local directory, archive or website)</label>
             <input
               type="text"
               value={sourceForm.url}
@@ -877,7 +1176,7 @@ export default function Skills() {
               Supports auto-fetch
             </label>
             <p style={{ color: 'var(--text-secondary)', fontSize: '0.75rem', marginTop: '0.25rem' }}>
-              Enable if this is a GitHub repository with skills folder structure that supports automatic skill fetching.
+              Enable if this source has a skills folder structure that supports automatic skill fetching: a GitHub or other git repository, a local or network directory, or a .tar.gz/.zip archive.
             </p>
           </div>
 
@@ -890,13 +1189,52 @@ export default function Skills() {
               >
                 <option value="standard">Standard (agentskills.io)</option>
                 <option value="custom">Custom</option>
+                <option value="marketplace">Claude plugin marketplace</option>
               </select>
               <p style={{ color: 'var(--text-secondary)', fontSize: '0.75rem', marginTop: '0.25rem' }}>
-                Standard: GitHub repo with <code>/skills/skill-name/SKILL.md</code> structure (Anthropic, Vercel)
+                Standard: <code>/skills/skill-name/SKILL.md</code> structure (Anthropic, Vercel).
+                Marketplace: plugins listed in <code>.claude-plugin/marketplace.json</code>.
               </p>
             </div>
           )}
 
+          {sourceForm.fetchable && sourceForm.schema === 'custom' && (
+            <>
+              <div className="form-group">
+                <label>Skill Directories</label>
+                <textarea
+                  value={sourceForm.layoutRoots}
+                  onChange={(e) => setSourceForm({ ...sourceForm, layoutRoots: e.target.value })}
+                  placeholder={'.claude/skills/*\nplugins/*/skills/*'}
+                  rows={3}
+                />
+                <p style={{ color: 'var(--text-secondary)', fontSize: '0.75rem', marginTop: '0.25rem' }}>
+                  One glob per line. <code>*</code> matches a folder name, <code>**</code> any depth, <code>.</code> the repository root.
+                </p>
+              </div>
+              <div className="form-group">
+                <label>Skill File</label>
+                <input
+                  type="text"
+                  value={sourceForm.skillFile}
+                  onChange={(e) => setSourceForm({ ...sourceForm, skillFile: e.target.value })}
+                  placeholder="SKILL.md"
+                />
+              </div>
+              <div className="form-group">
+                <label>Skill IDs</label>
+                <select
+                  value={sourceForm.idFrom}
+                  onChange={(e) => setSourceForm({ ...sourceForm, idFrom: e.target.value as SkillIdFrom })}
+                >
+                  <option value="dirName">Folder name</option>
+                  <option value="wildcards">Wildcard matches joined with '-'</option>
+                  <option value="frontmatter">Frontmatter name</option>
+                </select>
+              </div>
+            </>
+          )}
+
           <div style={{ display: 'flex', gap: '0.75rem' }}>
             <button className="btn btn-primary" onClick={isEditing ? handleSaveSource : handleAddSource}>
               {isEditing ? 'Save Changes' : 'Add Source'}
@@ -1143,6 +1481,21 @@ export default function Skills() {
         <p>Manage Claude Code skills and their sources</p>
       </div>
 
+      {draggingPackage && (
+        <div
+          style={{
+            padding: '1.5rem',
+            marginBottom: '1rem',
+            border: '2px dashed var(--accent)',
+            borderRadius: '8px',
+            textAlign: 'center',
+            color: 'var(--text-secondary)',
+          }}
+        >
+          Drop to install the skill package
+        </div>
+      )}
+
       {/* Tabs */}
       <div style={{ display: 'flex', gap: '0', marginBottom: '1.5rem', borderBottom: '2px solid var(--border)' }}>
         {([
@@ -1177,18 +1530,39 @@ export default function Skills() {
             <button className="btn btn-primary" onClick={() => setCreating(true)}>
               Create Skill
             </button>
+            <button
+              className="btn btn-secondary"
+              onClick={handlePickPackage}
+              title="Or drop .skill files on the window"
+            >
+              Install .skill
+            </button>
             <select
               className="btn btn-secondary"
               value={filter}
-              onChange={(e) => setFilter(e.target.value as SkillCategory | 'all')}
+              onChange={(e) => setFilter(e.target.value)}
             >
               <option value="all">All Categories</option>
-              {CATEGORIES.map((cat) => (
-                <option key={cat} value={cat}>
-                  {cat.charAt(0).toUpperCase() + cat.slice(1)}
+              {categories.map((cat) => (
+                <option key={cat.id} value={cat.id}>
+                  {cat.name}
                 </option>
               ))}
             </select>
+            {allTags.length > 0 && (
+              <select
+                className="btn btn-secondary"
+                value={tagFilter ?? ''}
+                onChange={(e) => setTagFilter(e.target.value || null)}
+              >
+                <option value="">All Tags</option>
+                {allTags.map((tag) => (
+                  <option key={tag} value={tag}>
+                    #{tag}
+                  </option>
+                ))}
+              </select>
+            )}
           </div>
 
           {/* Stats */}
@@ -1215,7 +1589,8 @@ export default function Skills() {
           <div style={SCROLLABLE_LIST_STYLE}>
             {filteredSkills.length === 0 ? (
               <p style={{ color: 'var(--text-secondary)', textAlign: 'center', padding: '2rem' }}>
-                No skills found{filter !== 'all' ? ` in category "${filter}"` : ''}.
+                No skills found{filter !== 'all' ? ` in category "${categoryName(filter)}"` : ''}
+                {tagFilter ? ` tagged #${tagFilter}` : ''}.
               </p>
             ) : (
               filteredSkills.map((skill) => (
@@ -1227,13 +1602,35 @@ export default function Skills() {
                   <div className="item-info">
                     <h4 style={{ display: 'flex', alignItems: 'center', gap: '0.5rem', flexWrap: 'wrap' }}>
                       {skill.name}
-                      <span className={`category-badge ${skill.category}`}>{skill.category}</span>
+                      <span className="category-badge" style={categoryStyle(categoryById(skill.category))}>
+                        {categoryName(skill.category)}
+                      </span>
                       {getSourceBadge(skill)}
                       {skill.isModified && (
                         <span className="category-badge" style={{ background: '#f59e0b' }}>modified</span>
                       )}
                     </h4>
                     <p>{skill.description}</p>
+                    {skill.tags.length > 0 && (
+                      <div style={{ display: 'flex', gap: '0.375rem', marginTop: '0.25rem', flexWrap: 'wrap' }}>
+                        {skill.tags.map((tag) => (
+                          <button
+                            key={tag}
+                            onClick={() => setTagFilter(tag)}
+                            style={{
+                              background: 'none',
+                              border: 'none',
+                              padding: 0,
+                              cursor: 'pointer',
+                              fontSize: '0.75rem',
+                              color: tagFilter === tag ? 'var(--accent)' : 'var(--text-secondary)',
+                            }}
+                          >
+                            #{tag}
+                          </button>
+                        ))}
+                      </div>
+                    )}
                   </div>
                   <div className="item-actions">
                     <label className="toggle-switch">
@@ -1269,6 +1666,7 @@ export default function Skills() {
                       className="btn btn-sm btn-secondary"
                       onClick={() => {
                         setSelectedCategory(skill.category);
+                        setTagsInput(skill.tags.join(', '));
                         setCategoryPopup({
                           mode: 'change',
                           id: skill.id,
@@ -1276,8 +1674,16 @@ export default function Skills() {
                         });
                       }}
                     >
-                      Category
+                      Category & Tags
+                    </button>
+                    <button className="btn btn-sm btn-secondary" onClick={() => handleClone(skill)}>
+                      Clone
                     </button>
+                    {skill.isCustom && !skill.sourceId && (
+                      <button className="btn btn-sm btn-secondary" onClick={() => handleRename(skill)}>
+                        Rename
+                      </button>
+                    )}
                     {(skill.isCustom || skill.sourceId) && (
                       <button className="btn btn-sm btn-danger" onClick={() => handleDelete(skill)}>
                         Delete
@@ -1294,6 +1700,69 @@ export default function Skills() {
       {/* Browse Tab */}
       {activeTab === 'browse' && (
         <>
+          <div className="card" style={{ marginBottom: '1rem' }}>
+            <div style={{ display: 'flex', gap: '0.5rem' }}>
+              <input
+                type="text"
+                value={searchQuery}
+                onChange={(e) => setSearchQuery(e.target.value)}
+                onKeyDown={(e) => e.key === 'Enter' && handleSearch()}
+                placeholder="Search installed skills and browsed sources..."
+                style={{ flex: 1 }}
+              />
+              <button className="btn btn-primary" onClick={handleSearch}>
+                Search
+              </button>
+              {searchResults && (
+                <button
+                  className="btn btn-secondary"
+                  onClick={() => {
+                    setSearchQuery('');
+                    setSearchResults(null);
+                  }}
+                >
+                  Clear
+                </button>
+              )}
+            </div>
+            <p style={{ color: 'var(--text-secondary)', fontSize: '0.75rem', marginTop: '0.5rem' }}>
+              Works offline: sources are searched as they were last browsed.
+            </p>
+            {searchResults && searchResults.length === 0 && (
+              <p style={{ color: 'var(--text-secondary)', marginTop: '1rem' }}>No skills match the search.</p>
+            )}
+            {searchResults && searchResults.length > 0 && (
+              <div style={{ marginTop: '1rem' }}>
+                {searchResults.map((result) => (
+                  <div
+                    key={`${result.sourceId ?? 'local'}-${result.skillId}`}
+                    className="list-item"
+                    style={{ cursor: 'pointer' }}
+                    onClick={() => handleOpenSearchResult(result)}
+                  >
+                    <div className="item-info">
+                      <div style={{ display: 'flex', alignItems: 'center', gap: '0.5rem' }}>
+                        <h4>{result.name}</h4>
+                        {result.installed && <span className="status-badge">Installed</span>}
+                        {result.sourceName && (
+                          <span style={{ fontSize: '0.75rem', color: 'var(--text-secondary)' }}>
+                            {result.sourceName}
+                          </span>
+                        )}
+                      </div>
+                      <p>{result.description}</p>
+                      {result.snippet && (
+                        <p style={{ fontSize: '0.75rem', fontStyle: 'italic', color: 'var(--text-secondary)' }}>
+                          {result.snippet}
+                        </p>
+                      )}
+                    </div>
+                  </div>
+                ))}
+              </div>
+            )}
+          </div>
+
           <div style={{ marginBottom: '1rem' }}>
             <div className="form-group" style={{ marginBottom: '0.5rem' }}>
               <label>Select a source to browse skills</label>
@@ -1323,6 +1792,17 @@ export default function Skills() {
                   </option>
                 ))}
               </select>
+              {selectedSource && enabledSources.find((s) => s.id === selectedSource)?.fetchable && (
+                <button
+                  className="btn btn-secondary"
+                  style={{ marginLeft: '0.5rem' }}
+                  onClick={() => handleSelectSource(selectedSource, true)}
+                  disabled={browseLoading}
+                  title="Fetch the source again instead of using the cached catalog"
+                >
+                  Refresh
+                </button>
+              )}
             </div>
             {enabledSources.length === 0 && (
               <p style={{ color: 'var(--text-secondary)', fontStyle: 'italic' }}>
@@ -1353,6 +1833,52 @@ export default function Skills() {
             </div>
           )}
 
+          {/* Plugins of a Claude plugin marketplace */}
+          {selectedSource && !browseLoading && marketplacePlugins.length > 0 && (
+            <div style={{ ...SCROLLABLE_LIST_STYLE, marginBottom: '1rem' }}>
+              {marketplacePlugins.map((plugin) => {
+                const { components } = plugin;
+                const summary = [
+                  [components.skills.length, 'skills'],
+                  [components.commands.length, 'commands'],
+                  [components.agents.length, 'agents'],
+                  [components.mcpServers.length, 'MCP servers'],
+                ]
+                  .filter(([count]) => count)
+                  .map(([count, label]) => `${count} ${label}`)
+                  .join(' · ');
+                return (
+                  <div
+                    key={plugin.name}
+                    className="list-item"
+                    style={{ margin: 0, borderRadius: 0, borderBottom: '1px solid var(--border)' }}
+                  >
+                    <div className="item-info">
+                      <h4 style={{ display: 'flex', alignItems: 'center', gap: '0.5rem' }}>
+                        {plugin.name}
+                        {plugin.version && <span className="status-badge">v{plugin.version}</span>}
+                      </h4>
+                      <p>{plugin.description}</p>
+                      <p style={{ color: 'var(--text-secondary)', fontSize: '0.75rem' }}>
+                        {summary || 'No installable components'}
+                        {components.hooks && ' · hooks (not installed)'}
+                      </p>
+                    </div>
+                    <div className="item-actions">
+                      <button
+                        className="btn btn-sm btn-primary"
+                        onClick={() => handleInstallPlugin(plugin)}
+                        disabled={installingPlugin !== null}
+                      >
+                        {installingPlugin === plugin.name ? 'Installing...' : 'Install Plugin'}
+                      </button>
+                    </div>
+                  </div>
+                );
+              })}
+            </div>
+          )}
+
           {/* Remote skills list (only for fetchable sources) */}
           {selectedSource && enabledSources.find((s) => s.id === selectedSource)?.fetchable && (
             <div style={SCROLLABLE_LIST_STYLE}>
@@ -1490,6 +2016,34 @@ export default function Skills() {
               ))
             )}
           </div>
+
+          {/* Security scan policy per source type */}
+          {scanPolicies && (
+            <div className="card" style={{ marginTop: '1.5rem', padding: '1rem' }}>
+              <h4 style={{ marginBottom: '0.5rem' }}>Security Scan</h4>
+              <p style={{ color: 'var(--text-secondary)', fontSize: '0.875rem', marginBottom: '0.75rem' }}>
+                Skills are scanned for scripts, remote code execution, suspicious URLs, prompt
+                injection and hidden characters before they are added. <strong>Warn</strong> asks
+                you to review the findings; <strong>Block</strong> refuses the skill.
+              </p>
+              <div style={{ display: 'grid', gridTemplateColumns: 'repeat(auto-fill, minmax(180px, 1fr))', gap: '0.75rem' }}>
+                {SOURCE_TYPES.map((sourceType) => (
+                  <label key={sourceType} style={{ display: 'flex', alignItems: 'center', gap: '0.5rem' }}>
+                    <span className="category-badge" style={{ background: SOURCE_TYPE_COLORS[sourceType] }}>
+                      {sourceType}
+                    </span>
+                    <select
+                      value={scanPolicies[sourceType]}
+                      onChange={(e) => handleScanPolicyChange(sourceType, e.target.value as ScanPolicy)}
+                    >
+                      <option value="warn">Warn</option>
+                      <option value="block">Block</option>
+                    </select>
+                  </label>
+                ))}
+              </div>
+            </div>
+          )}
         </>
       )}
 
@@ -1515,21 +2069,35 @@ export default function Skills() {
             style={{ padding: '1.5rem', minWidth: '300px' }}
             onClick={(e) => e.stopPropagation()}
           >
-            <h3 style={{ marginBottom: '1rem' }}>Change Category</h3>
+            <h3 style={{ marginBottom: '1rem' }}>
+              {categoryPopup.mode === 'change' ? 'Category & Tags' : 'Choose Category'}
+            </h3>
             <div className="form-group">
               <label>Category</label>
               <select
                 value={selectedCategory}
-                onChange={(e) => setSelectedCategory(e.target.value as SkillCategory)}
+                onChange={(e) => setSelectedCategory(e.target.value)}
                 style={{ width: '100%' }}
               >
-                {CATEGORIES.map((cat) => (
-                  <option key={cat} value={cat}>
-                    {cat}
+                {categories.map((cat) => (
+                  <option key={cat.id} value={cat.id}>
+                    {cat.name}
                   </option>
                 ))}
               </select>
             </div>
+            {categoryPopup.mode === 'change' && (
+              <div className="form-group">
+                <label>Tags</label>
+                <input
+                  type="text"
+                  value={tagsInput}
+                  onChange={(e) => setTagsInput(e.target.value)}
+                  placeholder="docker, kubernetes"
+                  style={{ width: '100%' }}
+                />
+              </div>
+            )}
             <div style={{ display: 'flex', gap: '0.75rem', marginTop: '1rem' }}>
               <button className="btn btn-primary" onClick={handleCategoryPopupConfirm}>
                 Save
@@ -82,7 +82,19 @@ export interface OutputStyle {
 export type SkillSourceType = 'official' | 'marketplace' | 'community' | 'local';
 
 /** Schema/structure used by a skill source repository */
-export type SkillSchema = 'standard' | 'custom';
+export type SkillSchema = 'standard' | 'custom' | 'marketplace';
+
+/** How skill ids are derived from the directories a layout matches */
+export type SkillIdFrom = 'dirName' | 'wildcards' | 'frontmatter';
+
+/** Where skills live in a source using the custom schema */
+export interface SkillLayout {
+  /** Glob patterns for skill directories (`*`, `?`, `**`, `.` = root) */
+  roots: string[];
+  /** File that defines a skill (default: SKILL.md) */
+  skillFile?: string;
+  idFrom?: SkillIdFrom;
+}
 
 export interface SkillSource {
   id: string;
@@ -95,9 +107,46 @@ export interface SkillSource {
   fetchable: boolean;
   /** The schema/structure used by this source */
   schema: SkillSchema;
+  /** Branch, tag or commit to fetch from (default: the repo's default branch) */
+  gitRef?: string;
+  /** Where skills live when schema is custom */
+  layout?: SkillLayout;
   skillCount?: number;
 }
 
+export type ScanPolicy = 'block' | 'warn';
+
+export type RiskLevel = 'low' | 'medium' | 'high';
+
+export type RiskKind =
+  | 'executableScript'
+  | 'remoteExecution'
+  | 'suspiciousUrl'
+  | 'promptInjection'
+  | 'hiddenUnicode';
+
+export interface RiskFinding {
+  kind: RiskKind;
+  level: RiskLevel;
+  /** File relative to the skill directory */
+  file: string;
+  line?: number;
+  message: string;
+  /** The offending line, with hidden characters made visible */
+  excerpt?: string;
+}
+
+/** Security scan of a skill before install */
+export interface SecurityReport {
+  skillId: string;
+  findings: RiskFinding[];
+  risk?: RiskLevel;
+  /** Acknowledgements are tied to the scanned content */
+  fingerprint: string;
+  policy: ScanPolicy;
+  blocked: boolean;
+}
+
 export interface RemoteSkill {
   id: string;
   name: string;
@@ -118,7 +167,10 @@ export interface Skill {
   name: string;
   description: string;
   enabled: boolean;
-  category: SkillCategory;
+  /** Id of a SkillCategory */
+  category: string;
+  /** Free-form tags */
+  tags: string[];
   path: string;
   content: string;
   createdAt?: string;
@@ -126,21 +178,153 @@ export interface Skill {
   sourceId?: string;
   sourceName?: string;
   isModified: boolean;
+  version?: string;
+  sourceCommit?: string;
+  /** Skills this one builds on (`requires` in the frontmatter) */
+  requires?: string[];
+  /** Skills that can't be used together with this one */
+  conflicts?: string[];
+}
+
+export interface SkillUpdate {
+  skillId: string;
+  sourceId: string;
+  currentVersion: string | null;
+  latestVersion: string | null;
+  currentCommit: string | null;
+  latestCommit: string | null;
+  isModified: boolean;
+}
+
+export interface SkillUpdateCheck {
+  updates: SkillUpdate[];
+  /** Sources and skills that couldn't be checked */
+  warnings: string[];
+}
+
+export type MergeStatus = 'unchanged' | 'updated' | 'added' | 'removed' | 'merged' | 'conflict';
+
+export interface SkillFileMerge {
+  path: string;
+  status: MergeStatus;
+}
+
+export interface SkillUpgradeResult {
+  skillId: string;
+  version: string | null;
+  commit: string | null;
+  files: SkillFileMerge[];
+  conflicts: number;
+}
+
+export interface SkillRenameResult {
+  skill: Skill;
+  /** Profiles whose skill lists or auto-invoke rules were updated */
+  profiles: string[];
+  /** Skills whose requires/conflicts were rewritten to the new id */
+  references: string[];
+}
+
+// ============================================
+// Plugin Marketplaces
+// ============================================
+
+export interface PluginComponents {
+  skills: string[];
+  commands: string[];
+  agents: string[];
+  mcpServers: string[];
+  /** Listed only; hooks are never installed */
+  hooks: boolean;
+}
+
+export interface MarketplacePlugin {
+  name: string;
+  description: string;
+  version: string | null;
+  author: string | null;
+  category: string | null;
+  keywords: string[];
+  source: string;
+  components: PluginComponents;
+}
+
+export interface MarketplaceCatalog {
+  name: string;
+  description: string | null;
+  owner: string | null;
+  gitRef: string;
+  plugins: MarketplacePlugin[];
+}
+
+/** What installing a plugin would run, shown before installing it */
+export interface PluginReview {
+  plugin: string;
+  /** Scan of the plugin's commands and agents (skillId is `<source>/<plugin>`) */
+  report: SecurityReport;
+  /** MCP servers that requesting them would add */
+  mcpServers: Record<string, McpServer>;
+}
+
+export interface PluginInstallOptions {
+  /** Add the plugin's MCP servers; they run arbitrary commands */
+  mcpServers: boolean;
+}
+
+export interface PluginInstallResult {
+  plugin: string;
+  skills: string[];
+  commands: string[];
+  agents: string[];
+  mcpServers: Record<string, McpServer>;
+  skipped: string[];
+}
+
+/** Skill category, defined in .skills-config.json */
+export interface SkillCategory {
+  id: string;
+  name: string;
+  /** Position in listings, lowest first */
+  order: number;
+  /** Badge color (any CSS color) */
+  color: string;
+}
+
+export interface SkillFilter {
+  category?: string;
+  /** Skills must carry every tag */
+  tags?: string[];
 }
 
-export type SkillCategory =
-  | 'corporate'
-  | 'frontend'
-  | 'testing'
-  | 'ai-sdk'
-  | 'utilities'
-  | 'custom';
+export interface SearchOptions {
+  /** Search installed skills (default true) */
+  local?: boolean;
+  /** Search the cached catalogs of enabled sources (default true) */
+  remote?: boolean;
+  sourceId?: string;
+  /** 0 = no limit (default 20) */
+  limit?: number;
+}
+
+export interface SearchResult {
+  skillId: string;
+  name: string;
+  description: string;
+  tags: string[];
+  sourceId?: string;
+  sourceName?: string;
+  installed: boolean;
+  score: number;
+  /** Skill content around the first match */
+  snippet?: string;
+}
 
 export interface CreateSkillInput {
   id: string;
   name: string;
   description: string;
-  category: SkillCategory;
+  /** Category id */
+  category: string;
   content: string;
 }
 
@@ -149,7 +333,8 @@ export interface UpdateSkillInput {
   description?: string;
   content?: string;
   enabled?: boolean;
-  category?: SkillCategory;
+  category?: string;
+  tags?: string[];
 }
 
 export interface InstallSkillInput {
@@ -293,6 +478,10 @@ export interface Profile {
   generateCopilot: boolean;
   /** Generate AGENTS.md as master file */
   generateAgents: boolean;
+  /** Token budget for the profile's context (default 30000) */
+  tokenBudget?: number;
+  /** Output style installed with a project profile */
+  outputStyle?: string;
   createdAt: string;
   updatedAt: string;
 }
@@ -306,6 +495,9 @@ export interface CreateProfileInput {
   instructions?: string;
   generateCopilot?: boolean;
   generateAgents?: boolean;
+  /** Token budget; omitted or 0 uses the default */
+  tokenBudget?: number;
+  outputStyle?: string;
 }
 
 export interface UpdateProfileInput {
@@ -315,6 +507,29 @@ export interface UpdateProfileInput {
   instructions?: string;
   generateCopilot?: boolean;
   generateAgents?: boolean;
+  /** 0 goes back to the default budget */
+  tokenBudget?: number;
+  /** Output style id; empty removes it */
+  outputStyle?: string;
+}
+
+/** Estimated size of a skill, output style or instruction file */
+export interface TokenEstimate {
+  id: string;
+  tokens: number;
+  files: string[];
+}
+
+export interface ProfileTokenReport {
+  profileId: string;
+  /** Skills of the profile, including dependencies */
+  skills: TokenEstimate[];
+  outputStyle?: TokenEstimate;
+  /** CLAUDE.md and AGENTS.md */
+  instructions: TokenEstimate[];
+  total: number;
+  budget: number;
+  overBudget: boolean;
 }
 
 export interface ProfileInstallResult {
@@ -342,6 +557,10 @@ export interface SkillInstallError {
 
 export interface ConfigManifest {
   version: string;
+  /** Bundle layout version (1 = bundles that predate versioning) */
+  formatVersion: number;
+  /** Oldest CLI able to apply the bundle */
+  minCliVersion?: string;
   createdAt: string;
   profilesCount: number;
   skillsCount: number;
@@ -350,8 +569,12 @@ export interface ConfigManifest {
   outputStylesCount: number;
 }
 
+/** Release channel, from most to least stable */
+export type ReleaseChannel = 'stable' | 'beta' | 'canary';
+
 export interface DeployResult {
   version: string;
+  channel: ReleaseChannel;
   releaseUrl: string;
   assetUrl: string;
   manifest: ConfigManifest;
@@ -364,4 +587,59 @@ export interface SyncResult {
   instructionsInstalled: boolean;
   settingsInstalled: boolean;
   outputStylesInstalled: number;
+  /** Items modified locally that the synced bundle also changed */
+  conflicts: SyncConflict[];
+  /** Compatibility warnings (e.g. the bundle expects a newer CLI) */
+  warnings: string[];
+}
+
+/** How to resolve a locally modified item during sync */
+export type ConflictStrategy = 'keep-local' | 'take-remote' | 'write-remote';
+
+export interface SyncOptions {
+  defaultStrategy: ConflictStrategy;
+  /** Per-item overrides, keyed by item id (e.g. "skills/react-patterns") */
+  itemStrategies: Record<string, ConflictStrategy>;
+  /** Channel to sync from instead of this machine's channel */
+  channel?: ReleaseChannel;
+}
+
+export interface SyncConflict {
+  item: string;
+  files: string[];
+  resolution: ConflictStrategy;
+}
+
+// ============================================
+// Config Diff Types
+// ============================================
+
+export type ChangeKind = 'added' | 'removed' | 'modified';
+
+export interface ConfigDiff {
+  profilesAdded: string[];
+  profilesRemoved: string[];
+  profileChanges: {
+    profileId: string;
+    skillsAssigned: string[];
+    skillsUnassigned: string[];
+  }[];
+  skillsAdded: string[];
+  skillsRemoved: string[];
+  skillChanges: {
+    skillId: string;
+    additions: number;
+    deletions: number;
+    /** Unified diff of SKILL.md */
+    diff: string;
+  }[];
+  permissionChanges: {
+    list: 'allow' | 'ask' | 'deny';
+    rule: string;
+    change: ChangeKind;
+  }[];
+  mcpChanges: {
+    name: string;
+    change: ChangeKind;
+  }[];
 }